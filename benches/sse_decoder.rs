@@ -0,0 +1,133 @@
+use bytes::{Buf, BytesMut};
+use criterion::{
+    criterion_group, criterion_main, BenchmarkId, Criterion, Throughput,
+};
+
+use clust::messages::SseDecoder;
+
+/// Builds a stream of text delta events of about the size.
+fn text_delta_stream(size: usize) -> Vec<u8> {
+    let event = "event: content_block_delta\r\ndata: {\"type\": \"content_block_delta\", \"index\": 0, \"delta\": {\"type\": \"text_delta\", \"text\": \"こんにちは, world!\"}}\r\n\r\n";
+    event
+        .repeat(size / event.len() + 1)
+        .into_bytes()
+}
+
+/// Builds a stream of a single event with a long data line of about the size.
+fn long_line_stream(size: usize) -> Vec<u8> {
+    format!(
+        "event: content_block_delta\ndata: {{\"type\": \"content_block_delta\", \"index\": 0, \"delta\": {{\"type\": \"input_json_delta\", \"partial_json\": \"{}\"}}}}\n\n",
+        "a".repeat(size)
+    )
+    .into_bytes()
+}
+
+fn decode(
+    source: &[u8],
+    frame_size: usize,
+) -> usize {
+    let mut decoder = SseDecoder::new();
+    let mut count = 0;
+    for frame in source.chunks(frame_size) {
+        decoder.feed(frame).unwrap();
+        while decoder.next_event().is_some() {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Decodes the events as the previous chunk stream: rescans the buffer for `\n\n` from the start
+/// on every frame and copies each event by `.to_vec()`.
+fn decode_by_rescan(
+    source: &[u8],
+    frame_size: usize,
+) -> usize {
+    let mut buffer = BytesMut::new();
+    let mut count = 0;
+    for frame in source.chunks(frame_size) {
+        buffer.extend_from_slice(frame);
+        while let Some(position) = buffer
+            .iter()
+            .position(|b| *b == b'\n')
+        {
+            let Some(end) = buffer[position + 1..]
+                .iter()
+                .position(|b| *b == b'\n')
+            else {
+                break;
+            };
+            let chunk = buffer
+                .split_to(position + end + 2)
+                .to_vec();
+            if !buffer.is_empty() {
+                buffer.advance(1);
+            }
+            if String::from_utf8(chunk).is_ok() {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn bench_sse_decoder(criterion: &mut Criterion) {
+    const SIZE: usize = 4 * 1024 * 1024;
+
+    let streams = [
+        ("text_delta", text_delta_stream(SIZE)),
+        ("long_line", long_line_stream(SIZE)),
+    ];
+
+    let mut group = criterion.benchmark_group("sse_decoder");
+    group.sample_size(10);
+    for (name, source) in streams.iter() {
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        for frame_size in [16, 1024, 16 * 1024] {
+            group.bench_with_input(
+                BenchmarkId::new(*name, frame_size),
+                &frame_size,
+                |bencher, frame_size| {
+                    bencher.iter(|| decode(source, *frame_size))
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_baseline(criterion: &mut Criterion) {
+    // The rescan is quadratic in the line length, so the stream is smaller.
+    const SIZE: usize = 256 * 1024;
+
+    let streams = [
+        ("text_delta", text_delta_stream(SIZE)),
+        ("long_line", long_line_stream(SIZE)),
+    ];
+
+    let mut group = criterion.benchmark_group("sse_decoder_baseline");
+    group.sample_size(10);
+    for (name, source) in streams.iter() {
+        group.throughput(Throughput::Bytes(source.len() as u64));
+        for frame_size in [1024, 16 * 1024] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}/rescan", name), frame_size),
+                &frame_size,
+                |bencher, frame_size| {
+                    bencher.iter(|| decode_by_rescan(source, *frame_size))
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}/decoder", name), frame_size),
+                &frame_size,
+                |bencher, frame_size| {
+                    bencher.iter(|| decode(source, *frame_size))
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sse_decoder, bench_baseline);
+criterion_main!(benches);
@@ -99,7 +99,7 @@ mod tool;
 /// let result = tool.call(tool_use).unwrap();
 /// assert_eq!(result.tool_use_id, "toolu_XXXX");
 /// assert_eq!(result.is_error, None);
-/// assert_eq!(result.content.unwrap().text, "43");
+/// assert_eq!(result.content.unwrap().flatten_into_text().unwrap(), "43");
 /// ```
 #[proc_macro_attribute]
 pub fn clust_tool(
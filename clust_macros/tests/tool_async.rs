@@ -51,5 +51,5 @@ async fn test_call() {
 
     assert_eq!(result.tool_use_id, "toolu_XXXX");
     assert_eq!(result.is_error, None);
-    assert_eq!(result.content.unwrap().text, "43");
+    assert_eq!(result.content.unwrap().flatten_into_text().unwrap(), "43");
 }
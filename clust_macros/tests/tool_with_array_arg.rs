@@ -63,5 +63,5 @@ fn test_call() {
 
     assert_eq!(result.tool_use_id, "toolu_XXXX");
     assert_eq!(result.is_error, None);
-    assert_eq!(result.content.unwrap().text, "10");
+    assert_eq!(result.content.unwrap().flatten_into_text().unwrap(), "10");
 }
@@ -53,7 +53,7 @@ fn test_call() {
 
     assert_eq!(result.tool_use_id, "toolu_XXXX");
     assert_eq!(result.is_error, None);
-    assert_eq!(result.content.unwrap().text, "0");
+    assert_eq!(result.content.unwrap().flatten_into_text().unwrap(), "0");
 
     let tool_use = ToolUse::new(
         "toolu_XXXX",
@@ -67,5 +67,5 @@ fn test_call() {
 
     assert_eq!(result.tool_use_id, "toolu_XXXX");
     assert_eq!(result.is_error, None);
-    assert_eq!(result.content.unwrap().text, "42");
+    assert_eq!(result.content.unwrap().flatten_into_text().unwrap(), "42");
 }
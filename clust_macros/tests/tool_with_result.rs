@@ -73,7 +73,7 @@ fn test_call() {
 
     assert_eq!(result.tool_use_id, "toolu_XXXX");
     assert_eq!(result.is_error, None);
-    assert_eq!(result.content.unwrap().text, "43");
+    assert_eq!(result.content.unwrap().flatten_into_text().unwrap(), "43");
 
     let tool_use = ToolUse::new(
         "toolu_XXXX",
@@ -85,5 +85,5 @@ fn test_call() {
 
     assert_eq!(result.tool_use_id, "toolu_XXXX");
     assert_eq!(result.is_error, Some(true));
-    assert_eq!(result.content.unwrap().text, "arg1 is negative");
+    assert_eq!(result.content.unwrap().flatten_into_text().unwrap(), "arg1 is negative");
 }
@@ -11,13 +11,11 @@
 
 use std::path::PathBuf;
 
-use base64::Engine;
 use clap::Parser;
 
 use clust::messages::ClaudeModel;
 use clust::messages::ContentBlock;
 use clust::messages::ImageContentSource;
-use clust::messages::ImageMediaType;
 use clust::messages::MaxTokens;
 use clust::messages::Message;
 use clust::messages::MessagesRequestBody;
@@ -44,13 +42,10 @@ async fn main() -> anyhow::Result<()> {
     // or specify the API key directly
     // let client = Client::from_api_key(clust::ApiKey::new("your-api-key"));
 
-    // 2. Read image file and encode it to Base64.
-    let image_file = tokio::fs::read(&arguments.image_path).await?;
-    let image_base64 = base64::prelude::BASE64_STANDARD.encode(&image_file);
-    let image_source = ImageContentSource::base64(
-        ImageMediaType::from_path(&PathBuf::from(&arguments.image_path))?,
-        image_base64,
-    );
+    // 2. Load the image file, detect its media type and encode it to Base64.
+    let image_source = ImageContentSource::from_path(&PathBuf::from(
+        &arguments.image_path,
+    ))?;
 
     // 3. Create a request body.
     let model = ClaudeModel::Claude3Sonnet20240229;
@@ -10,6 +10,8 @@
 //! ```
 
 use clust::messages::ClaudeModel;
+use clust::messages::ContentBlockDelta;
+use clust::messages::ContentBlockDeltaChunk;
 use clust::messages::MaxTokens;
 use clust::messages::Message;
 use clust::messages::MessageChunk;
@@ -69,8 +71,11 @@ async fn main() -> anyhow::Result<()> {
             | Ok(chunk) => {
                 println!("Chunk:\n{}", chunk);
                 match chunk {
-                    | MessageChunk::ContentBlockDelta(content_block_delta) => {
-                        buffer.push_str(&content_block_delta.delta.text);
+                    | MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk {
+                        delta: ContentBlockDelta::TextDelta(text_delta),
+                        ..
+                    }) => {
+                        buffer.push_str(&text_delta.text);
                     },
                     | _ => {},
                 }
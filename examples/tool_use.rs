@@ -21,7 +21,7 @@ use clust_macros::clust_tool;
 /// - `location` - The city and state, e.g. San Francisco, CA
 #[clust_tool]
 fn get_weather(location: String) -> String {
-    "15 degrees".to_string() // Dummy response
+    format!("15 degrees in {}", location) // Dummy response
 }
 
 #[tokio::main]
@@ -35,7 +35,7 @@ async fn main() -> anyhow::Result<()> {
     let tool_increment = ClustTool_get_weather {}; // function_name -> Generate ClustTool_function_name struct that implements Tool
     let tools = ToolList::new(vec![Box::new(
         tool_increment,
-    )]);
+    )])?;
 
     // 3. Create a request body.
     let model = ClaudeModel::Claude35Sonnet20240620;
@@ -3,18 +3,13 @@ use std::fmt::Display;
 /// The beta feature.
 ///
 /// See also [the API reference](https://docs.anthropic.com/claude/reference/versions).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Beta {
     /// tools-2024-04-04
+    #[default]
     Tools2024_04_04,
 }
 
-impl Default for Beta {
-    fn default() -> Self {
-        Self::Tools2024_04_04
-    }
-}
-
 impl Display for Beta {
     fn fmt(
         &self,
@@ -1,9 +1,11 @@
 use futures_core::Stream;
 use reqwest::RequestBuilder;
+use std::io::Write;
+use std::time::Duration;
 
 use crate::messages::{
     MessageChunk, MessagesError, MessagesRequestBody, MessagesResponseBody,
-    StreamError,
+    StreamError, StreamRecorder,
 };
 use crate::{ApiKey, Beta, Version};
 
@@ -18,6 +20,10 @@ pub struct Client {
     client: reqwest::Client,
     /// Beta feature.
     beta: Option<Beta>,
+    /// The idle timeout of the message stream.
+    stream_idle_timeout: Option<Duration>,
+    /// The timeout to the first event of the message stream.
+    stream_first_event_timeout: Option<Duration>,
 }
 
 impl Client {
@@ -39,6 +45,8 @@ impl Client {
             version,
             client,
             beta: None,
+            stream_idle_timeout: None,
+            stream_first_event_timeout: None,
         })
     }
 
@@ -64,6 +72,8 @@ impl Client {
             version,
             client,
             beta: None,
+            stream_idle_timeout: None,
+            stream_first_event_timeout: None,
         }
     }
 
@@ -87,6 +97,16 @@ impl Client {
 
         builder
     }
+
+    /// The idle timeout of the message stream.
+    pub(crate) fn stream_idle_timeout(&self) -> Option<Duration> {
+        self.stream_idle_timeout
+    }
+
+    /// The timeout to the first event of the message stream.
+    pub(crate) fn stream_first_event_timeout(&self) -> Option<Duration> {
+        self.stream_first_event_timeout
+    }
 }
 
 impl Client {
@@ -195,6 +215,73 @@ impl Client {
     > {
         crate::messages::api::create_a_message_stream(self, request_body).await
     }
+
+    /// Create a Message with incrementally streaming the response using server-sent events (SSE),
+    /// and record the raw bytes of the event stream to the recorder.
+    ///
+    /// The recording can be replayed by `clust::messages::StreamReplay` without the network.
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `recorder` - The recorder of the raw bytes.
+    ///
+    /// ## NOTE
+    /// The `stream` option must be `StreamOption::ReturnStream`.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::messages::{MessagesRequestBody, ClaudeModel, Message, MaxTokens, RecordingFormat, StreamOption, StreamRecorder};
+    /// use tokio_stream::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///     let model = ClaudeModel::Claude3Sonnet20240229;
+    ///     let max_tokens = MaxTokens::new(1024, model)?;
+    ///     let request_body = MessagesRequestBody {
+    ///         model,
+    ///         max_tokens,
+    ///         messages: vec![
+    ///             Message::user("Hello, Claude!"),
+    ///         ],
+    ///         stream: Some(StreamOption::ReturnStream),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let file = std::fs::File::create("stream.jsonl")?;
+    ///     let mut stream = client
+    ///         .create_a_message_stream_with_recorder(
+    ///             request_body,
+    ///             StreamRecorder::new(file, RecordingFormat::Timed),
+    ///         )
+    ///         .await?;
+    ///
+    ///     while let Some(chunk) = stream.next().await {
+    ///         // Process the chunk.
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_a_message_stream_with_recorder<W>(
+        &self,
+        request_body: MessagesRequestBody,
+        recorder: StreamRecorder<W>,
+    ) -> Result<
+        impl Stream<Item = Result<MessageChunk, StreamError>>,
+        MessagesError,
+    >
+    where
+        W: Write,
+    {
+        crate::messages::api::create_a_message_stream_with_recorder(
+            self,
+            request_body,
+            recorder,
+        )
+        .await
+    }
 }
 
 /// The builder of `Client`.
@@ -222,6 +309,10 @@ pub struct ClientBuilder {
     client: Option<reqwest::Client>,
     /// Beta feature.
     beta: Option<Beta>,
+    /// The idle timeout of the message stream.
+    stream_idle_timeout: Option<Duration>,
+    /// The timeout to the first event of the message stream.
+    stream_first_event_timeout: Option<Duration>,
 }
 
 impl ClientBuilder {
@@ -232,6 +323,8 @@ impl ClientBuilder {
             version: None,
             client: None,
             beta: None,
+            stream_idle_timeout: None,
+            stream_first_event_timeout: None,
         }
     }
 
@@ -269,6 +362,30 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the idle timeout of the message stream.
+    ///
+    /// The stream fails with `StreamError::IdleTimeout` when no byte arrives for the duration.
+    /// The timer is reset by any byte, including the `ping` events,
+    /// so it detects a stalled connection without limiting the total time of a long generation.
+    pub fn stream_idle_timeout(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout to the first event of the message stream.
+    ///
+    /// The stream fails with `StreamError::FirstEventTimeout` when no event arrives for the duration from the first poll.
+    pub fn stream_first_event_timeout(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.stream_first_event_timeout = Some(timeout);
+        self
+    }
+
     /// Builds the API client.
     pub fn build(self) -> Client {
         let version = self
@@ -276,13 +393,15 @@ impl ClientBuilder {
             .unwrap_or_default();
         let client = self
             .client
-            .unwrap_or_else(|| reqwest::Client::new());
+            .unwrap_or_default();
 
         Client {
             api_key: self.api_key,
             version,
             client,
             beta: self.beta,
+            stream_idle_timeout: self.stream_idle_timeout,
+            stream_first_event_timeout: self.stream_first_event_timeout,
         }
     }
 }
@@ -319,5 +438,18 @@ mod tests {
             .build();
         assert_eq!(client.api_key.value(), "api-key");
         assert_eq!(client.beta, Some(Beta::Tools2024_04_04));
+
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .stream_idle_timeout(Duration::from_secs(30))
+            .stream_first_event_timeout(Duration::from_secs(60))
+            .build();
+        assert_eq!(
+            client.stream_idle_timeout(),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            client.stream_first_event_timeout(),
+            Some(Duration::from_secs(60))
+        );
     }
 }
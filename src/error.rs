@@ -90,11 +90,14 @@ impl Display for ApiError {
 
 impl ApiError {
     /// Creates a new API error.
+    ///
+    /// The type of the error is classified by the type in the response, or by the status code if it is unknown.
     pub(crate) fn new(
         status: StatusCode,
         response: ApiErrorResponse,
     ) -> Self {
-        let _type = ApiErrorType::from(status);
+        let _type = ApiErrorType::from_name(&response.error._type)
+            .unwrap_or_else(|| ApiErrorType::from(status));
         Self {
             status,
             _type,
@@ -182,6 +185,30 @@ impl Display for ApiErrorType {
     }
 }
 
+impl ApiErrorType {
+    /// Gets the error type from the name, e.g. `overloaded_error`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            | "invalid_request_error" => Some(Self::InvalidRequestError),
+            | "authentication_error" => Some(Self::AuthenticationError),
+            | "permission_error" => Some(Self::PermissionError),
+            | "not_found_error" => Some(Self::NotFoundError),
+            | "rate_limit_error" => Some(Self::RateLimitError),
+            | "api_error" => Some(Self::ApiError),
+            | "overloaded_error" => Some(Self::OverloadedError),
+            | _ => None,
+        }
+    }
+
+    /// Whether the request can be retried later, i.e. the error is not caused by the request.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimitError | Self::ApiError | Self::OverloadedError
+        )
+    }
+}
+
 impl From<StatusCode> for ApiErrorType {
     fn from(status: StatusCode) -> Self {
         if status == StatusCode::from_u16(529).unwrap() {
@@ -8,6 +8,11 @@
 //! ## Feature flags
 //! - `macros`: Enable the [`attributes::clust_tool`] attribute macro for generating [`messages::ToolDefinition`]
 //!   or [`messages::AsyncTool`] from a Rust function.
+//! - `image`: Enable the image preprocessing, e.g. `messages::ImageContentSource::preprocess_path`, that downscales
+//!   an image to the recommended size and estimates the input tokens of it.
+//! - `axum`: Enable `messages::SseResponse` for relaying a message stream to downstream clients as an `axum` response.
+//! - `validation` (default): Enable the validation of the tool use input against the input schema, compiled once when the tool is added,
+//!   in [`messages::ToolList`] and [`messages::AsyncToolList`].
 //!
 //! ## Usages
 //!
@@ -392,6 +397,8 @@
 //! use clust::messages::SystemPrompt;
 //! use clust::messages::StreamOption;
 //! use clust::messages::MessageChunk;
+//! use clust::messages::ContentBlockDelta;
+//! use clust::messages::ContentBlockDeltaChunk;
 //! use clust::Client;
 //!
 //! use tokio_stream::StreamExt;
@@ -431,9 +438,12 @@
 //!             | Ok(chunk) => {
 //!                 println!("Chunk:\n{}", chunk);
 //!                 match chunk {
-//!                     | MessageChunk::ContentBlockDelta(content_block_delta) => {
+//!                     | MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk {
+//!                         delta: ContentBlockDelta::TextDelta(text_delta),
+//!                         ..
+//!                     }) => {
 //!                         // Buffer message delta.
-//!                         buffer.push_str(&content_block_delta.delta.text);
+//!                         buffer.push_str(&text_delta.text);
 //!                     }
 //!                     | _ => {}
 //!                 }
@@ -3,7 +3,39 @@
 /// ## Arguments
 /// - `$enum_name`: The name of the enum.
 /// - `$($variant:ident => $str:expr),*`: The variants of the enum and their corresponding string representations.
+/// - `_ => $fallback:ident`: Optional, the [`String`] variant that keeps an unknown string instead of failing.
 macro_rules! impl_enum_string_serialization {
+    ($enum_name:ident, $($variant:ident => $str:expr),* ; _ => $fallback:ident) => {
+        impl serde::Serialize for $enum_name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    $(
+                        $enum_name::$variant => serializer.serialize_str($str),
+                    )*
+                    $enum_name::$fallback(ref value) => serializer.serialize_str(value),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $enum_name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+
+                match value.as_str() {
+                    $(
+                        $str => Ok($enum_name::$variant),
+                    )*
+                    _ => Ok($enum_name::$fallback(value)),
+                }
+            }
+        }
+    };
     ($enum_name:ident, $($variant:ident => $str:expr),*) => {
         impl serde::Serialize for $enum_name {
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -63,7 +95,52 @@ pub(crate) use impl_enum_string_serialization;
 /// - `$enum_name`: The name of the enum.
 /// - `$tag_field`: The name of the [`String`] field that contains the tag that indicates the variant.
 /// - `$( $variant:ident($struct:ident, $tag:expr) ),*`: The variants of the enum and their corresponding structs and tags.
+/// - `_ => $fallback:ident`: Optional, the [`serde_json::Value`] variant that keeps an unknown tag instead of failing.
 macro_rules! impl_enum_struct_serialization {
+    ($enum_name:ident, $tag_field:ident, $( $variant:ident($struct:ident, $tag:expr) ),* ; _ => $fallback:ident) => {
+        impl serde::Serialize for $enum_name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    $(
+                        $enum_name::$variant(ref inner) => inner.serialize(serializer),
+                    )*
+                    $enum_name::$fallback(ref value) => value.serialize(serializer),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $enum_name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = serde_json::Value::deserialize(deserializer)?;
+
+                let tag = value.get(stringify!($tag_field)).and_then(serde_json::Value::as_str)
+                    .ok_or_else(|| serde::de::Error::missing_field(stringify!($tag_field)))?;
+
+                match tag {
+                    $(
+                        $tag => serde_json::from_value(value.clone())
+                            .map($enum_name::$variant)
+                            .map_err(serde::de::Error::custom),
+                    )*
+                    _ => Ok($enum_name::$fallback(value)),
+                }
+            }
+        }
+
+        $(
+            impl From<$struct> for $enum_name {
+                fn from(item: $struct) -> Self {
+                    $enum_name::$variant(item)
+                }
+            }
+        )*
+    };
     ($enum_name:ident, $tag_field:ident, $( $variant:ident($struct:ident, $tag:expr) ),* ) => {
         impl serde::Serialize for $enum_name {
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1,59 +1,148 @@
 //! The [Messages API](https://docs.anthropic.com/claude/reference/messages_post) implementations.
 
+mod anthropic_tool;
+mod async_tool_list;
+mod cache_control;
+mod cache_planner;
 mod chunk_stream;
+mod citation;
 mod claude_model;
 mod content;
 mod error;
+#[cfg(feature = "image")]
+mod image_processing;
 mod max_tokens;
 mod message;
+mod message_accumulator;
 mod message_chunk;
+mod message_stream;
 mod messages_request_body;
 mod messages_response_body;
 mod metadata;
 mod role;
+mod server_tool_result;
+mod sse;
+#[cfg(feature = "axum")]
+mod sse_response;
 mod stop_reason;
 mod stop_sequence;
 mod stream_option;
+mod stream_recording;
 mod system_prompt;
 mod temperature;
+mod thinking;
 mod top_k;
 mod top_p;
 mod usage;
 
 pub(crate) mod api;
 mod tool;
+mod tool_choice;
+mod tool_index;
+#[cfg(feature = "validation")]
+mod tool_input_validation;
+mod tool_runner;
 
+pub use anthropic_tool::BashTool;
+pub use anthropic_tool::BashToolType;
+pub use anthropic_tool::CodeExecutionTool;
+pub use anthropic_tool::CodeExecutionToolType;
+pub use anthropic_tool::ComputerUseTool;
+pub use anthropic_tool::ComputerUseToolType;
+pub use anthropic_tool::TextEditorTool;
+pub use anthropic_tool::TextEditorToolType;
+pub use anthropic_tool::UserLocation;
+pub use anthropic_tool::UserLocationType;
+pub use anthropic_tool::WebSearchTool;
+pub use anthropic_tool::WebSearchToolType;
+pub use async_tool_list::AsyncToolList;
+pub use async_tool_list::BlockingTool;
+pub use async_tool_list::DynAsyncTool;
+pub use async_tool_list::ToolCallFuture;
+pub use cache_control::CacheControl;
+pub use cache_control::CacheControlType;
+pub use cache_control::CacheTtl;
+pub use cache_planner::CacheBreakpoint;
+pub use cache_planner::CacheBreakpointPosition;
+pub use cache_planner::CachePlan;
+pub use cache_planner::CachePlanner;
+pub use cache_planner::ExpectedCacheUsage;
+pub use cache_planner::MAX_CACHE_BREAKPOINTS;
+pub use citation::CharLocationCitation;
+pub use citation::Citation;
+pub use citation::CitationType;
+pub use citation::CitationsOption;
+pub use citation::ContentBlockLocationCitation;
+pub use citation::PageLocationCitation;
+pub use citation::SearchResultLocationCitation;
+pub use citation::WebSearchResultLocationCitation;
 pub use claude_model::ClaudeModel;
+pub use content::Base64DocumentSource;
+pub use content::Base64ImageSource;
+pub use content::CodeExecutionToolResultContentBlock;
 pub use content::Content;
 pub use content::ContentBlock;
+pub use content::ContentDocumentSource;
 pub use content::ContentType;
+pub use content::DocumentContentBlock;
+pub use content::DocumentContentSource;
+pub use content::DocumentMediaType;
+pub use content::DocumentSourceType;
 pub use content::ImageContentBlock;
 pub use content::ImageContentSource;
 pub use content::ImageMediaType;
 pub use content::ImageSourceType;
+pub use content::PlainTextDocumentSource;
+pub use content::RedactedThinkingContentBlock;
+pub use content::ServerToolUseContentBlock;
 pub use content::TextContentBlock;
+pub use content::ThinkingContentBlock;
 pub use content::ToolResultContentBlock;
 pub use content::ToolUseContentBlock;
+pub use content::UrlDocumentSource;
+pub use content::UrlImageSource;
+pub use content::WebSearchToolResultContentBlock;
 pub use error::ContentFlatteningError;
+pub use error::DocumentLoadError;
+pub use error::DocumentMediaTypeParseError;
+pub use error::ImageLoadError;
 pub use error::ImageMediaTypeParseError;
 pub use error::MessageChunkTypeError;
 pub use error::MessagesError;
 pub use error::StreamError;
 pub use error::ToolCallError;
+pub use error::ToolListError;
+pub use error::ToolRunnerError;
+#[cfg(feature = "image")]
+pub use image_processing::ImagePreprocessOptions;
+#[cfg(feature = "image")]
+pub use image_processing::PreprocessedImage;
 pub use max_tokens::MaxTokens;
 pub use message::Message;
+pub use message_accumulator::MessageAccumulator;
+pub use message_chunk::CitationsDeltaContentBlock;
+pub use message_chunk::ContentBlockDelta;
 pub use message_chunk::ContentBlockDeltaChunk;
 pub use message_chunk::ContentBlockStartChunk;
 pub use message_chunk::ContentBlockStopChunk;
 pub use message_chunk::DeltaUsage;
+pub use message_chunk::InputJsonDeltaContentBlock;
 pub use message_chunk::MessageChunk;
 pub use message_chunk::MessageChunkType;
 pub use message_chunk::MessageDeltaChunk;
 pub use message_chunk::MessageStartChunk;
 pub use message_chunk::MessageStopChunk;
 pub use message_chunk::PingChunk;
+pub use message_chunk::SignatureDeltaContentBlock;
 pub use message_chunk::StreamStop;
 pub use message_chunk::TextDeltaContentBlock;
+pub use message_chunk::ThinkingDeltaContentBlock;
+pub use message_stream::CollectMessage;
+pub use message_stream::HandleMessage;
+pub use message_stream::MessageStreamExt;
+pub use message_stream::MessageStreamHandler;
+pub use message_stream::SseBytes;
+pub use message_stream::TextStream;
 pub use messages_request_body::MessagesRequestBody;
 pub use messages_request_body::MessagesRequestBuilder;
 pub use messages_response_body::MessageObjectType;
@@ -61,17 +150,46 @@ pub use messages_response_body::MessagesResponseBody;
 pub use metadata::Metadata;
 pub use metadata::UserId;
 pub use role::Role;
+pub use server_tool_result::CodeExecutionOutput;
+pub use server_tool_result::CodeExecutionResult;
+pub use server_tool_result::CodeExecutionToolResultContent;
+pub use server_tool_result::CodeExecutionToolResultError;
+pub use server_tool_result::ServerToolResultType;
+pub use server_tool_result::WebSearchResult;
+pub use server_tool_result::WebSearchToolResultContent;
+pub use server_tool_result::WebSearchToolResultError;
+pub use sse::SseDecoder;
+pub use sse::SseEvent;
+#[cfg(feature = "axum")]
+pub use sse_response::SseResponse;
 pub use stop_reason::StopReason;
 pub use stop_sequence::StopSequence;
 pub use stream_option::StreamOption;
+pub use stream_recording::RecordingFormat;
+pub use stream_recording::StreamRecorder;
+pub use stream_recording::StreamReplay;
 pub use system_prompt::SystemPrompt;
 pub use temperature::Temperature;
+pub use thinking::Thinking;
+pub use thinking::ThinkingType;
+pub use thinking::MIN_THINKING_BUDGET_TOKENS;
 pub use tool::AsyncTool;
+pub use tool::CustomToolDefinition;
 pub use tool::Tool;
 pub use tool::ToolDefinition;
 pub use tool::ToolList;
 pub use tool::ToolResult;
+pub use tool::ToolResultBlock;
+pub use tool::ToolResultContent;
 pub use tool::ToolUse;
+pub use tool_choice::ToolChoice;
+pub use tool_choice::ToolChoiceType;
+#[cfg(feature = "validation")]
+pub use tool_input_validation::SchemaViolation;
+pub use tool_runner::ToolRun;
+pub use tool_runner::ToolRunStatus;
+pub use tool_runner::ToolRunner;
+pub use tool_runner::ToolRunnerHook;
 pub use top_k::TopK;
 pub use top_p::TopP;
 pub use usage::Usage;
@@ -0,0 +1,591 @@
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+use crate::messages::CacheControl;
+use std::fmt::Display;
+
+/// The bash tool that executes shell commands on the client side.
+///
+/// See [bash tool](https://docs.anthropic.com/en/docs/agents-and-tools/tool-use/bash-tool).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BashTool {
+    /// The versioned type of the tool.
+    #[serde(rename = "type")]
+    pub _type: BashToolType,
+    /// The name of the tool. It is always `bash`.
+    pub name: String,
+    /// The cache control of the tool definition.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for BashTool {
+    fn default() -> Self {
+        Self {
+            _type: BashToolType::default(),
+            name: "bash".to_string(),
+            cache_control: None,
+        }
+    }
+}
+
+impl_display_for_serialize!(BashTool);
+
+impl BashTool {
+    /// Creates a new bash tool of the latest version.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The versioned type of the bash tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BashToolType {
+    /// bash_20241022
+    Bash20241022,
+    /// bash_20250124
+    #[default]
+    Bash20250124,
+}
+
+impl Display for BashToolType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | BashToolType::Bash20241022 => {
+                write!(f, "bash_20241022")
+            },
+            | BashToolType::Bash20250124 => {
+                write!(f, "bash_20250124")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    BashToolType,
+    Bash20241022 => "bash_20241022",
+    Bash20250124 => "bash_20250124"
+);
+
+/// The text editor tool that views and modifies text files on the client side.
+///
+/// See [text editor tool](https://docs.anthropic.com/en/docs/agents-and-tools/tool-use/text-editor-tool).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TextEditorTool {
+    /// The versioned type of the tool.
+    #[serde(rename = "type")]
+    pub _type: TextEditorToolType,
+    /// The name of the tool that depends on the version.
+    pub name: String,
+    /// The maximum number of characters when viewing a file, only for `text_editor_20250728`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_characters: Option<u32>,
+    /// The cache control of the tool definition.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for TextEditorTool {
+    fn default() -> Self {
+        Self::with_version(TextEditorToolType::default())
+    }
+}
+
+impl_display_for_serialize!(TextEditorTool);
+
+impl TextEditorTool {
+    /// Creates a new text editor tool of the latest version.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new text editor tool of the version with the corresponding name.
+    pub fn with_version(version: TextEditorToolType) -> Self {
+        Self {
+            _type: version,
+            name: version.tool_name().to_string(),
+            max_characters: None,
+            cache_control: None,
+        }
+    }
+
+    /// Sets the maximum number of characters when viewing a file.
+    pub fn with_max_characters(
+        mut self,
+        max_characters: u32,
+    ) -> Self {
+        self.max_characters = Some(max_characters);
+        self
+    }
+}
+
+/// The versioned type of the text editor tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextEditorToolType {
+    /// text_editor_20241022
+    TextEditor20241022,
+    /// text_editor_20250124
+    TextEditor20250124,
+    /// text_editor_20250429
+    TextEditor20250429,
+    /// text_editor_20250728
+    #[default]
+    TextEditor20250728,
+}
+
+impl Display for TextEditorToolType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | TextEditorToolType::TextEditor20241022 => {
+                write!(f, "text_editor_20241022")
+            },
+            | TextEditorToolType::TextEditor20250124 => {
+                write!(f, "text_editor_20250124")
+            },
+            | TextEditorToolType::TextEditor20250429 => {
+                write!(f, "text_editor_20250429")
+            },
+            | TextEditorToolType::TextEditor20250728 => {
+                write!(f, "text_editor_20250728")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    TextEditorToolType,
+    TextEditor20241022 => "text_editor_20241022",
+    TextEditor20250124 => "text_editor_20250124",
+    TextEditor20250429 => "text_editor_20250429",
+    TextEditor20250728 => "text_editor_20250728"
+);
+
+impl TextEditorToolType {
+    /// The name of the tool required by the version.
+    pub fn tool_name(&self) -> &'static str {
+        match self {
+            | TextEditorToolType::TextEditor20241022
+            | TextEditorToolType::TextEditor20250124 => "str_replace_editor",
+            | TextEditorToolType::TextEditor20250429
+            | TextEditorToolType::TextEditor20250728 => {
+                "str_replace_based_edit_tool"
+            },
+        }
+    }
+}
+
+/// The computer use tool that controls a desktop environment on the client side.
+///
+/// See [computer use tool](https://docs.anthropic.com/en/docs/agents-and-tools/tool-use/computer-use-tool).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ComputerUseTool {
+    /// The versioned type of the tool.
+    #[serde(rename = "type")]
+    pub _type: ComputerUseToolType,
+    /// The name of the tool. It is always `computer`.
+    pub name: String,
+    /// The width of the display in pixels.
+    pub display_width_px: u32,
+    /// The height of the display in pixels.
+    pub display_height_px: u32,
+    /// The display number for X11 environments.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub display_number: Option<u32>,
+    /// The cache control of the tool definition.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for ComputerUseTool {
+    fn default() -> Self {
+        Self::new(1024, 768)
+    }
+}
+
+impl_display_for_serialize!(ComputerUseTool);
+
+impl ComputerUseTool {
+    /// Creates a new computer use tool of the latest version.
+    ///
+    /// ## Arguments
+    /// - `display_width_px` - The width of the display in pixels.
+    /// - `display_height_px` - The height of the display in pixels.
+    pub fn new(
+        display_width_px: u32,
+        display_height_px: u32,
+    ) -> Self {
+        Self {
+            _type: ComputerUseToolType::default(),
+            name: "computer".to_string(),
+            display_width_px,
+            display_height_px,
+            display_number: None,
+            cache_control: None,
+        }
+    }
+
+    /// Sets the display number for X11 environments.
+    pub fn with_display_number(
+        mut self,
+        display_number: u32,
+    ) -> Self {
+        self.display_number = Some(display_number);
+        self
+    }
+}
+
+/// The versioned type of the computer use tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ComputerUseToolType {
+    /// computer_20241022
+    Computer20241022,
+    /// computer_20250124
+    #[default]
+    Computer20250124,
+}
+
+impl Display for ComputerUseToolType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | ComputerUseToolType::Computer20241022 => {
+                write!(f, "computer_20241022")
+            },
+            | ComputerUseToolType::Computer20250124 => {
+                write!(f, "computer_20250124")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    ComputerUseToolType,
+    Computer20241022 => "computer_20241022",
+    Computer20250124 => "computer_20250124"
+);
+
+/// The web search tool that searches the web on the server side.
+///
+/// See [web search tool](https://docs.anthropic.com/en/docs/agents-and-tools/tool-use/web-search-tool).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WebSearchTool {
+    /// The versioned type of the tool.
+    #[serde(rename = "type")]
+    pub _type: WebSearchToolType,
+    /// The name of the tool. It is always `web_search`.
+    pub name: String,
+    /// The maximum number of searches per request.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_uses: Option<u32>,
+    /// The domains to include in the search results.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub allowed_domains: Option<Vec<String>>,
+    /// The domains to exclude from the search results.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub blocked_domains: Option<Vec<String>>,
+    /// The location of the user to localize the search results.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub user_location: Option<UserLocation>,
+    /// The cache control of the tool definition.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for WebSearchTool {
+    fn default() -> Self {
+        Self {
+            _type: WebSearchToolType::default(),
+            name: "web_search".to_string(),
+            max_uses: None,
+            allowed_domains: None,
+            blocked_domains: None,
+            user_location: None,
+            cache_control: None,
+        }
+    }
+}
+
+impl_display_for_serialize!(WebSearchTool);
+
+impl WebSearchTool {
+    /// Creates a new web search tool of the latest version.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of searches per request.
+    pub fn with_max_uses(
+        mut self,
+        max_uses: u32,
+    ) -> Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Sets the domains to include in the search results.
+    pub fn with_allowed_domains<S>(
+        mut self,
+        allowed_domains: Vec<S>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.allowed_domains = Some(
+            allowed_domains
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets the domains to exclude from the search results.
+    pub fn with_blocked_domains<S>(
+        mut self,
+        blocked_domains: Vec<S>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.blocked_domains = Some(
+            blocked_domains
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets the location of the user.
+    pub fn with_user_location(
+        mut self,
+        user_location: UserLocation,
+    ) -> Self {
+        self.user_location = Some(user_location);
+        self
+    }
+}
+
+/// The versioned type of the web search tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WebSearchToolType {
+    /// web_search_20250305
+    #[default]
+    WebSearch20250305,
+}
+
+impl Display for WebSearchToolType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | WebSearchToolType::WebSearch20250305 => {
+                write!(f, "web_search_20250305")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    WebSearchToolType,
+    WebSearch20250305 => "web_search_20250305"
+);
+
+/// The approximate location of the user for the web search.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct UserLocation {
+    /// The type of the location. It is always `approximate`.
+    #[serde(rename = "type")]
+    pub _type: UserLocationType,
+    /// The city, e.g. `San Francisco`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub city: Option<String>,
+    /// The region, e.g. `California`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub region: Option<String>,
+    /// The ISO 3166-1 alpha-2 country code, e.g. `US`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub country: Option<String>,
+    /// The IANA time zone, e.g. `America/Los_Angeles`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub timezone: Option<String>,
+}
+
+impl_display_for_serialize!(UserLocation);
+
+/// The type of the user location.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UserLocationType {
+    /// approximate
+    #[default]
+    Approximate,
+}
+
+impl Display for UserLocationType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | UserLocationType::Approximate => {
+                write!(f, "approximate")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    UserLocationType,
+    Approximate => "approximate"
+);
+
+/// The code execution tool that runs code in a sandbox on the server side.
+///
+/// See [code execution tool](https://docs.anthropic.com/en/docs/agents-and-tools/tool-use/code-execution-tool).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CodeExecutionTool {
+    /// The versioned type of the tool.
+    #[serde(rename = "type")]
+    pub _type: CodeExecutionToolType,
+    /// The name of the tool. It is always `code_execution`.
+    pub name: String,
+    /// The cache control of the tool definition.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for CodeExecutionTool {
+    fn default() -> Self {
+        Self {
+            _type: CodeExecutionToolType::default(),
+            name: "code_execution".to_string(),
+            cache_control: None,
+        }
+    }
+}
+
+impl_display_for_serialize!(CodeExecutionTool);
+
+impl CodeExecutionTool {
+    /// Creates a new code execution tool of the latest version.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The versioned type of the code execution tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CodeExecutionToolType {
+    /// code_execution_20250522
+    #[default]
+    CodeExecution20250522,
+}
+
+impl Display for CodeExecutionToolType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | CodeExecutionToolType::CodeExecution20250522 => {
+                write!(f, "code_execution_20250522")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    CodeExecutionToolType,
+    CodeExecution20250522 => "code_execution_20250522"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_bash_tool() {
+        assert_eq!(
+            serde_json::to_string(&BashTool::new()).unwrap(),
+            "{\"type\":\"bash_20250124\",\"name\":\"bash\"}"
+        );
+    }
+
+    #[test]
+    fn text_editor_tool_name() {
+        assert_eq!(
+            serde_json::to_string(
+                &TextEditorTool::new().with_max_characters(10000)
+            )
+            .unwrap(),
+            "{\"type\":\"text_editor_20250728\",\"name\":\"str_replace_based_edit_tool\",\"max_characters\":10000}"
+        );
+        assert_eq!(
+            TextEditorTool::with_version(
+                TextEditorToolType::TextEditor20250124
+            )
+            .name,
+            "str_replace_editor"
+        );
+    }
+
+    #[test]
+    fn serialize_computer_use_tool() {
+        assert_eq!(
+            serde_json::to_string(
+                &ComputerUseTool::new(1280, 800).with_display_number(1)
+            )
+            .unwrap(),
+            "{\"type\":\"computer_20250124\",\"name\":\"computer\",\"display_width_px\":1280,\"display_height_px\":800,\"display_number\":1}"
+        );
+    }
+
+    #[test]
+    fn serialize_web_search_tool() {
+        let tool = WebSearchTool::new()
+            .with_max_uses(5)
+            .with_allowed_domains(vec!["example.com"])
+            .with_user_location(UserLocation {
+                city: Some("San Francisco".to_string()),
+                country: Some("US".to_string()),
+                ..Default::default()
+            });
+        assert_eq!(
+            serde_json::to_string(&tool).unwrap(),
+            "{\"type\":\"web_search_20250305\",\"name\":\"web_search\",\"max_uses\":5,\"allowed_domains\":[\"example.com\"],\"user_location\":{\"type\":\"approximate\",\"city\":\"San Francisco\",\"country\":\"US\"}}"
+        );
+    }
+
+    #[test]
+    fn deserialize_code_execution_tool() {
+        assert_eq!(
+            serde_json::from_str::<CodeExecutionTool>(
+                "{\"type\":\"code_execution_20250522\",\"name\":\"code_execution\"}"
+            )
+            .unwrap(),
+            CodeExecutionTool::new()
+        );
+        assert!(serde_json::from_str::<CodeExecutionTool>(
+            "{\"type\":\"code_execution_20990101\",\"name\":\"code_execution\"}"
+        )
+        .is_err());
+    }
+}
@@ -1,13 +1,16 @@
 use crate::messages::chunk_stream::ChunkStream;
+use crate::messages::stream_recording::RecordingStream;
 use crate::messages::{
     MessageChunk, MessagesError, MessagesRequestBody, MessagesResponseBody,
-    StreamError, StreamOption,
+    StreamError, StreamOption, StreamRecorder,
 };
 use crate::ApiError;
 use crate::Client;
 use crate::ClientError;
 
+use bytes::Bytes;
 use futures_core::Stream;
+use std::io::Write;
 
 pub(crate) async fn create_a_message(
     client: &Client,
@@ -20,6 +23,9 @@ pub(crate) async fn create_a_message(
         }
     }
 
+    // Validate the request body.
+    request_body.validate()?;
+
     // Send the request.
     let response = client
         .post("https://api.anthropic.com/v1/messages")
@@ -70,8 +76,42 @@ pub(crate) async fn create_a_message_stream(
     request_body: MessagesRequestBody,
 ) -> Result<impl Stream<Item = Result<MessageChunk, StreamError>>, MessagesError>
 {
+    // Create a chunk stream from response bytes stream.
+    let byte_stream =
+        send_a_message_stream_request(client, request_body).await?;
+    let chunk_stream = ChunkStream::new(byte_stream)
+        .idle_timeout(client.stream_idle_timeout())
+        .first_event_timeout(client.stream_first_event_timeout());
+    Ok(chunk_stream)
+}
+
+pub(crate) async fn create_a_message_stream_with_recorder<W>(
+    client: &Client,
+    request_body: MessagesRequestBody,
+    recorder: StreamRecorder<W>,
+) -> Result<impl Stream<Item = Result<MessageChunk, StreamError>>, MessagesError>
+where
+    W: Write,
+{
+    // Create a chunk stream from response bytes stream teed to the recorder.
+    let byte_stream =
+        send_a_message_stream_request(client, request_body).await?;
+    let chunk_stream =
+        ChunkStream::new(RecordingStream::new(byte_stream, recorder))
+            .idle_timeout(client.stream_idle_timeout())
+            .first_event_timeout(client.stream_first_event_timeout());
+    Ok(chunk_stream)
+}
+
+async fn send_a_message_stream_request(
+    client: &Client,
+    request_body: MessagesRequestBody,
+) -> Result<
+    impl Stream<Item = Result<Bytes, reqwest::Error>> + Unpin,
+    MessagesError,
+> {
     // Validate stream option.
-    if let None = &request_body.stream {
+    if request_body.stream.is_none() {
         return Err(MessagesError::StreamOptionMismatch);
     }
     if let Some(stream) = &request_body.stream {
@@ -80,6 +120,9 @@ pub(crate) async fn create_a_message_stream(
         }
     }
 
+    // Validate the request body.
+    request_body.validate()?;
+
     // Send the request.
     let response = client
         .post("https://api.anthropic.com/v1/messages")
@@ -93,10 +136,7 @@ pub(crate) async fn create_a_message_stream(
 
     // Ok
     if status_code.is_success() {
-        // Create a chunk stream from response bytes stream.
-        let byte_stream = response.bytes_stream();
-        let chunk_stream = ChunkStream::new(byte_stream);
-        Ok(chunk_stream)
+        Ok(response.bytes_stream())
     }
     // Error
     else {
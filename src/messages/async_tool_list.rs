@@ -0,0 +1,620 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::messages::tool_index::ToolIndex;
+use crate::messages::{
+    AsyncTool, MessagesResponseBody, Tool, ToolCallError, ToolDefinition,
+    ToolListError, ToolResult, ToolResultContent, ToolUse,
+};
+
+/// The boxed future of a tool call.
+pub type ToolCallFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<ToolResult, ToolCallError>> + Send + 'a>>;
+
+/// An object-safe asynchronous tool that can be stored in `AsyncToolList` with other tools.
+///
+/// It is implemented for all `AsyncTool`s, and for `Tool`s by `BlockingTool`.
+pub trait DynAsyncTool: Send + Sync {
+    /// Gets the definition of the tool.
+    fn definition(&self) -> ToolDefinition;
+    /// Calls the tool asynchronously with the boxed future.
+    fn call_boxed(
+        &self,
+        tool_use: ToolUse,
+    ) -> ToolCallFuture<'_>;
+}
+
+impl<T> DynAsyncTool for T
+where
+    T: AsyncTool + Send + Sync,
+{
+    fn definition(&self) -> ToolDefinition {
+        AsyncTool::definition(self)
+    }
+
+    fn call_boxed(
+        &self,
+        tool_use: ToolUse,
+    ) -> ToolCallFuture<'_> {
+        Box::pin(AsyncTool::call(self, tool_use))
+    }
+}
+
+/// An adapter of a synchronous `Tool` to `DynAsyncTool`.
+///
+/// The tool is called by `tokio::task::spawn_blocking` when the future is first polled,
+/// so the calls run concurrently on the blocking threads without blocking the runtime.
+/// It must be polled in a `tokio` runtime.
+pub struct BlockingTool<T>(Arc<T>);
+
+impl<T> BlockingTool<T> {
+    /// Creates a new adapter of the tool.
+    pub fn new(tool: T) -> Self {
+        Self(Arc::new(tool))
+    }
+}
+
+impl<T> DynAsyncTool for BlockingTool<T>
+where
+    T: Tool + Send + Sync + 'static,
+{
+    fn definition(&self) -> ToolDefinition {
+        self.0.definition()
+    }
+
+    fn call_boxed(
+        &self,
+        tool_use: ToolUse,
+    ) -> ToolCallFuture<'_> {
+        let tool = Arc::clone(&self.0);
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || tool.call(tool_use))
+                .await
+                .map_err(|error| {
+                    ToolCallError::BlockingCallFailed(error.to_string())
+                })?
+        })
+    }
+}
+
+/// A list of synchronous and asynchronous tools that can be called concurrently by the assistant.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{
+///     AsyncTool, AsyncToolList, BlockingTool, Tool, ToolCallError,
+///     ToolDefinition, ToolResult, ToolUse,
+/// };
+/// use std::future::Future;
+///
+/// struct Clock;
+///
+/// impl Tool for Clock {
+///     fn definition(&self) -> ToolDefinition {
+///         ToolDefinition::new("clock", None::<String>, serde_json::json!({"type": "object"}))
+///     }
+///
+///     fn call(&self, tool_use: ToolUse) -> Result<ToolResult, ToolCallError> {
+///         Ok(ToolResult::success(tool_use.id, Some("12:00")))
+///     }
+/// }
+///
+/// struct Weather;
+///
+/// impl AsyncTool for Weather {
+///     fn definition(&self) -> ToolDefinition {
+///         ToolDefinition::new("weather", None::<String>, serde_json::json!({"type": "object"}))
+///     }
+///
+///     fn call(
+///         &self,
+///         tool_use: ToolUse,
+///     ) -> impl Future<Output = Result<ToolResult, ToolCallError>> + Send {
+///         async move { Ok(ToolResult::success(tool_use.id, Some("Sunny"))) }
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let tools = AsyncToolList::new(vec![
+///         Box::new(BlockingTool::new(Clock)),
+///         Box::new(Weather),
+///     ])
+///     .unwrap();
+///
+///     let results = tools
+///         .call_all(vec![
+///             ToolUse::new("toolu_01", "weather", serde_json::json!({})),
+///             ToolUse::new("toolu_02", "clock", serde_json::json!({})),
+///             ToolUse::new("toolu_03", "unknown", serde_json::json!({})),
+///         ])
+///         .await;
+///
+///     assert_eq!(results[0].tool_use_id, "toolu_01");
+///     assert_eq!(results[1].tool_use_id, "toolu_02");
+///     assert_eq!(results[2].is_error, Some(true));
+/// }
+/// ```
+pub struct AsyncToolList {
+    tools: ToolIndex<dyn DynAsyncTool>,
+}
+
+impl AsyncToolList {
+    /// Create a new tool list.
+    ///
+    /// ## Errors
+    /// It returns `ToolListError::InvalidName` if a tool name does not match `^[a-zA-Z0-9_-]{1,64}$`,
+    /// `ToolListError::DuplicateName` if multiple tools have the same name,
+    /// or `ToolListError::InvalidSchema` if an input schema is invalid with the `validation` feature.
+    pub fn new(
+        tools: Vec<Box<dyn DynAsyncTool>>
+    ) -> Result<Self, ToolListError> {
+        let mut list = Self {
+            tools: ToolIndex::new(),
+        };
+        for tool in tools {
+            list.add(tool)?;
+        }
+
+        Ok(list)
+    }
+
+    /// Adds a tool to this list.
+    ///
+    /// ## Errors
+    /// It returns `ToolListError::InvalidName` if the tool name does not match `^[a-zA-Z0-9_-]{1,64}$`,
+    /// `ToolListError::DuplicateName` if a tool with the same name is already in this list,
+    /// or `ToolListError::InvalidSchema` if the input schema is invalid with the `validation` feature.
+    pub fn add(
+        &mut self,
+        tool: Box<dyn DynAsyncTool>,
+    ) -> Result<(), ToolListError> {
+        self.tools
+            .insert(tool.definition(), tool)
+    }
+
+    /// Removes the tool with the name from this list.
+    pub fn remove(
+        &mut self,
+        name: &str,
+    ) -> Option<Box<dyn DynAsyncTool>> {
+        self.tools.remove(name)
+    }
+
+    /// Whether the tool with the name is in this list.
+    pub fn contains(
+        &self,
+        name: &str,
+    ) -> bool {
+        self.tools.get(name).is_some()
+    }
+
+    /// The number of tools.
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether this list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The names of the tools in the order of addition.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tools.names()
+    }
+
+    /// The definition of the tool with the name.
+    pub fn definition(
+        &self,
+        name: &str,
+    ) -> Option<&ToolDefinition> {
+        self.tools
+            .get(name)
+            .map(|(definition, _)| definition)
+    }
+
+    /// List of tool definitions.
+    pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools.definitions()
+    }
+
+    /// List of the definitions of the tools enabled for a request, in the order of the names.
+    ///
+    /// ## Errors
+    /// It returns `ToolListError::ToolNotFound` if a tool with the name is not in this list.
+    pub fn enabled_definitions<I, S>(
+        &self,
+        names: I,
+    ) -> Result<Vec<ToolDefinition>, ToolListError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.tools
+            .enabled_definitions(names)
+    }
+
+    /// Calls a tool in this list.
+    ///
+    /// With the `validation` feature enabled by default, if the input does not match the input schema of the tool,
+    /// the tool is not called and the error result listing the violations is returned.
+    pub async fn call(
+        &self,
+        tool_use: ToolUse,
+    ) -> Result<ToolResult, ToolCallError> {
+        self.call_boxed(tool_use).await
+    }
+
+    /// Calls the tools of all tool uses concurrently.
+    ///
+    /// The results are in the same order as the tool uses,
+    /// and an error of a call is converted into an error result with the error message.
+    pub async fn call_all<I>(
+        &self,
+        tool_uses: I,
+    ) -> Vec<ToolResult>
+    where
+        I: IntoIterator<Item = ToolUse>,
+    {
+        let calls = tool_uses
+            .into_iter()
+            .map(|tool_use| JoinCall::Pending {
+                id: tool_use.id.clone(),
+                future: self.call_boxed(tool_use),
+            })
+            .collect();
+
+        JoinAll {
+            calls,
+        }
+        .await
+    }
+
+    /// Calls the tools of all tool uses in the response concurrently.
+    ///
+    /// See `AsyncToolList::call_all`.
+    pub async fn call_all_in(
+        &self,
+        response: &MessagesResponseBody,
+    ) -> Vec<ToolResult> {
+        self.call_all(
+            response
+                .content
+                .tool_uses()
+                .cloned(),
+        )
+        .await
+    }
+
+    fn call_boxed(
+        &self,
+        tool_use: ToolUse,
+    ) -> ToolCallFuture<'_> {
+        match self.tools.get(&tool_use.name) {
+            | Some((_, tool)) => {
+                match self
+                    .tools
+                    .validate_tool_use(&tool_use)
+                {
+                    | Some(result) => Box::pin(async move { Ok(result) }),
+                    | None => tool.call_boxed(tool_use),
+                }
+            },
+            | None => Box::pin(async move {
+                Err(ToolCallError::ToolNotFound(tool_use.name))
+            }),
+        }
+    }
+}
+
+/// The future that polls all calls concurrently and keeps the order of the results.
+///
+/// An error of a call is converted into an error result with the error message.
+struct JoinAll<'a> {
+    calls: Vec<JoinCall<'a>>,
+}
+
+/// A call of `JoinAll`.
+enum JoinCall<'a> {
+    Pending {
+        id: String,
+        future: ToolCallFuture<'a>,
+    },
+    Done(ToolResult),
+}
+
+impl Future for JoinAll<'_> {
+    type Output = Vec<ToolResult>;
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        let mut is_pending = false;
+
+        for call in self.calls.iter_mut() {
+            if let JoinCall::Pending {
+                id,
+                future,
+            } = call
+            {
+                match future.as_mut().poll(cx) {
+                    | Poll::Ready(output) => {
+                        *call = JoinCall::Done(output.unwrap_or_else(
+                            |error| {
+                                ToolResult::error(
+                                    std::mem::take(id),
+                                    Some(ToolResultContent::Text(
+                                        error.to_string(),
+                                    )),
+                                )
+                            },
+                        ));
+                    },
+                    | Poll::Pending => is_pending = true,
+                }
+            }
+        }
+
+        if is_pending {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(
+            std::mem::take(&mut self.calls)
+                .into_iter()
+                .filter_map(|call| match call {
+                    | JoinCall::Done(result) => Some(result),
+                    | JoinCall::Pending {
+                        ..
+                    } => None,
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Condvar, Mutex};
+    use std::time::Duration;
+
+    struct EchoTool;
+
+    impl Tool for EchoTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition::new(
+                "echo",
+                None::<String>,
+                serde_json::json!({"type": "object"}),
+            )
+        }
+
+        fn call(
+            &self,
+            tool_use: ToolUse,
+        ) -> Result<ToolResult, ToolCallError> {
+            Ok(ToolResult::success(
+                tool_use.id,
+                Some(tool_use.input.to_string().as_str()),
+            ))
+        }
+    }
+
+    struct SleepTool;
+
+    impl AsyncTool for SleepTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition::new(
+                "sleep",
+                None::<String>,
+                serde_json::json!({"type": "object"}),
+            )
+        }
+
+        async fn call(
+            &self,
+            tool_use: ToolUse,
+        ) -> Result<ToolResult, ToolCallError> {
+            let milliseconds = tool_use.input["milliseconds"]
+                .as_u64()
+                .ok_or_else(|| {
+                    ToolCallError::ParameterNotFound("milliseconds".to_string())
+                })?;
+            tokio::time::sleep(Duration::from_millis(milliseconds)).await;
+            Ok(ToolResult::success(
+                tool_use.id,
+                Some(milliseconds.to_string().as_str()),
+            ))
+        }
+    }
+
+    fn tools() -> AsyncToolList {
+        AsyncToolList::new(vec![
+            Box::new(BlockingTool::new(EchoTool)),
+            Box::new(SleepTool),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn definitions() {
+        assert_eq!(
+            tools()
+                .definitions()
+                .iter()
+                .map(|definition| definition.name().to_string())
+                .collect::<Vec<_>>(),
+            vec!["echo", "sleep"]
+        );
+    }
+
+    #[tokio::test]
+    async fn call() {
+        let tools = tools();
+        assert_eq!(
+            tools
+                .call(ToolUse::new(
+                    "toolu_01",
+                    "echo",
+                    serde_json::json!({"text": "Hello"}),
+                ))
+                .await
+                .unwrap(),
+            ToolResult::success("toolu_01", Some("{\"text\":\"Hello\"}"))
+        );
+        assert!(matches!(
+            tools
+                .call(ToolUse::new(
+                    "toolu_02",
+                    "unknown",
+                    serde_json::json!({}),
+                ))
+                .await,
+            Err(ToolCallError::ToolNotFound(_))
+        ));
+        #[cfg(feature = "validation")]
+        assert_eq!(
+            tools
+                .call(ToolUse::new(
+                    "toolu_03",
+                    "sleep",
+                    serde_json::json!([]),
+                ))
+                .await
+                .unwrap()
+                .is_error,
+            Some(true)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn call_all_concurrently_in_order() {
+        let start = tokio::time::Instant::now();
+        let results = tools()
+            .call_all(vec![
+                ToolUse::new(
+                    "toolu_01",
+                    "sleep",
+                    serde_json::json!({"milliseconds": 30}),
+                ),
+                ToolUse::new(
+                    "toolu_02",
+                    "sleep",
+                    serde_json::json!({"milliseconds": 10}),
+                ),
+                ToolUse::new("toolu_03", "echo", serde_json::json!({})),
+                ToolUse::new("toolu_04", "sleep", serde_json::json!({})),
+            ])
+            .await;
+
+        assert_eq!(start.elapsed(), Duration::from_millis(30));
+        assert_eq!(
+            results,
+            vec![
+                ToolResult::success("toolu_01", Some("30")),
+                ToolResult::success("toolu_02", Some("10")),
+                ToolResult::success("toolu_03", Some("{}")),
+                ToolResult::error(
+                    "toolu_04",
+                    Some("Tool parameter not found: milliseconds"),
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn call_blocking_tool_when_polled() {
+        struct CountTool(AtomicUsize);
+
+        impl Tool for CountTool {
+            fn definition(&self) -> ToolDefinition {
+                ToolDefinition::new(
+                    "count",
+                    None::<String>,
+                    serde_json::json!({"type": "object"}),
+                )
+            }
+
+            fn call(
+                &self,
+                tool_use: ToolUse,
+            ) -> Result<ToolResult, ToolCallError> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(ToolResult::success(tool_use.id, None::<String>))
+            }
+        }
+
+        let tool = BlockingTool::new(CountTool(AtomicUsize::new(0)));
+        let future = tool.call_boxed(ToolUse::new(
+            "toolu_01",
+            "count",
+            serde_json::json!({}),
+        ));
+        assert_eq!(tool.0 .0.load(Ordering::SeqCst), 0);
+
+        future.await.unwrap();
+        assert_eq!(tool.0 .0.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn call_blocking_tools_concurrently() {
+        /// A tool that waits until all calls have started.
+        struct RendezvousTool(Arc<(Mutex<usize>, Condvar)>);
+
+        impl Tool for RendezvousTool {
+            fn definition(&self) -> ToolDefinition {
+                ToolDefinition::new(
+                    "rendezvous",
+                    None::<String>,
+                    serde_json::json!({"type": "object"}),
+                )
+            }
+
+            fn call(
+                &self,
+                tool_use: ToolUse,
+            ) -> Result<ToolResult, ToolCallError> {
+                let (started, condvar) = &*self.0;
+                let mut started = started.lock().unwrap();
+                *started += 1;
+                condvar.notify_all();
+                let (started, _) = condvar
+                    .wait_timeout_while(
+                        started,
+                        Duration::from_secs(5),
+                        |started| *started < 2,
+                    )
+                    .unwrap();
+                Ok(ToolResult::success(
+                    tool_use.id,
+                    Some(started.to_string().as_str()),
+                ))
+            }
+        }
+
+        let tools = AsyncToolList::new(vec![Box::new(BlockingTool::new(
+            RendezvousTool(Arc::new((Mutex::new(0), Condvar::new()))),
+        ))])
+        .unwrap();
+        let results = tools
+            .call_all(vec![
+                ToolUse::new("toolu_01", "rendezvous", serde_json::json!({})),
+                ToolUse::new("toolu_02", "rendezvous", serde_json::json!({})),
+            ])
+            .await;
+
+        assert_eq!(
+            results,
+            vec![
+                ToolResult::success("toolu_01", Some("2")),
+                ToolResult::success("toolu_02", Some("2")),
+            ]
+        );
+    }
+}
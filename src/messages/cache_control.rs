@@ -0,0 +1,184 @@
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+use std::fmt::Display;
+
+/// The cache control of a content block, a tool definition or a system prompt block.
+///
+/// The prefix of the request up to and including the block is cached.
+/// See [prompt caching](https://docs.anthropic.com/en/docs/build-with-claude/prompt-caching).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct CacheControl {
+    /// The type of the cache control. It is always `ephemeral`.
+    #[serde(rename = "type")]
+    pub _type: CacheControlType,
+    /// The time to live of the cache. The default is 5 minutes if not specified.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ttl: Option<CacheTtl>,
+}
+
+impl_display_for_serialize!(CacheControl);
+
+impl CacheControl {
+    /// Creates a new ephemeral cache control with the default time to live.
+    pub fn ephemeral() -> Self {
+        Self {
+            _type: CacheControlType::Ephemeral,
+            ttl: None,
+        }
+    }
+
+    /// Creates a new ephemeral cache control with the time to live.
+    pub fn ephemeral_with_ttl(ttl: CacheTtl) -> Self {
+        Self {
+            _type: CacheControlType::Ephemeral,
+            ttl: Some(ttl),
+        }
+    }
+}
+
+/// The type of the cache control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CacheControlType {
+    /// The ephemeral cache.
+    #[default]
+    Ephemeral,
+}
+
+impl Display for CacheControlType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | CacheControlType::Ephemeral => {
+                write!(f, "ephemeral")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    CacheControlType,
+    Ephemeral => "ephemeral"
+);
+
+/// The time to live of the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CacheTtl {
+    /// 5 minutes.
+    #[default]
+    FiveMinutes,
+    /// 1 hour.
+    OneHour,
+}
+
+impl Display for CacheTtl {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | CacheTtl::FiveMinutes => {
+                write!(f, "5m")
+            },
+            | CacheTtl::OneHour => {
+                write!(f, "1h")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    CacheTtl,
+    FiveMinutes => "5m",
+    OneHour => "1h"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let cache_control = CacheControl::ephemeral();
+        assert_eq!(
+            cache_control._type,
+            CacheControlType::Ephemeral
+        );
+        assert_eq!(cache_control.ttl, None);
+
+        let cache_control = CacheControl::ephemeral_with_ttl(CacheTtl::OneHour);
+        assert_eq!(
+            cache_control._type,
+            CacheControlType::Ephemeral
+        );
+        assert_eq!(
+            cache_control.ttl,
+            Some(CacheTtl::OneHour)
+        );
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(
+            CacheControl::default(),
+            CacheControl::ephemeral()
+        );
+        assert_eq!(
+            CacheTtl::default(),
+            CacheTtl::FiveMinutes
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            CacheControlType::Ephemeral.to_string(),
+            "ephemeral"
+        );
+        assert_eq!(CacheTtl::FiveMinutes.to_string(), "5m");
+        assert_eq!(CacheTtl::OneHour.to_string(), "1h");
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_json::to_string(&CacheControl::ephemeral()).unwrap(),
+            "{\"type\":\"ephemeral\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&CacheControl::ephemeral_with_ttl(
+                CacheTtl::OneHour
+            ))
+            .unwrap(),
+            "{\"type\":\"ephemeral\",\"ttl\":\"1h\"}"
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            serde_json::from_str::<CacheControl>("{\"type\":\"ephemeral\"}")
+                .unwrap(),
+            CacheControl::ephemeral()
+        );
+        assert_eq!(
+            serde_json::from_str::<CacheControl>(
+                "{\"type\":\"ephemeral\",\"ttl\":\"5m\"}"
+            )
+            .unwrap(),
+            CacheControl::ephemeral_with_ttl(CacheTtl::FiveMinutes)
+        );
+    }
+}
@@ -0,0 +1,723 @@
+use crate::messages::{
+    CacheControl, Content, ContentBlock, MessagesRequestBody, Role,
+    SystemPrompt, TextContentBlock,
+};
+
+/// The maximum number of cache breakpoints in a request.
+pub const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+/// The number of blocks that the API looks back from a breakpoint to find a cache hit.
+const CACHE_LOOKBACK_BLOCKS: usize = 20;
+
+/// The rough number of characters per token to estimate the length of a prompt.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// The rough number of tokens of an image that has the recommended size.
+const IMAGE_TOKENS: u32 = 1_600;
+
+/// A planner that places prompt cache breakpoints to a request.
+///
+/// The planner removes all existing cache controls and places up to [`MAX_CACHE_BREAKPOINTS`] breakpoints at:
+/// 1. the end of the tools,
+/// 2. the end of the system prompt,
+/// 3. the trailing turn of the previous request, i.e. the last user turn before the last message, to read the cache written by it,
+/// 4. the trailing conversation turn to write the cache for the next request.
+///
+/// A breakpoint whose prefix is shorter than the minimum cacheable length of the model is skipped.
+/// The lengths are estimated roughly from the characters of the prompt.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{CachePlanner, ClaudeModel, Message, MessagesRequestBody, SystemPrompt};
+///
+/// let mut request_body = MessagesRequestBody {
+///     model: ClaudeModel::Claude35Sonnet20240620,
+///     system: Some(SystemPrompt::new("long-system-prompt".repeat(1000))),
+///     messages: vec![Message::user("Hello, Claude!")],
+///     ..Default::default()
+/// };
+///
+/// let plan = CachePlanner::default().plan(&mut request_body);
+/// assert_eq!(plan.breakpoints.len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CachePlanner {
+    /// The cache control of the placed breakpoints.
+    pub cache_control: CacheControl,
+}
+
+/// The position of a cache breakpoint in a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheBreakpointPosition {
+    /// The last tool definition.
+    Tools,
+    /// The last block of the system prompt.
+    System,
+    /// The last cacheable block of the message at the index.
+    Message(usize),
+}
+
+/// A cache breakpoint placed by the [`CachePlanner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheBreakpoint {
+    /// The position of the breakpoint.
+    pub position: CacheBreakpointPosition,
+    /// The estimated number of tokens of the prompt prefix up to and including the breakpoint.
+    pub prefix_tokens: u32,
+}
+
+/// The plan of cache breakpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CachePlan {
+    /// The placed breakpoints in the order of the prompt.
+    pub breakpoints: Vec<CacheBreakpoint>,
+}
+
+/// The expected cache usage of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ExpectedCacheUsage {
+    /// The estimated number of input tokens read from the cache written by the previous request.
+    pub cache_read_input_tokens: u32,
+    /// The estimated number of input tokens written to the cache.
+    pub cache_creation_input_tokens: u32,
+}
+
+impl CachePlanner {
+    /// Creates a new cache planner.
+    pub fn new(cache_control: CacheControl) -> Self {
+        Self {
+            cache_control,
+        }
+    }
+
+    /// Places cache breakpoints to the request body.
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body to place breakpoints.
+    pub fn plan(
+        &self,
+        request_body: &mut MessagesRequestBody,
+    ) -> CachePlan {
+        clear_cache_controls(request_body);
+
+        let minimum_tokens = request_body
+            .model
+            .minimum_cacheable_tokens();
+        let segments = segments(request_body);
+
+        let mut candidates = Vec::new();
+        if let Some(index) = segments
+            .iter()
+            .rposition(|segment| segment.owner == SegmentOwner::Tools)
+        {
+            candidates.push((CacheBreakpointPosition::Tools, index));
+        }
+        if let Some(index) = segments
+            .iter()
+            .rposition(|segment| segment.owner == SegmentOwner::System)
+        {
+            candidates.push((CacheBreakpointPosition::System, index));
+        }
+        for message_index in trailing_turns(request_body) {
+            if let Some(index) = segments
+                .iter()
+                .rposition(|segment| {
+                    segment.owner == SegmentOwner::Message(message_index)
+                        && segment.markable
+                })
+            {
+                candidates.push((
+                    CacheBreakpointPosition::Message(message_index),
+                    index,
+                ));
+            }
+        }
+
+        let mut breakpoints = Vec::new();
+        for (position, index) in candidates
+            .into_iter()
+            .take(MAX_CACHE_BREAKPOINTS)
+        {
+            let prefix_tokens = prefix_tokens(&segments, index);
+            if prefix_tokens < minimum_tokens {
+                continue;
+            }
+
+            mark(
+                request_body,
+                &segments[index],
+                self.cache_control,
+            );
+            breakpoints.push(CacheBreakpoint {
+                position,
+                prefix_tokens,
+            });
+        }
+
+        CachePlan {
+            breakpoints,
+        }
+    }
+
+    /// Estimates the cache usage of the request body given the previous request in the conversation.
+    ///
+    /// The cache written at a breakpoint of the previous request is read if the prompt prefix up to the breakpoint is not changed
+    /// and the request body has a breakpoint within the lookback window after it.
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body with cache breakpoints.
+    /// - `previous` - The previous request body with cache breakpoints, if any.
+    pub fn expected_cache_usage(
+        request_body: &MessagesRequestBody,
+        previous: Option<&MessagesRequestBody>,
+    ) -> ExpectedCacheUsage {
+        let minimum_tokens = request_body
+            .model
+            .minimum_cacheable_tokens();
+        let current = segments(request_body);
+        let current_breakpoints = current
+            .iter()
+            .enumerate()
+            .filter(|(index, segment)| {
+                segment.breakpoint
+                    && prefix_tokens(&current, *index) >= minimum_tokens
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        let cache_read_input_tokens = match previous {
+            | Some(previous) if previous.model == request_body.model => {
+                let previous = segments(previous);
+                let common_length = current
+                    .iter()
+                    .zip(previous.iter())
+                    .take_while(|(current, previous)| {
+                        current.key == previous.key
+                    })
+                    .count();
+
+                previous
+                    .iter()
+                    .enumerate()
+                    .take(common_length)
+                    .filter(|(index, segment)| {
+                        segment.breakpoint
+                            && current_breakpoints
+                                .iter()
+                                .any(|breakpoint| {
+                                    *breakpoint >= *index
+                                        && *breakpoint - *index
+                                            < CACHE_LOOKBACK_BLOCKS
+                                })
+                    })
+                    .map(|(index, _)| prefix_tokens(&current, index))
+                    .filter(|tokens| *tokens >= minimum_tokens)
+                    .max()
+                    .unwrap_or(0)
+            },
+            | _ => 0,
+        };
+
+        let cached_tokens = current_breakpoints
+            .last()
+            .map(|index| prefix_tokens(&current, *index))
+            .unwrap_or(0);
+
+        ExpectedCacheUsage {
+            cache_read_input_tokens,
+            cache_creation_input_tokens: cached_tokens
+                .saturating_sub(cache_read_input_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentOwner {
+    Tools,
+    System,
+    Message(usize),
+}
+
+/// A block of the prompt in the order of the cache prefix: tools, system and messages.
+#[derive(Debug, Clone)]
+struct Segment {
+    owner: SegmentOwner,
+    /// The index of the block in the owner.
+    index: usize,
+    /// The serialized block without the cache control to compare prefixes.
+    key: String,
+    tokens: u32,
+    breakpoint: bool,
+    markable: bool,
+}
+
+fn segments(request_body: &MessagesRequestBody) -> Vec<Segment> {
+    let mut segments = Vec::new();
+
+    for (index, tool) in request_body
+        .tools
+        .iter()
+        .flatten()
+        .enumerate()
+    {
+        let key = key_of(tool);
+        segments.push(Segment {
+            owner: SegmentOwner::Tools,
+            index,
+            tokens: estimate_tokens(&key),
+            key,
+            breakpoint: tool.cache_control().is_some(),
+            markable: true,
+        });
+    }
+
+    let system_blocks = match &request_body.system {
+        | Some(SystemPrompt::Text(text)) if !text.is_empty() => {
+            vec![TextContentBlock::new(text.clone())]
+        },
+        | Some(SystemPrompt::Blocks(blocks)) => blocks.clone(),
+        | _ => Vec::new(),
+    };
+    for (index, block) in system_blocks.iter().enumerate() {
+        let key = key_of(block);
+        segments.push(Segment {
+            owner: SegmentOwner::System,
+            index,
+            tokens: estimate_tokens(&key),
+            key,
+            breakpoint: block.cache_control.is_some(),
+            markable: true,
+        });
+    }
+
+    for (message_index, message) in request_body
+        .messages
+        .iter()
+        .enumerate()
+    {
+        let blocks = match &message.content {
+            | Content::SingleText(text) => {
+                vec![ContentBlock::Text(TextContentBlock::new(
+                    text.clone(),
+                ))]
+            },
+            | Content::MultipleBlocks(blocks) => blocks.clone(),
+        };
+
+        for (index, block) in blocks.iter().enumerate() {
+            let key = format!("{}:{}", message.role, key_of(block));
+            let tokens = match block {
+                | ContentBlock::Image(_) => IMAGE_TOKENS,
+                | _ => estimate_tokens(&key),
+            };
+            segments.push(Segment {
+                owner: SegmentOwner::Message(message_index),
+                index,
+                key,
+                tokens,
+                breakpoint: cache_control_of(block).is_some(),
+                markable: is_cacheable(block),
+            });
+        }
+    }
+
+    segments
+}
+
+fn key_of<T>(value: &T) -> String
+where
+    T: serde::Serialize,
+{
+    let mut value = serde_json::to_value(value).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.remove("cache_control");
+    }
+
+    value.to_string()
+}
+
+fn estimate_tokens(key: &str) -> u32 {
+    key.chars()
+        .count()
+        .div_ceil(CHARS_PER_TOKEN) as u32
+}
+
+fn prefix_tokens(
+    segments: &[Segment],
+    index: usize,
+) -> u32 {
+    segments[..=index]
+        .iter()
+        .map(|segment| segment.tokens)
+        .sum()
+}
+
+/// The indices of the trailing turn of the previous request and the last message.
+fn trailing_turns(request_body: &MessagesRequestBody) -> Vec<usize> {
+    let Some(last) = request_body
+        .messages
+        .len()
+        .checked_sub(1)
+    else {
+        return Vec::new();
+    };
+
+    let previous = request_body.messages[..last]
+        .iter()
+        .rposition(|message| message.role == Role::User);
+
+    match previous {
+        | Some(previous) => vec![previous, last],
+        | None => vec![last],
+    }
+}
+
+fn cache_control_of(block: &ContentBlock) -> Option<CacheControl> {
+    match block {
+        | ContentBlock::Text(block) => block.cache_control,
+        | ContentBlock::Image(block) => block.cache_control,
+        | ContentBlock::Document(block) => block.cache_control,
+        | ContentBlock::ToolResult(block) => block.cache_control,
+        | _ => None,
+    }
+}
+
+/// Whether the block can have a cache control.
+fn is_cacheable(block: &ContentBlock) -> bool {
+    matches!(
+        block,
+        ContentBlock::Text(_)
+            | ContentBlock::Image(_)
+            | ContentBlock::Document(_)
+            | ContentBlock::ToolResult(_)
+    )
+}
+
+fn set_cache_control(
+    block: &mut ContentBlock,
+    cache_control: Option<CacheControl>,
+) {
+    match block {
+        | ContentBlock::Text(block) => block.cache_control = cache_control,
+        | ContentBlock::Image(block) => block.cache_control = cache_control,
+        | ContentBlock::Document(block) => {
+            block.cache_control = cache_control
+        },
+        | ContentBlock::ToolResult(block) => {
+            block.cache_control = cache_control
+        },
+        | _ => {},
+    }
+}
+
+fn clear_cache_controls(request_body: &mut MessagesRequestBody) {
+    for tool in request_body
+        .tools
+        .iter_mut()
+        .flatten()
+    {
+        tool.set_cache_control(None);
+    }
+
+    if let Some(SystemPrompt::Blocks(blocks)) = &mut request_body.system {
+        for block in blocks {
+            block.cache_control = None;
+        }
+    }
+
+    for message in &mut request_body.messages {
+        if let Content::MultipleBlocks(blocks) = &mut message.content {
+            for block in blocks {
+                set_cache_control(block, None);
+            }
+        }
+    }
+}
+
+fn mark(
+    request_body: &mut MessagesRequestBody,
+    segment: &Segment,
+    cache_control: CacheControl,
+) {
+    match segment.owner {
+        | SegmentOwner::Tools => {
+            if let Some(tool) = request_body
+                .tools
+                .as_mut()
+                .and_then(|tools| tools.get_mut(segment.index))
+            {
+                tool.set_cache_control(Some(cache_control));
+            }
+        },
+        | SegmentOwner::System => {
+            if let Some(system) = request_body.system.take() {
+                request_body.system =
+                    Some(system.with_cache_control(cache_control));
+            }
+        },
+        | SegmentOwner::Message(message_index) => {
+            let content = &mut request_body.messages[message_index].content;
+            if let Content::SingleText(text) = content {
+                *content = Content::MultipleBlocks(vec![ContentBlock::Text(
+                    TextContentBlock::new(std::mem::take(text)),
+                )]);
+            }
+
+            if let Content::MultipleBlocks(blocks) = content {
+                if let Some(block) = blocks.get_mut(segment.index) {
+                    set_cache_control(block, Some(cache_control));
+                }
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        ClaudeModel, Message, ToolDefinition, ToolResult, ToolUse,
+    };
+
+    fn long_text(tokens: usize) -> String {
+        "a".repeat(tokens * CHARS_PER_TOKEN)
+    }
+
+    fn tool(name: &str) -> ToolDefinition {
+        ToolDefinition::new(
+            name,
+            Some(long_text(600)),
+            serde_json::json!({"type": "object"}),
+        )
+    }
+
+    #[test]
+    fn plan_tools_system_and_trailing_turns() {
+        let mut request_body = MessagesRequestBody {
+            model: ClaudeModel::Claude35Sonnet20240620,
+            tools: Some(vec![tool("tool1"), tool("tool2")]),
+            system: Some(SystemPrompt::new(long_text(100))),
+            messages: vec![
+                Message::user("first question"),
+                Message::assistant("first answer"),
+                Message::user("second question"),
+            ],
+            ..Default::default()
+        };
+
+        let plan = CachePlanner::default().plan(&mut request_body);
+
+        let positions = plan
+            .breakpoints
+            .iter()
+            .map(|breakpoint| breakpoint.position)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                CacheBreakpointPosition::Tools,
+                CacheBreakpointPosition::System,
+                CacheBreakpointPosition::Message(0),
+                CacheBreakpointPosition::Message(2),
+            ]
+        );
+        assert!(plan
+            .breakpoints
+            .windows(2)
+            .all(|pair| pair[0].prefix_tokens < pair[1].prefix_tokens));
+
+        let tools = request_body.tools.unwrap();
+        assert_eq!(tools[0].cache_control(), None);
+        assert_eq!(
+            tools[1].cache_control(),
+            Some(&CacheControl::ephemeral())
+        );
+        assert!(matches!(
+            request_body.system,
+            Some(SystemPrompt::Blocks(_))
+        ));
+        assert_eq!(
+            request_body.messages[2].content,
+            Content::MultipleBlocks(vec![ContentBlock::Text(
+                TextContentBlock::new("second question")
+                    .with_cache_control(CacheControl::ephemeral())
+            )])
+        );
+        assert_eq!(
+            request_body.messages[1].content,
+            Content::SingleText("first answer".to_string())
+        );
+    }
+
+    #[test]
+    fn plan_skips_short_prefix() {
+        let mut request_body = MessagesRequestBody {
+            model: ClaudeModel::Claude35Sonnet20240620,
+            system: Some(SystemPrompt::new("short system prompt")),
+            messages: vec![Message::user(long_text(2000))],
+            ..Default::default()
+        };
+
+        let plan = CachePlanner::default().plan(&mut request_body);
+
+        assert_eq!(plan.breakpoints.len(), 1);
+        assert_eq!(
+            plan.breakpoints[0].position,
+            CacheBreakpointPosition::Message(0)
+        );
+        assert_eq!(
+            request_body.system,
+            Some(SystemPrompt::new("short system prompt"))
+        );
+    }
+
+    #[test]
+    fn plan_respects_model_minimum() {
+        let new_request_body = |model| MessagesRequestBody {
+            model,
+            system: Some(SystemPrompt::new(long_text(1500))),
+            ..Default::default()
+        };
+
+        let mut request_body =
+            new_request_body(ClaudeModel::Claude35Sonnet20240620);
+        let plan = CachePlanner::default().plan(&mut request_body);
+        assert_eq!(plan.breakpoints.len(), 1);
+
+        let mut request_body =
+            new_request_body(ClaudeModel::Claude3Haiku20240307);
+        let plan = CachePlanner::default().plan(&mut request_body);
+        assert_eq!(plan.breakpoints.len(), 0);
+    }
+
+    #[test]
+    fn plan_skips_tool_use_block() {
+        let mut request_body = MessagesRequestBody {
+            model: ClaudeModel::Claude35Sonnet20240620,
+            messages: vec![
+                Message::user(long_text(2000)),
+                Message::assistant(Content::MultipleBlocks(vec![
+                    ContentBlock::from(TextContentBlock::new("thinking")),
+                    ContentBlock::from(ToolUse::new(
+                        "id",
+                        "tool",
+                        serde_json::json!({}),
+                    )),
+                ])),
+            ],
+            ..Default::default()
+        };
+
+        let plan = CachePlanner::default().plan(&mut request_body);
+
+        assert_eq!(
+            plan.breakpoints
+                .last()
+                .unwrap()
+                .position,
+            CacheBreakpointPosition::Message(1)
+        );
+        match &request_body.messages[1].content {
+            | Content::MultipleBlocks(blocks) => {
+                assert_eq!(
+                    cache_control_of(&blocks[0]),
+                    Some(CacheControl::ephemeral())
+                );
+            },
+            | _ => panic!("unexpected content"),
+        }
+    }
+
+    #[test]
+    fn plan_clears_existing_cache_controls() {
+        let mut request_body = MessagesRequestBody {
+            model: ClaudeModel::Claude35Sonnet20240620,
+            messages: vec![Message::user(Content::MultipleBlocks(vec![
+                ContentBlock::from(
+                    TextContentBlock::new("short")
+                        .with_cache_control(CacheControl::ephemeral()),
+                ),
+                ContentBlock::from(ToolResult::success(
+                    "id",
+                    Some("result"),
+                )),
+            ]))],
+            ..Default::default()
+        };
+
+        let plan = CachePlanner::default().plan(&mut request_body);
+
+        assert!(plan.breakpoints.is_empty());
+        match &request_body.messages[0].content {
+            | Content::MultipleBlocks(blocks) => {
+                assert!(blocks
+                    .iter()
+                    .all(|block| cache_control_of(block).is_none()));
+            },
+            | _ => panic!("unexpected content"),
+        }
+    }
+
+    #[test]
+    fn expected_cache_usage() {
+        let planner = CachePlanner::default();
+
+        let mut previous = MessagesRequestBody {
+            model: ClaudeModel::Claude35Sonnet20240620,
+            system: Some(SystemPrompt::new(long_text(2000))),
+            messages: vec![Message::user(long_text(1000))],
+            ..Default::default()
+        };
+        let previous_plan = planner.plan(&mut previous);
+
+        let usage = CachePlanner::expected_cache_usage(&previous, None);
+        assert_eq!(usage.cache_read_input_tokens, 0);
+        assert_eq!(
+            usage.cache_creation_input_tokens,
+            previous_plan
+                .breakpoints
+                .last()
+                .unwrap()
+                .prefix_tokens
+        );
+
+        let mut current = MessagesRequestBody {
+            model: ClaudeModel::Claude35Sonnet20240620,
+            system: Some(SystemPrompt::new(long_text(2000))),
+            messages: vec![
+                Message::user(long_text(1000)),
+                Message::assistant("answer"),
+                Message::user("next question"),
+            ],
+            ..Default::default()
+        };
+        let current_plan = planner.plan(&mut current);
+
+        let usage =
+            CachePlanner::expected_cache_usage(&current, Some(&previous));
+        assert_eq!(
+            usage.cache_read_input_tokens,
+            previous_plan
+                .breakpoints
+                .last()
+                .unwrap()
+                .prefix_tokens
+        );
+        assert_eq!(
+            usage.cache_read_input_tokens + usage.cache_creation_input_tokens,
+            current_plan
+                .breakpoints
+                .last()
+                .unwrap()
+                .prefix_tokens
+        );
+
+        // Changing the system prompt invalidates the cache.
+        let mut changed = current.clone();
+        changed.system = Some(SystemPrompt::new(long_text(2001)));
+        planner.plan(&mut changed);
+        let usage =
+            CachePlanner::expected_cache_usage(&changed, Some(&previous));
+        assert_eq!(usage.cache_read_input_tokens, 0);
+    }
+}
@@ -1,39 +1,72 @@
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
-use bytes::{Buf, BytesMut};
 use futures_core::Stream;
 use pin_project::pin_project;
+use tokio::time::{Instant, Sleep};
 
-use crate::messages::{MessageChunk, StreamError};
+use crate::messages::{
+    ContentBlock, ContentBlockDelta, MessageChunk, SseDecoder, StreamError,
+    ToolUse,
+};
 
 /// The stream of message chunks with `tokio` backend.
 #[pin_project]
-pub(crate) struct ChunkStream<S>
-where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
-{
+pub(crate) struct ChunkStream<S> {
     #[pin]
     stream: S,
-    buffer: BytesMut,
+    decoder: SseDecoder,
+    tool_uses: ToolUseAccumulator,
+    idle_timeout: Option<Duration>,
+    idle_deadline: Option<Pin<Box<Sleep>>>,
+    first_event_timeout: Option<Duration>,
+    first_event_deadline: Option<Pin<Box<Sleep>>>,
+    has_received_event: bool,
+    is_terminated: bool,
 }
 
-impl<S> ChunkStream<S>
-where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
-{
+impl<S> ChunkStream<S> {
     /// Create a new chunk stream.
     pub fn new(stream: S) -> Self {
         ChunkStream {
             stream,
-            buffer: BytesMut::new(),
+            decoder: SseDecoder::new(),
+            tool_uses: ToolUseAccumulator::default(),
+            idle_timeout: None,
+            idle_deadline: None,
+            first_event_timeout: None,
+            first_event_deadline: None,
+            has_received_event: false,
+            is_terminated: false,
         }
     }
+
+    /// Sets the timeout that fails the stream when no byte arrives for the duration.
+    pub fn idle_timeout(
+        mut self,
+        timeout: Option<Duration>,
+    ) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout that fails the stream when no event arrives for the duration from the first poll.
+    pub fn first_event_timeout(
+        mut self,
+        timeout: Option<Duration>,
+    ) -> Self {
+        self.first_event_timeout = timeout;
+        self
+    }
 }
 
-impl<S> Stream for ChunkStream<S>
+impl<S, E> Stream for ChunkStream<S>
 where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<bytes::Bytes, E>> + Unpin,
+    E: Into<StreamError>,
 {
     type Item = Result<MessageChunk, StreamError>;
 
@@ -43,36 +76,52 @@ where
     ) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
+        if *this.is_terminated {
+            return Poll::Ready(None);
+        }
+
+        // Start the timers at the first poll.
+        if let Some(timeout) = *this.idle_timeout {
+            this.idle_deadline
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)));
+        }
+        if let Some(timeout) = *this.first_event_timeout {
+            if !*this.has_received_event {
+                this.first_event_deadline
+                    .get_or_insert_with(|| {
+                        Box::pin(tokio::time::sleep(timeout))
+                    });
+            }
+        }
+
         loop {
-            if let Some(position) = this
-                .buffer
-                .iter()
-                .position(|b| *b == b'\n')
-            {
-                if let Some(end) = this.buffer[position + 1..]
-                    .iter()
-                    .position(|b| *b == b'\n')
-                {
-                    let chunk_end = position + end + 2;
-                    let chunk = this
-                        .buffer
-                        .split_to(chunk_end)
-                        .to_vec();
-
-                    // Skip the newline.
-                    this.buffer.advance(1);
-
-                    // Check if the chunk is not empty.
-                    if chunk
-                        .iter()
-                        .any(|b| *b != b'\n')
+            while let Some(event) = this.decoder.next_event() {
+                *this.has_received_event = true;
+                *this.first_event_deadline = None;
+
+                let chunk = match MessageChunk::from_event(&event) {
+                    | Ok(chunk) => chunk,
+                    // The stream cannot be resumed after an invalid event.
+                    | Err(error) => {
+                        *this.is_terminated = true;
+                        return Poll::Ready(Some(Err(error)));
+                    },
+                };
+
+                // Skip the chunk of an unknown event.
+                if let Some(mut chunk) = chunk {
+                    if let Some((tool_use, incomplete_input)) = this
+                        .tool_uses
+                        .accumulate(&chunk)
                     {
-                        let chunk = String::from_utf8(chunk)
-                            .map_err(StreamError::StringDecodingError)?;
-
-                        let chunk = MessageChunk::parse(&chunk)?;
-                        return Poll::Ready(Some(Ok(chunk)));
+                        if let MessageChunk::ContentBlockStop(stop) =
+                            &mut chunk
+                        {
+                            stop.tool_use = Some(tool_use);
+                            stop.incomplete_input = incomplete_input;
+                        }
                     }
+                    return Poll::Ready(Some(Ok(chunk)));
                 }
             }
 
@@ -82,36 +131,139 @@ where
                 .poll_next(cx)
             {
                 // The stream has more data.
-                | Poll::Ready(Some(Ok(chunk))) => {
-                    this.buffer.extend(&chunk);
+                | Poll::Ready(Some(Ok(bytes))) => {
+                    // Any byte, e.g. of a ping or a comment, keeps the stream alive.
+                    if let (Some(deadline), Some(timeout)) =
+                        (this.idle_deadline.as_mut(), *this.idle_timeout)
+                    {
+                        deadline
+                            .as_mut()
+                            .reset(Instant::now() + timeout);
+                    }
+                    // The stream cannot be resumed after invalid bytes.
+                    if let Err(error) = this.decoder.feed(&bytes) {
+                        *this.is_terminated = true;
+                        return Poll::Ready(Some(Err(error)));
+                    }
                     // Continue to the next iteration of the loop.
                 },
                 // The stream has an error.
                 | Poll::Ready(Some(Err(error))) => {
-                    return Poll::Ready(Some(Err(StreamError::ReqwestError(
-                        error,
-                    ))));
+                    *this.is_terminated = true;
+                    return Poll::Ready(Some(Err(error.into())));
                 },
-                // The stream has no more data.
+                // The stream has no more data, and the incomplete event is discarded.
                 | Poll::Ready(None) => {
-                    return if this.buffer.is_empty() {
-                        Poll::Ready(None)
-                    } else {
-                        let remaining = this.buffer.split_off(0);
-                        let remaining =
-                            String::from_utf8(remaining.to_vec())
-                                .map_err(StreamError::StringDecodingError)?;
-                        let chunk = MessageChunk::parse(&remaining)?;
-                        Poll::Ready(Some(Ok(chunk)))
-                    };
+                    *this.is_terminated = true;
+                    return Poll::Ready(None);
                 },
                 // The stream has no more data for now.
-                | Poll::Pending => return Poll::Pending,
+                | Poll::Pending => {
+                    if let (Some(deadline), Some(timeout)) = (
+                        this.first_event_deadline.as_mut(),
+                        *this.first_event_timeout,
+                    ) {
+                        if deadline
+                            .as_mut()
+                            .poll(cx)
+                            .is_ready()
+                        {
+                            *this.is_terminated = true;
+                            return Poll::Ready(Some(Err(
+                                StreamError::FirstEventTimeout(timeout),
+                            )));
+                        }
+                    }
+                    if let (Some(deadline), Some(timeout)) =
+                        (this.idle_deadline.as_mut(), *this.idle_timeout)
+                    {
+                        if deadline
+                            .as_mut()
+                            .poll(cx)
+                            .is_ready()
+                        {
+                            *this.is_terminated = true;
+                            return Poll::Ready(Some(Err(
+                                StreamError::IdleTimeout(timeout),
+                            )));
+                        }
+                    }
+                    return Poll::Pending;
+                },
             }
         }
     }
 }
 
+/// Accumulates the partial JSON inputs of the tool use blocks in the stream.
+#[derive(Debug, Default)]
+pub(crate) struct ToolUseAccumulator {
+    partial_tool_uses: BTreeMap<u32, (ToolUse, String)>,
+}
+
+impl ToolUseAccumulator {
+    /// Accumulates the chunk and returns the complete tool use at the content block stop chunk of a tool use block,
+    /// with the raw accumulated input if it is not valid JSON.
+    pub(crate) fn accumulate(
+        &mut self,
+        chunk: &MessageChunk,
+    ) -> Option<(ToolUse, Option<String>)> {
+        match chunk {
+            | MessageChunk::ContentBlockStart(start) => {
+                match &start.content_block {
+                    | ContentBlock::ToolUse(block) => {
+                        self.partial_tool_uses.insert(
+                            start.index,
+                            (block.tool_use.clone(), String::new()),
+                        );
+                    },
+                    | ContentBlock::ServerToolUse(block) => {
+                        self.partial_tool_uses.insert(
+                            start.index,
+                            (block.tool_use.clone(), String::new()),
+                        );
+                    },
+                    | _ => {},
+                }
+            },
+            | MessageChunk::ContentBlockDelta(delta) => {
+                if let ContentBlockDelta::InputJsonDelta(input_json) =
+                    &delta.delta
+                {
+                    if let Some((_, partial_json)) = self
+                        .partial_tool_uses
+                        .get_mut(&delta.index)
+                    {
+                        partial_json.push_str(&input_json.partial_json);
+                    }
+                }
+            },
+            | MessageChunk::ContentBlockStop(stop) => {
+                if let Some((mut tool_use, partial_json)) = self
+                    .partial_tool_uses
+                    .remove(&stop.index)
+                {
+                    // The input is kept as the start block when no delta has arrived,
+                    // or when the input has been cut off, e.g. by `max_tokens`.
+                    if partial_json.is_empty() {
+                        return Some((tool_use, None));
+                    }
+                    return match serde_json::from_str(&partial_json) {
+                        | Ok(input) => {
+                            tool_use.input = input;
+                            Some((tool_use, None))
+                        },
+                        | Err(_) => Some((tool_use, Some(partial_json))),
+                    };
+                }
+            },
+            | _ => {},
+        }
+
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::super::messages::*;
@@ -144,7 +296,7 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
 
 "#;
 
-        let input_stream = futures_util::stream::iter(vec![Ok(
+        let input_stream = futures_util::stream::iter(vec![Ok::<_, StreamError>(
             bytes::Bytes::from(source),
         )]);
 
@@ -171,6 +323,8 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
                         usage: Usage {
                             input_tokens: 25,
                             output_tokens: 1,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
                         },
                     }),
                 );
@@ -265,7 +419,9 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
                             stop_sequence: None,
                         },
                         DeltaUsage {
-                            output_tokens: 15
+                            output_tokens: 15,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
                         },
                     ),
                 );
@@ -306,7 +462,7 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
 
 "#;
 
-        let input_stream = tokio_stream::iter(vec![Ok(
+        let input_stream = tokio_stream::iter(vec![Ok::<_, StreamError>(
             bytes::Bytes::from(source),
         )]);
 
@@ -333,6 +489,8 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
                         usage: Usage {
                             input_tokens: 25,
                             output_tokens: 1,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
                         },
                     }),
                 );
@@ -427,7 +585,9 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
                             stop_sequence: None,
                         },
                         DeltaUsage {
-                            output_tokens: 15
+                            output_tokens: 15,
+                            cache_creation_input_tokens: None,
+                            cache_read_input_tokens: None,
                         },
                     ),
                 );
@@ -440,4 +600,341 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
             .await
             .is_none());
     }
+
+    #[tokio::test]
+    async fn skip_unknown_event() {
+        use futures_util::StreamExt;
+
+        let source = r#"event: new_event
+data: {"type": "new_event"}
+
+event: ping
+data: {"type": "ping"}
+
+event: new_event
+data: {"type": "new_event"}
+
+"#;
+
+        let input_stream = futures_util::stream::iter(vec![Ok::<_, StreamError>(
+            bytes::Bytes::from(source),
+        )]);
+
+        let mut chunk_stream = ChunkStream::new(input_stream);
+
+        assert_eq!(
+            chunk_stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap(),
+            MessageChunk::Ping(PingChunk::default())
+        );
+        assert!(chunk_stream
+            .next()
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn terminate_after_error() {
+        use futures_util::StreamExt;
+
+        let sources: [&[u8]; 2] = [
+            b"event: ping\ndata: {\"type\":\n\nevent: ping\ndata: {\"type\": \"ping\"}\n\n",
+            b"event: ping\ndata: \xFF\n\nevent: ping\ndata: {\"type\": \"ping\"}\n\n",
+        ];
+        for source in sources {
+            let input_stream =
+                futures_util::stream::iter(vec![Ok::<_, StreamError>(
+                    bytes::Bytes::from_static(source),
+                )]);
+
+            let mut chunk_stream = ChunkStream::new(input_stream);
+            assert!(matches!(
+                chunk_stream.next().await,
+                Some(Err(_))
+            ));
+            assert!(chunk_stream.next().await.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn decode_server_sent_events() {
+        use futures_util::StreamExt;
+
+        let source = ": keep-alive\r\n\r\nevent:ping\r\ndata:{\"type\": \"ping\"}\r\n\r\nevent: content_block_stop\r\ndata: {\"type\": \"content_block_stop\",\r\ndata: \"index\": 0}\r\n\r\n";
+
+        let input_stream = futures_util::stream::iter(
+            source
+                .as_bytes()
+                .chunks(7)
+                .map(|bytes| {
+                    Ok::<_, StreamError>(bytes::Bytes::copy_from_slice(bytes))
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let mut chunk_stream = ChunkStream::new(input_stream);
+
+        assert_eq!(
+            chunk_stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap(),
+            MessageChunk::Ping(PingChunk::default())
+        );
+        assert_eq!(
+            chunk_stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap(),
+            MessageChunk::ContentBlockStop(ContentBlockStopChunk::new(0))
+        );
+        assert!(chunk_stream
+            .next()
+            .await
+            .is_none());
+    }
+
+    fn delayed_byte_stream(
+        frames: Vec<(u64, &'static str)>,
+    ) -> impl Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin {
+        Box::pin(futures_util::stream::unfold(
+            frames.into_iter(),
+            |mut frames| async move {
+                let (delay, frame) = frames.next()?;
+                tokio::time::sleep(Duration::from_secs(delay)).await;
+                Some((Ok(bytes::Bytes::from(frame)), frames))
+            },
+        ))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_timeout() {
+        use futures_util::StreamExt;
+
+        let input_stream = delayed_byte_stream(vec![
+            (0, "event: ping\ndata: {\"type\": \"ping\"}\n\n"),
+            (60, "event: ping\ndata: {\"type\": \"ping\"}\n\n"),
+        ]);
+
+        let mut chunk_stream = ChunkStream::new(input_stream)
+            .idle_timeout(Some(Duration::from_secs(30)));
+
+        assert_eq!(
+            chunk_stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap(),
+            MessageChunk::Ping(PingChunk::default())
+        );
+        assert!(matches!(
+            chunk_stream.next().await,
+            Some(Err(StreamError::IdleTimeout(_)))
+        ));
+        assert!(chunk_stream
+            .next()
+            .await
+            .is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reset_idle_timeout_by_any_byte() {
+        use futures_util::StreamExt;
+
+        let input_stream = delayed_byte_stream(vec![
+            (20, ": keep-alive\n\n"),
+            (20, "event: ping\ndata: {\"type\""),
+            (20, ": \"ping\"}\n\n"),
+        ]);
+
+        let mut chunk_stream = ChunkStream::new(input_stream)
+            .idle_timeout(Some(Duration::from_secs(30)));
+
+        assert_eq!(
+            chunk_stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap(),
+            MessageChunk::Ping(PingChunk::default())
+        );
+        assert!(chunk_stream
+            .next()
+            .await
+            .is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn first_event_timeout() {
+        use futures_util::StreamExt;
+
+        let input_stream = delayed_byte_stream(vec![
+            (10, ": keep-alive\n\n"),
+            (10, ": keep-alive\n\n"),
+            (10, ": keep-alive\n\n"),
+            (10, "event: ping\ndata: {\"type\": \"ping\"}\n\n"),
+        ]);
+
+        let mut chunk_stream = ChunkStream::new(input_stream)
+            .idle_timeout(Some(Duration::from_secs(30)))
+            .first_event_timeout(Some(Duration::from_secs(25)));
+
+        assert!(matches!(
+            chunk_stream.next().await,
+            Some(Err(StreamError::FirstEventTimeout(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn accumulate_tool_use() {
+        use futures_util::StreamExt;
+
+        let source = r#"event: content_block_start
+data: {"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_01", "name": "get_weather", "input": {}}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": ""}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"location\": \"San Fra"}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "ncisco, CA\"}"}}
+
+event: content_block_stop
+data: {"type": "content_block_stop", "index": 1}
+
+"#;
+
+        let input_stream = futures_util::stream::iter(vec![Ok::<_, StreamError>(
+            bytes::Bytes::from(source),
+        )]);
+
+        let chunks = ChunkStream::new(input_stream)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(
+            chunks[2],
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                1,
+                InputJsonDeltaContentBlock::new("{\"location\": \"San Fra")
+                    .into(),
+            ))
+        );
+        match &chunks[4] {
+            | MessageChunk::ContentBlockStop(stop) => {
+                assert_eq!(
+                    stop.tool_use,
+                    Some(ToolUse::new(
+                        "toolu_01",
+                        "get_weather",
+                        serde_json::json!({"location": "San Francisco, CA"}),
+                    ))
+                );
+            },
+            | _ => panic!("unexpected chunk type"),
+        }
+    }
+
+    #[tokio::test]
+    async fn accumulate_tool_use_cut_off_by_max_tokens() {
+        use futures_util::StreamExt;
+
+        let source = r#"event: content_block_start
+data: {"type": "content_block_start", "index": 0, "content_block": {"type": "tool_use", "id": "toolu_01", "name": "get_weather", "input": {}}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "input_json_delta", "partial_json": "{\"location\": \"San Fra"}}
+
+event: content_block_stop
+data: {"type": "content_block_stop", "index": 0}
+
+event: message_delta
+data: {"type": "message_delta", "delta": {"stop_reason": "max_tokens", "stop_sequence": null}, "usage": {"output_tokens": 10}}
+
+event: message_stop
+data: {"type": "message_stop"}
+
+"#;
+
+        let input_stream = futures_util::stream::iter(vec![Ok::<_, StreamError>(
+            bytes::Bytes::from(source),
+        )]);
+
+        let chunks = ChunkStream::new(input_stream)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(chunks.len(), 5);
+        match &chunks[2] {
+            | MessageChunk::ContentBlockStop(stop) => {
+                assert_eq!(
+                    stop.tool_use,
+                    Some(ToolUse::new(
+                        "toolu_01",
+                        "get_weather",
+                        serde_json::json!({}),
+                    ))
+                );
+                assert_eq!(
+                    stop.incomplete_input.as_deref(),
+                    Some("{\"location\": \"San Fra")
+                );
+            },
+            | _ => panic!("unexpected chunk type"),
+        }
+        match &chunks[3] {
+            | MessageChunk::MessageDelta(delta) => {
+                assert_eq!(
+                    delta.delta.stop_reason,
+                    Some(StopReason::MaxTokens)
+                );
+            },
+            | _ => panic!("unexpected chunk type"),
+        }
+    }
+
+    #[test]
+    fn accumulate_tool_use_without_delta() {
+        let mut accumulator = ToolUseAccumulator::default();
+        let tool_use =
+            accumulator.accumulate(&MessageChunk::ContentBlockStart(
+                ContentBlockStartChunk::new(
+                    0,
+                    ToolUseContentBlock::new(ToolUse::new(
+                        "id",
+                        "name",
+                        serde_json::json!({}),
+                    ))
+                    .into(),
+                ),
+            ));
+        assert_eq!(tool_use, None);
+
+        let stop =
+            MessageChunk::ContentBlockStop(ContentBlockStopChunk::new(0));
+        assert_eq!(
+            accumulator.accumulate(&stop),
+            Some((
+                ToolUse::new(
+                    "id",
+                    "name",
+                    serde_json::json!({}),
+                ),
+                None
+            ))
+        );
+        assert_eq!(accumulator.accumulate(&stop), None);
+    }
 }
@@ -0,0 +1,491 @@
+use std::fmt::Display;
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+    impl_enum_struct_serialization,
+};
+
+/// The citation option of a document.
+///
+/// See [citations](https://docs.anthropic.com/en/docs/build-with-claude/citations).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct CitationsOption {
+    /// Whether the citations are enabled.
+    pub enabled: bool,
+}
+
+impl_display_for_serialize!(CitationsOption);
+
+impl CitationsOption {
+    /// Creates a new enabled citation option.
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+        }
+    }
+}
+
+/// A citation that supports a text content block of the response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Citation {
+    /// A character range of a plain text document.
+    CharLocation(CharLocationCitation),
+    /// A page range of a PDF document.
+    PageLocation(PageLocationCitation),
+    /// A content block range of a custom content document.
+    ContentBlockLocation(ContentBlockLocationCitation),
+    /// A content block range of a search result.
+    SearchResultLocation(SearchResultLocationCitation),
+    /// A result of the web search tool.
+    WebSearchResultLocation(WebSearchResultLocationCitation),
+}
+
+impl Default for Citation {
+    fn default() -> Self {
+        Self::CharLocation(CharLocationCitation::default())
+    }
+}
+
+impl_enum_struct_serialization!(
+    Citation,
+    type,
+    CharLocation(CharLocationCitation, "char_location"),
+    PageLocation(PageLocationCitation, "page_location"),
+    ContentBlockLocation(
+        ContentBlockLocationCitation,
+        "content_block_location"
+    ),
+    SearchResultLocation(
+        SearchResultLocationCitation,
+        "search_result_location"
+    ),
+    WebSearchResultLocation(
+        WebSearchResultLocationCitation,
+        "web_search_result_location"
+    )
+);
+
+impl_display_for_serialize!(Citation);
+
+impl Citation {
+    /// The cited text of the source.
+    pub fn cited_text(&self) -> &str {
+        match self {
+            | Citation::CharLocation(citation) => &citation.cited_text,
+            | Citation::PageLocation(citation) => &citation.cited_text,
+            | Citation::ContentBlockLocation(citation) => &citation.cited_text,
+            | Citation::SearchResultLocation(citation) => &citation.cited_text,
+            | Citation::WebSearchResultLocation(citation) => {
+                &citation.cited_text
+            },
+        }
+    }
+
+    /// The title of the cited source, if any.
+    pub fn source_title(&self) -> Option<&str> {
+        match self {
+            | Citation::CharLocation(citation) => {
+                citation.document_title.as_deref()
+            },
+            | Citation::PageLocation(citation) => {
+                citation.document_title.as_deref()
+            },
+            | Citation::ContentBlockLocation(citation) => {
+                citation.document_title.as_deref()
+            },
+            | Citation::SearchResultLocation(citation) => {
+                citation.title.as_deref()
+            },
+            | Citation::WebSearchResultLocation(citation) => {
+                citation.title.as_deref()
+            },
+        }
+    }
+
+    /// The human readable location in the cited source.
+    pub fn location(&self) -> String {
+        match self {
+            | Citation::CharLocation(citation) => format!(
+                "document {}, characters {}-{}",
+                citation.document_index,
+                citation.start_char_index,
+                citation.end_char_index
+            ),
+            | Citation::PageLocation(citation) => format!(
+                "document {}, pages {}-{}",
+                citation.document_index,
+                citation.start_page_number,
+                citation.end_page_number
+            ),
+            | Citation::ContentBlockLocation(citation) => format!(
+                "document {}, blocks {}-{}",
+                citation.document_index,
+                citation.start_block_index,
+                citation.end_block_index
+            ),
+            | Citation::SearchResultLocation(citation) => format!(
+                "{}, blocks {}-{}",
+                citation.source,
+                citation.start_block_index,
+                citation.end_block_index
+            ),
+            | Citation::WebSearchResultLocation(citation) => {
+                citation.url.clone()
+            },
+        }
+    }
+}
+
+/// The type of the citation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CitationType {
+    /// char_location
+    #[default]
+    CharLocation,
+    /// page_location
+    PageLocation,
+    /// content_block_location
+    ContentBlockLocation,
+    /// search_result_location
+    SearchResultLocation,
+    /// web_search_result_location
+    WebSearchResultLocation,
+}
+
+impl Display for CitationType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | CitationType::CharLocation => {
+                write!(f, "char_location")
+            },
+            | CitationType::PageLocation => {
+                write!(f, "page_location")
+            },
+            | CitationType::ContentBlockLocation => {
+                write!(f, "content_block_location")
+            },
+            | CitationType::SearchResultLocation => {
+                write!(f, "search_result_location")
+            },
+            | CitationType::WebSearchResultLocation => {
+                write!(f, "web_search_result_location")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    CitationType,
+    CharLocation => "char_location",
+    PageLocation => "page_location",
+    ContentBlockLocation => "content_block_location",
+    SearchResultLocation => "search_result_location",
+    WebSearchResultLocation => "web_search_result_location"
+);
+
+/// The citation of a character range of a plain text document.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CharLocationCitation {
+    /// The citation type. It is always `char_location`.
+    #[serde(rename = "type")]
+    pub _type: CitationType,
+    /// The cited text.
+    pub cited_text: String,
+    /// The 0-based index of the document in the request.
+    pub document_index: u32,
+    /// The title of the document.
+    pub document_title: Option<String>,
+    /// The 0-based start index of the characters, inclusive.
+    pub start_char_index: u32,
+    /// The 0-based end index of the characters, exclusive.
+    pub end_char_index: u32,
+}
+
+impl Default for CharLocationCitation {
+    fn default() -> Self {
+        Self {
+            _type: CitationType::CharLocation,
+            cited_text: String::new(),
+            document_index: 0,
+            document_title: None,
+            start_char_index: 0,
+            end_char_index: 0,
+        }
+    }
+}
+
+/// The citation of a page range of a PDF document.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PageLocationCitation {
+    /// The citation type. It is always `page_location`.
+    #[serde(rename = "type")]
+    pub _type: CitationType,
+    /// The cited text.
+    pub cited_text: String,
+    /// The 0-based index of the document in the request.
+    pub document_index: u32,
+    /// The title of the document.
+    pub document_title: Option<String>,
+    /// The 1-based start page number, inclusive.
+    pub start_page_number: u32,
+    /// The 1-based end page number, exclusive.
+    pub end_page_number: u32,
+}
+
+impl Default for PageLocationCitation {
+    fn default() -> Self {
+        Self {
+            _type: CitationType::PageLocation,
+            cited_text: String::new(),
+            document_index: 0,
+            document_title: None,
+            start_page_number: 1,
+            end_page_number: 1,
+        }
+    }
+}
+
+/// The citation of a content block range of a custom content document.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ContentBlockLocationCitation {
+    /// The citation type. It is always `content_block_location`.
+    #[serde(rename = "type")]
+    pub _type: CitationType,
+    /// The cited text.
+    pub cited_text: String,
+    /// The 0-based index of the document in the request.
+    pub document_index: u32,
+    /// The title of the document.
+    pub document_title: Option<String>,
+    /// The 0-based start index of the content blocks, inclusive.
+    pub start_block_index: u32,
+    /// The 0-based end index of the content blocks, exclusive.
+    pub end_block_index: u32,
+}
+
+impl Default for ContentBlockLocationCitation {
+    fn default() -> Self {
+        Self {
+            _type: CitationType::ContentBlockLocation,
+            cited_text: String::new(),
+            document_index: 0,
+            document_title: None,
+            start_block_index: 0,
+            end_block_index: 0,
+        }
+    }
+}
+
+/// The citation of a content block range of a search result.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchResultLocationCitation {
+    /// The citation type. It is always `search_result_location`.
+    #[serde(rename = "type")]
+    pub _type: CitationType,
+    /// The cited text.
+    pub cited_text: String,
+    /// The source of the search result, e.g. URL.
+    pub source: String,
+    /// The title of the search result.
+    pub title: Option<String>,
+    /// The 0-based index of the search result in the request.
+    pub search_result_index: u32,
+    /// The 0-based start index of the content blocks, inclusive.
+    pub start_block_index: u32,
+    /// The 0-based end index of the content blocks, exclusive.
+    pub end_block_index: u32,
+}
+
+impl Default for SearchResultLocationCitation {
+    fn default() -> Self {
+        Self {
+            _type: CitationType::SearchResultLocation,
+            cited_text: String::new(),
+            source: String::new(),
+            title: None,
+            search_result_index: 0,
+            start_block_index: 0,
+            end_block_index: 0,
+        }
+    }
+}
+
+/// The citation of a result of the web search tool.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WebSearchResultLocationCitation {
+    /// The citation type. It is always `web_search_result_location`.
+    #[serde(rename = "type")]
+    pub _type: CitationType,
+    /// The cited text.
+    pub cited_text: String,
+    /// The URL of the cited source page.
+    pub url: String,
+    /// The title of the cited source page.
+    pub title: Option<String>,
+    /// The encrypted reference that must be passed back in the multi-turn conversation.
+    pub encrypted_index: String,
+}
+
+impl Default for WebSearchResultLocationCitation {
+    fn default() -> Self {
+        Self {
+            _type: CitationType::WebSearchResultLocation,
+            cited_text: String::new(),
+            url: String::new(),
+            title: None,
+            encrypted_index: String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_citations_option() {
+        assert_eq!(
+            serde_json::to_string(&CitationsOption::enabled()).unwrap(),
+            "{\"enabled\":true}"
+        );
+    }
+
+    #[test]
+    fn display_citation_type() {
+        assert_eq!(
+            CitationType::CharLocation.to_string(),
+            "char_location"
+        );
+        assert_eq!(
+            CitationType::PageLocation.to_string(),
+            "page_location"
+        );
+        assert_eq!(
+            CitationType::ContentBlockLocation.to_string(),
+            "content_block_location"
+        );
+        assert_eq!(
+            CitationType::SearchResultLocation.to_string(),
+            "search_result_location"
+        );
+        assert_eq!(
+            CitationType::WebSearchResultLocation.to_string(),
+            "web_search_result_location"
+        );
+    }
+
+    #[test]
+    fn deserialize_citation() {
+        assert_eq!(
+            serde_json::from_str::<Citation>(
+                "{\"type\":\"char_location\",\"cited_text\":\"The grass is green.\",\"document_index\":0,\"document_title\":\"Example\",\"start_char_index\":0,\"end_char_index\":20}"
+            )
+            .unwrap(),
+            Citation::CharLocation(CharLocationCitation {
+                cited_text: "The grass is green.".to_string(),
+                document_title: Some("Example".to_string()),
+                end_char_index: 20,
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Citation>(
+                "{\"type\":\"page_location\",\"cited_text\":\"text\",\"document_index\":1,\"document_title\":null,\"start_page_number\":2,\"end_page_number\":3}"
+            )
+            .unwrap(),
+            Citation::PageLocation(PageLocationCitation {
+                cited_text: "text".to_string(),
+                document_index: 1,
+                start_page_number: 2,
+                end_page_number: 3,
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Citation>(
+                "{\"type\":\"content_block_location\",\"cited_text\":\"text\",\"document_index\":0,\"document_title\":null,\"start_block_index\":1,\"end_block_index\":2}"
+            )
+            .unwrap(),
+            Citation::ContentBlockLocation(ContentBlockLocationCitation {
+                cited_text: "text".to_string(),
+                start_block_index: 1,
+                end_block_index: 2,
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Citation>(
+                "{\"type\":\"search_result_location\",\"cited_text\":\"text\",\"source\":\"https://example.com\",\"title\":\"Example\",\"search_result_index\":0,\"start_block_index\":0,\"end_block_index\":1}"
+            )
+            .unwrap(),
+            Citation::SearchResultLocation(SearchResultLocationCitation {
+                cited_text: "text".to_string(),
+                source: "https://example.com".to_string(),
+                title: Some("Example".to_string()),
+                end_block_index: 1,
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Citation>(
+                "{\"type\":\"web_search_result_location\",\"cited_text\":\"text\",\"url\":\"https://example.com\",\"title\":\"Example\",\"encrypted_index\":\"abc\"}"
+            )
+            .unwrap(),
+            Citation::WebSearchResultLocation(WebSearchResultLocationCitation {
+                cited_text: "text".to_string(),
+                url: "https://example.com".to_string(),
+                title: Some("Example".to_string()),
+                encrypted_index: "abc".to_string(),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_citation() {
+        let citation = Citation::PageLocation(PageLocationCitation {
+            cited_text: "text".to_string(),
+            start_page_number: 1,
+            end_page_number: 2,
+            ..Default::default()
+        });
+        assert_eq!(
+            serde_json::to_string(&citation).unwrap(),
+            "{\"type\":\"page_location\",\"cited_text\":\"text\",\"document_index\":0,\"document_title\":null,\"start_page_number\":1,\"end_page_number\":2}"
+        );
+    }
+
+    #[test]
+    fn citation_accessors() {
+        let citation = Citation::PageLocation(PageLocationCitation {
+            cited_text: "text".to_string(),
+            document_title: Some("title".to_string()),
+            start_page_number: 1,
+            end_page_number: 2,
+            ..Default::default()
+        });
+        assert_eq!(citation.cited_text(), "text");
+        assert_eq!(citation.source_title(), Some("title"));
+        assert_eq!(
+            citation.location(),
+            "document 0, pages 1-2"
+        );
+    }
+}
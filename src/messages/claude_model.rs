@@ -4,13 +4,14 @@ use std::fmt::Display;
 /// The model that will complete your prompt.
 ///
 /// See [models](https://docs.anthropic.com/claude/docs/models-overview) for additional details and options.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ClaudeModel {
     // Claude 3 Opus
     /// Claude 3 Opus at 2024/02/29.
     Claude3Opus20240229,
     // Claude 3 Sonnet
     /// Claude 3 Sonnet at 2024/02/29.
+    #[default]
     Claude3Sonnet20240229,
     // Claude 3 Haiku
     /// Claude 3 Haiku at 2024/03/07.
@@ -20,12 +21,6 @@ pub enum ClaudeModel {
     Claude35Sonnet20240620,
 }
 
-impl Default for ClaudeModel {
-    fn default() -> Self {
-        Self::Claude3Sonnet20240229
-    }
-}
-
 impl Display for ClaudeModel {
     fn fmt(
         &self,
@@ -57,6 +52,16 @@ impl ClaudeModel {
             | ClaudeModel::Claude35Sonnet20240620 => 4096,
         }
     }
+
+    /// The minimum number of tokens of a cacheable prompt prefix.
+    pub(crate) fn minimum_cacheable_tokens(&self) -> u32 {
+        match self {
+            | ClaudeModel::Claude3Opus20240229 => 1024,
+            | ClaudeModel::Claude3Sonnet20240229 => 1024,
+            | ClaudeModel::Claude3Haiku20240307 => 2048,
+            | ClaudeModel::Claude35Sonnet20240620 => 1024,
+        }
+    }
 }
 
 impl_enum_string_serialization!(
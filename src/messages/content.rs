@@ -1,5 +1,7 @@
 use std::fmt::Display;
-use std::path::PathBuf;
+use std::path::Path;
+
+use base64::Engine;
 
 use crate::macros::{
     impl_display_for_serialize, impl_enum_string_serialization,
@@ -7,7 +9,10 @@ use crate::macros::{
     impl_enum_with_string_or_array_serialization,
 };
 use crate::messages::{
-    ContentFlatteningError, ImageMediaTypeParseError, ToolResult, ToolUse,
+    CacheControl, Citation, CitationsOption, CodeExecutionToolResultContent,
+    ContentFlatteningError, DocumentLoadError, DocumentMediaTypeParseError,
+    ImageLoadError, ImageMediaTypeParseError, ToolResult, ToolUse,
+    WebSearchToolResultContent,
 };
 
 /// The content of the message.
@@ -90,6 +95,14 @@ impl From<ImageContentSource> for Content {
     }
 }
 
+impl From<DocumentContentSource> for Content {
+    fn from(document: DocumentContentSource) -> Self {
+        Self::MultipleBlocks(vec![ContentBlock::Document(
+            document.into(),
+        )])
+    }
+}
+
 impl From<ToolUse> for Content {
     fn from(tool_use: ToolUse) -> Self {
         Self::MultipleBlocks(vec![
@@ -115,6 +128,60 @@ impl_enum_with_string_or_array_serialization!(
 impl_display_for_serialize!(Content);
 
 impl Content {
+    /// Renders the text blocks with footnote markers of the citations, e.g.
+    ///
+    /// ```text
+    /// The grass is green[1].
+    ///
+    /// [1] "The grass is green." - Example document (document 0, characters 0-20)
+    /// ```
+    ///
+    /// The same citations share a footnote number. The other blocks than text are ignored.
+    pub fn render_with_footnotes(&self) -> String {
+        let blocks = match self {
+            | Content::SingleText(text) => return text.clone(),
+            | Content::MultipleBlocks(blocks) => blocks,
+        };
+
+        let mut rendered = String::new();
+        let mut footnotes: Vec<&Citation> = Vec::new();
+        for block in blocks {
+            if let ContentBlock::Text(text) = block {
+                rendered.push_str(&text.text);
+                for citation in text.citations.iter().flatten() {
+                    let number = match footnotes
+                        .iter()
+                        .position(|footnote| *footnote == citation)
+                    {
+                        | Some(position) => position + 1,
+                        | None => {
+                            footnotes.push(citation);
+                            footnotes.len()
+                        },
+                    };
+                    rendered.push_str(&format!("[{}]", number));
+                }
+            }
+        }
+
+        if !footnotes.is_empty() {
+            rendered.push('\n');
+            for (index, citation) in footnotes.iter().enumerate() {
+                rendered.push_str(&format!(
+                    "\n[{}] \"{}\"",
+                    index + 1,
+                    citation.cited_text().trim()
+                ));
+                if let Some(title) = citation.source_title() {
+                    rendered.push_str(&format!(" - {}", title));
+                }
+                rendered.push_str(&format!(" ({})", citation.location()));
+            }
+        }
+
+        rendered
+    }
+
     /// Flattens the content into a single text.
     /// - `Content::SingleText` => Returns "`Ok(text)`"
     /// - `Content::MultipleBlock` =>
@@ -155,6 +222,28 @@ impl Content {
         }
     }
 
+    /// Flattens the content into a single document source.
+    /// - `Content::SingleText` => Returns "`Err(NotFoundTargetBlock)`"
+    /// - `Content::MultipleBlock` =>
+    ///     - Has `ContentBlock::Document` at the first block => Returns "`Ok(document_source)`"
+    ///     - Otherwise => Returns "`Err(NotFoundTargetBlock)`".
+    pub fn flatten_into_document_source(
+        &self
+    ) -> Result<&DocumentContentSource, ContentFlatteningError> {
+        match self {
+            | Content::SingleText(_) => {
+                Err(ContentFlatteningError::NotFoundTargetBlock)
+            },
+            | Content::MultipleBlocks(blocks) => match blocks.first() {
+                | Some(first) => match first {
+                    | ContentBlock::Document(document) => Ok(&document.source),
+                    | _ => Err(ContentFlatteningError::NotFoundTargetBlock),
+                },
+                | None => Err(ContentFlatteningError::Empty),
+            },
+        }
+    }
+
     /// Flattens the content into a single tool use.
     /// - `Content::SingleText` => Returns "`Err(NotFoundTargetBlock)`"
     /// - `Content::MultipleBlock` =>
@@ -202,6 +291,182 @@ impl Content {
             },
         }
     }
+
+    /// Iterates over all texts: the single text or the texts of all text blocks.
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        let single = match self {
+            | Content::SingleText(text) => Some(text.as_str()),
+            | Content::MultipleBlocks(_) => None,
+        };
+
+        single.into_iter().chain(
+            self.block_slice()
+                .iter()
+                .filter_map(|block| match block {
+                    | ContentBlock::Text(text) => Some(text.text.as_str()),
+                    | _ => None,
+                }),
+        )
+    }
+
+    /// Concatenates all texts into a single text.
+    pub fn concatenated_text(&self) -> String {
+        self.texts().collect()
+    }
+
+    /// Iterates over all tool uses, e.g. parallel tool uses of the assistant.
+    pub fn tool_uses(&self) -> impl Iterator<Item = &ToolUse> {
+        self.block_slice()
+            .iter()
+            .filter_map(|block| match block {
+                | ContentBlock::ToolUse(tool_use) => Some(&tool_use.tool_use),
+                | _ => None,
+            })
+    }
+
+    /// Iterates over all tool results.
+    pub fn tool_results(&self) -> impl Iterator<Item = &ToolResult> {
+        self.block_slice()
+            .iter()
+            .filter_map(|block| match block {
+                | ContentBlock::ToolResult(tool_result) => {
+                    Some(&tool_result.tool_result)
+                },
+                | _ => None,
+            })
+    }
+
+    /// Iterates over all image sources.
+    pub fn images(&self) -> impl Iterator<Item = &ImageContentSource> {
+        self.block_slice()
+            .iter()
+            .filter_map(|block| match block {
+                | ContentBlock::Image(image) => Some(&image.source),
+                | _ => None,
+            })
+    }
+
+    /// Whether the content has no text and no blocks.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            | Content::SingleText(text) => text.is_empty(),
+            | Content::MultipleBlocks(blocks) => blocks.is_empty(),
+        }
+    }
+
+    /// Gets the mutable blocks by converting `Content::SingleText` into `Content::MultipleBlocks`.
+    ///
+    /// An empty single text is converted into no blocks.
+    pub fn blocks_mut(&mut self) -> &mut Vec<ContentBlock> {
+        if let Content::SingleText(text) = self {
+            let blocks = if text.is_empty() {
+                Vec::new()
+            } else {
+                vec![ContentBlock::Text(TextContentBlock::new(
+                    std::mem::take(text),
+                ))]
+            };
+            *self = Content::MultipleBlocks(blocks);
+        }
+
+        match self {
+            | Content::MultipleBlocks(blocks) => blocks,
+            | Content::SingleText(_) => unreachable!(),
+        }
+    }
+
+    /// Converts the content into blocks.
+    ///
+    /// An empty single text is converted into no blocks.
+    pub fn into_blocks(mut self) -> Vec<ContentBlock> {
+        std::mem::take(self.blocks_mut())
+    }
+
+    /// Appends a block to the end of the content.
+    pub fn push<T>(
+        &mut self,
+        block: T,
+    ) where
+        T: Into<ContentBlock>,
+    {
+        self.blocks_mut()
+            .push(block.into());
+    }
+
+    /// Appends blocks to the end of the content.
+    pub fn extend<I, T>(
+        &mut self,
+        blocks: I,
+    ) where
+        I: IntoIterator<Item = T>,
+        T: Into<ContentBlock>,
+    {
+        self.blocks_mut().extend(
+            blocks
+                .into_iter()
+                .map(Into::into),
+        );
+    }
+
+    /// Retains only the blocks specified by the predicate.
+    pub fn retain<F>(
+        &mut self,
+        predicate: F,
+    ) where
+        F: FnMut(&ContentBlock) -> bool,
+    {
+        self.blocks_mut()
+            .retain(predicate);
+    }
+
+    /// Maps each block into another block.
+    pub fn map_blocks<F>(
+        self,
+        f: F,
+    ) -> Self
+    where
+        F: FnMut(ContentBlock) -> ContentBlock,
+    {
+        Content::MultipleBlocks(
+            self.into_blocks()
+                .into_iter()
+                .map(f)
+                .collect(),
+        )
+    }
+
+    /// Normalizes the content into the simplest form.
+    ///
+    /// - A single text block without citations and cache control => `Content::SingleText`
+    /// - No blocks => An empty `Content::SingleText`
+    /// - Otherwise => As it is
+    pub fn normalize(self) -> Self {
+        match self {
+            | Content::MultipleBlocks(blocks) if blocks.is_empty() => {
+                Content::SingleText(String::new())
+            },
+            | Content::MultipleBlocks(blocks) => {
+                match <[ContentBlock; 1]>::try_from(blocks) {
+                    | Ok([ContentBlock::Text(text)])
+                        if text.citations.is_none()
+                            && text.cache_control.is_none() =>
+                    {
+                        Content::SingleText(text.text)
+                    },
+                    | Ok(block) => Content::MultipleBlocks(block.into()),
+                    | Err(blocks) => Content::MultipleBlocks(blocks),
+                }
+            },
+            | single => single,
+        }
+    }
+
+    fn block_slice(&self) -> &[ContentBlock] {
+        match self {
+            | Content::SingleText(_) => &[],
+            | Content::MultipleBlocks(blocks) => blocks,
+        }
+    }
 }
 
 /// The content block of the message.
@@ -211,10 +476,24 @@ pub enum ContentBlock {
     Text(TextContentBlock),
     /// The image content block.
     Image(ImageContentBlock),
+    /// The document content block.
+    Document(DocumentContentBlock),
     /// The tool use content block.
     ToolUse(ToolUseContentBlock),
     /// The tool result content block.
     ToolResult(ToolResultContentBlock),
+    /// The thinking content block.
+    Thinking(ThinkingContentBlock),
+    /// The redacted thinking content block.
+    RedactedThinking(RedactedThinkingContentBlock),
+    /// Server tool use content block.
+    ServerToolUse(ServerToolUseContentBlock),
+    /// Web search tool result content block.
+    WebSearchToolResult(WebSearchToolResultContentBlock),
+    /// Code execution tool result content block.
+    CodeExecutionToolResult(CodeExecutionToolResultContentBlock),
+    /// A content block of an unknown type that is kept as the raw JSON to be sent back as it is.
+    Unknown(serde_json::Value),
 }
 
 impl Default for ContentBlock {
@@ -241,6 +520,12 @@ impl From<ImageContentSource> for ContentBlock {
     }
 }
 
+impl From<DocumentContentSource> for ContentBlock {
+    fn from(document: DocumentContentSource) -> Self {
+        Self::Document(DocumentContentBlock::new(document))
+    }
+}
+
 impl From<ToolUse> for ContentBlock {
     fn from(tool_use: ToolUse) -> Self {
         Self::ToolUse(tool_use.into())
@@ -258,8 +543,21 @@ impl_enum_struct_serialization!(
     type,
     Text(TextContentBlock, "text"),
     Image(ImageContentBlock, "image"),
+    Document(DocumentContentBlock, "document"),
     ToolUse(ToolUseContentBlock, "tool_use"),
-    ToolResult(ToolResultContentBlock, "tool_result")
+    ToolResult(ToolResultContentBlock, "tool_result"),
+    Thinking(ThinkingContentBlock, "thinking"),
+    RedactedThinking(RedactedThinkingContentBlock, "redacted_thinking"),
+    ServerToolUse(ServerToolUseContentBlock, "server_tool_use"),
+    WebSearchToolResult(
+        WebSearchToolResultContentBlock,
+        "web_search_tool_result"
+    ),
+    CodeExecutionToolResult(
+        CodeExecutionToolResultContentBlock,
+        "code_execution_tool_result"
+    );
+    _ => Unknown
 );
 
 impl_display_for_serialize!(ContentBlock);
@@ -272,6 +570,12 @@ pub struct TextContentBlock {
     pub _type: ContentType,
     /// The text content.
     pub text: String,
+    /// The citations that support the text in the response.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub citations: Option<Vec<Citation>>,
+    /// The cache control of the block.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
 }
 
 impl Default for TextContentBlock {
@@ -279,6 +583,8 @@ impl Default for TextContentBlock {
         Self {
             _type: ContentType::Text,
             text: String::new(),
+            citations: None,
+            cache_control: None,
         }
     }
 }
@@ -306,8 +612,19 @@ impl TextContentBlock {
         Self {
             _type: ContentType::Text,
             text: text.into(),
+            citations: None,
+            cache_control: None,
         }
     }
+
+    /// Sets the cache control of the block.
+    pub fn with_cache_control(
+        mut self,
+        cache_control: CacheControl,
+    ) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
 }
 
 /// The image content block.
@@ -318,6 +635,9 @@ pub struct ImageContentBlock {
     pub _type: ContentType,
     /// The image content source.
     pub source: ImageContentSource,
+    /// The cache control of the block.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
 }
 
 impl Default for ImageContentBlock {
@@ -325,6 +645,7 @@ impl Default for ImageContentBlock {
         Self {
             _type: ContentType::Image,
             source: ImageContentSource::default(),
+            cache_control: None,
         }
     }
 }
@@ -343,29 +664,54 @@ impl ImageContentBlock {
         Self {
             _type: ContentType::Image,
             source,
+            cache_control: None,
         }
     }
+
+    /// Sets the cache control of the block.
+    pub fn with_cache_control(
+        mut self,
+        cache_control: CacheControl,
+    ) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
 }
 
 /// The content type of the message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ContentType {
     /// text
+    #[default]
     Text,
     /// image
     Image,
+    /// document
+    Document,
     /// text_delta
     TextDelta,
+    /// citations_delta
+    CitationsDelta,
+    /// thinking
+    Thinking,
+    /// redacted_thinking
+    RedactedThinking,
+    /// thinking_delta
+    ThinkingDelta,
+    /// signature_delta
+    SignatureDelta,
+    /// input_json_delta
+    InputJsonDelta,
     /// tool_use
     ToolUse,
     /// tool_result
     ToolResult,
-}
-
-impl Default for ContentType {
-    fn default() -> Self {
-        Self::Text
-    }
+    /// server_tool_use
+    ServerToolUse,
+    /// web_search_tool_result
+    WebSearchToolResult,
+    /// code_execution_tool_result
+    CodeExecutionToolResult,
 }
 
 impl Display for ContentType {
@@ -380,15 +726,45 @@ impl Display for ContentType {
             | ContentType::Image => {
                 write!(f, "image")
             },
+            | ContentType::Document => {
+                write!(f, "document")
+            },
             | ContentType::TextDelta => {
                 write!(f, "text_delta")
             },
+            | ContentType::CitationsDelta => {
+                write!(f, "citations_delta")
+            },
+            | ContentType::Thinking => {
+                write!(f, "thinking")
+            },
+            | ContentType::RedactedThinking => {
+                write!(f, "redacted_thinking")
+            },
+            | ContentType::ThinkingDelta => {
+                write!(f, "thinking_delta")
+            },
+            | ContentType::SignatureDelta => {
+                write!(f, "signature_delta")
+            },
+            | ContentType::InputJsonDelta => {
+                write!(f, "input_json_delta")
+            },
             | ContentType::ToolUse => {
                 write!(f, "tool_use")
             },
             | ContentType::ToolResult => {
                 write!(f, "tool_result")
             },
+            | ContentType::ServerToolUse => {
+                write!(f, "server_tool_use")
+            },
+            | ContentType::WebSearchToolResult => {
+                write!(f, "web_search_tool_result")
+            },
+            | ContentType::CodeExecutionToolResult => {
+                write!(f, "code_execution_tool_result")
+            },
         }
     }
 }
@@ -397,36 +773,57 @@ impl_enum_string_serialization!(
     ContentType,
     Text => "text",
     Image => "image",
+    Document => "document",
     TextDelta => "text_delta",
+    CitationsDelta => "citations_delta",
+    Thinking => "thinking",
+    RedactedThinking => "redacted_thinking",
+    ThinkingDelta => "thinking_delta",
+    SignatureDelta => "signature_delta",
+    InputJsonDelta => "input_json_delta",
     ToolUse => "tool_use",
-    ToolResult => "tool_result"
+    ToolResult => "tool_result",
+    ServerToolUse => "server_tool_use",
+    WebSearchToolResult => "web_search_tool_result",
+    CodeExecutionToolResult => "code_execution_tool_result"
 );
 
 /// The image content source.
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct ImageContentSource {
-    /// The source type.
-    #[serde(rename = "type")]
-    pub _type: ImageSourceType,
-    /// The media type.
-    pub media_type: ImageMediaType,
-    ///  The data of the image.
-    pub data: String,
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{ImageContentSource, ImageMediaType};
+///
+/// let source = ImageContentSource::base64(ImageMediaType::Png, "base64");
+/// let source = ImageContentSource::url("https://example.com/image.png");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageContentSource {
+    /// The Base64 encoded image source.
+    Base64(Base64ImageSource),
+    /// The URL image source.
+    Url(UrlImageSource),
 }
 
 impl Default for ImageContentSource {
     fn default() -> Self {
-        Self {
-            _type: ImageSourceType::default(),
-            media_type: ImageMediaType::default(),
-            data: String::new(),
-        }
+        Self::Base64(Base64ImageSource::default())
     }
 }
 
+impl_enum_struct_serialization!(
+    ImageContentSource,
+    type,
+    Base64(Base64ImageSource, "base64"),
+    Url(UrlImageSource, "url")
+);
+
 impl_display_for_serialize!(ImageContentSource);
 
 impl ImageContentSource {
+    /// The maximum size of the Base64 encoded image data in bytes accepted by the API.
+    pub const MAX_BASE64_SIZE: usize = 5 * 1024 * 1024;
+
     /// Creates a new image content source from Base64 encoded image data.
     ///
     /// ## Arguments
@@ -439,25 +836,135 @@ impl ImageContentSource {
     where
         S: Into<String>,
     {
-        Self {
+        Self::Base64(Base64ImageSource {
             _type: ImageSourceType::Base64,
             media_type,
             data: data.into(),
+        })
+    }
+
+    /// Creates a new image content source from a URL of the image.
+    ///
+    /// ## Arguments
+    /// - `url` - The URL of the image.
+    pub fn url<S>(url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Url(UrlImageSource {
+            _type: ImageSourceType::Url,
+            url: url.into(),
+        })
+    }
+
+    /// Creates a new image content source from raw bytes of the image,
+    /// detecting the media type by sniffing the magic bytes.
+    ///
+    /// ## Arguments
+    /// - `bytes` - The raw bytes of the image.
+    ///
+    /// ## Errors
+    /// It returns an error if the media type is not detected or the encoded image is too large.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageLoadError> {
+        let media_type = ImageMediaType::from_magic_bytes(bytes)?;
+
+        Self::from_bytes_with_media_type(media_type, bytes)
+    }
+
+    /// Creates a new image content source from raw bytes of the image with the media type.
+    ///
+    /// ## Arguments
+    /// - `media_type` - The media type of the image.
+    /// - `bytes` - The raw bytes of the image.
+    ///
+    /// ## Errors
+    /// It returns an error if the encoded image is too large.
+    pub fn from_bytes_with_media_type(
+        media_type: ImageMediaType,
+        bytes: &[u8],
+    ) -> Result<Self, ImageLoadError> {
+        let size = base64::encoded_len(bytes.len(), true)
+            .unwrap_or(usize::MAX);
+        if size > Self::MAX_BASE64_SIZE {
+            return Err(ImageLoadError::TooLarge {
+                size,
+                max: Self::MAX_BASE64_SIZE,
+            });
+        }
+
+        Ok(Self::base64(
+            media_type,
+            base64::engine::general_purpose::STANDARD.encode(bytes),
+        ))
+    }
+
+    /// Loads an image content source from a file.
+    ///
+    /// The media type is detected by sniffing the magic bytes of the file,
+    /// and falls back to [`ImageMediaType::from_path`] when the signature is unknown.
+    ///
+    /// ## Arguments
+    /// - `path` - The path of the image file.
+    ///
+    /// ## Errors
+    /// It returns an error if reading the file failed, the media type is not supported or the encoded image is too large.
+    pub fn from_path(path: &Path) -> Result<Self, ImageLoadError> {
+        let bytes =
+            std::fs::read(path).map_err(ImageLoadError::ReadFileFailed)?;
+        let media_type = match ImageMediaType::from_magic_bytes(&bytes) {
+            | Ok(media_type) => media_type,
+            | Err(_) => ImageMediaType::from_path(path)?,
+        };
+
+        Self::from_bytes_with_media_type(media_type, &bytes)
+    }
+}
+
+/// The Base64 encoded image source.
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct Base64ImageSource {
+    /// The source type. It is always `base64`.
+    #[serde(rename = "type")]
+    pub _type: ImageSourceType,
+    /// The media type.
+    pub media_type: ImageMediaType,
+    ///  The data of the image.
+    pub data: String,
+}
+
+impl_display_for_serialize!(Base64ImageSource);
+
+/// The URL image source.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UrlImageSource {
+    /// The source type. It is always `url`.
+    #[serde(rename = "type")]
+    pub _type: ImageSourceType,
+    /// The URL of the image.
+    pub url: String,
+}
+
+impl Default for UrlImageSource {
+    fn default() -> Self {
+        Self {
+            _type: ImageSourceType::Url,
+            url: String::new(),
         }
     }
 }
 
+impl_display_for_serialize!(UrlImageSource);
+
 /// The source type of the image.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ImageSourceType {
     /// base64
+    #[default]
     Base64,
-}
-
-impl Default for ImageSourceType {
-    fn default() -> Self {
-        Self::Base64
-    }
+    /// url
+    Url,
 }
 
 impl Display for ImageSourceType {
@@ -469,19 +976,24 @@ impl Display for ImageSourceType {
             | ImageSourceType::Base64 => {
                 write!(f, "base64")
             },
+            | ImageSourceType::Url => {
+                write!(f, "url")
+            },
         }
     }
 }
 
 impl_enum_string_serialization!(
     ImageSourceType,
-    Base64 => "base64"
+    Base64 => "base64",
+    Url => "url"
 );
 
 /// The media type of the image.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ImageMediaType {
     /// image/jpeg
+    #[default]
     Jpeg,
     /// image/png
     Png,
@@ -491,12 +1003,6 @@ pub enum ImageMediaType {
     Webp,
 }
 
-impl Default for ImageMediaType {
-    fn default() -> Self {
-        Self::Jpeg
-    }
-}
-
 impl Display for ImageMediaType {
     fn fmt(
         &self,
@@ -529,7 +1035,7 @@ impl_enum_string_serialization!(
 
 impl ImageMediaType {
     /// Creates the media type from the extension of the path.
-    pub fn from_path(path: &PathBuf) -> Result<Self, ImageMediaTypeParseError> {
+    pub fn from_path(path: &Path) -> Result<Self, ImageMediaTypeParseError> {
         match path
             .extension()
             .and_then(|ext| ext.to_str())
@@ -544,80 +1050,736 @@ impl ImageMediaType {
             | None => Err(ImageMediaTypeParseError::NotFound),
         }
     }
+
+    /// Detects the media type from the magic bytes at the head of the image data.
+    pub fn from_magic_bytes(
+        bytes: &[u8]
+    ) -> Result<Self, ImageMediaTypeParseError> {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Ok(Self::Jpeg)
+        } else if bytes.starts_with(&[
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A,
+        ]) {
+            Ok(Self::Png)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a")
+        {
+            Ok(Self::Gif)
+        } else if bytes.len() >= 12
+            && bytes.starts_with(b"RIFF")
+            && &bytes[8..12] == b"WEBP"
+        {
+            Ok(Self::Webp)
+        } else {
+            Err(ImageMediaTypeParseError::UnknownSignature)
+        }
+    }
 }
 
-/// The tool use content block.
+/// The document content block.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct ToolUseContentBlock {
-    /// The content type. It is always `tool_use`.
+pub struct DocumentContentBlock {
+    /// The content type. It is always `document`.
     #[serde(rename = "type")]
     pub _type: ContentType,
-    /// The tool use.
-    #[serde(flatten)]
-    pub tool_use: ToolUse,
+    /// The document content source.
+    pub source: DocumentContentSource,
+    /// The optional title of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The optional context about the document, e.g. metadata that is not cited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// The citation option of the document.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub citations: Option<CitationsOption>,
+    /// The cache control of the block.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
 }
 
-impl Default for ToolUseContentBlock {
+impl Default for DocumentContentBlock {
     fn default() -> Self {
         Self {
-            _type: ContentType::ToolUse,
-            tool_use: ToolUse::default(),
+            _type: ContentType::Document,
+            source: DocumentContentSource::default(),
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
         }
     }
 }
 
-impl_display_for_serialize!(ToolUseContentBlock);
+impl_display_for_serialize!(DocumentContentBlock);
 
-impl From<ToolUse> for ToolUseContentBlock {
-    fn from(tool_use: ToolUse) -> Self {
-        Self::new(tool_use)
+impl From<DocumentContentSource> for DocumentContentBlock {
+    fn from(source: DocumentContentSource) -> Self {
+        Self::new(source)
     }
 }
 
-impl ToolUseContentBlock {
-    /// Creates a new tool use content block.
-    pub fn new(tool_use: ToolUse) -> Self {
+impl DocumentContentBlock {
+    /// Creates a new document content block.
+    pub fn new(source: DocumentContentSource) -> Self {
         Self {
-            _type: ContentType::ToolUse,
-            tool_use,
+            _type: ContentType::Document,
+            source,
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
         }
     }
-}
 
-/// The tool result content block.
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-pub struct ToolResultContentBlock {
-    /// The content type. It is always `tool_result`.
-    #[serde(rename = "type")]
-    pub _type: ContentType,
-    /// The tool result.
-    #[serde(flatten)]
-    pub tool_result: ToolResult,
-}
+    /// Sets the title of the document.
+    pub fn with_title<S>(
+        mut self,
+        title: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
 
-impl Default for ToolResultContentBlock {
-    fn default() -> Self {
-        Self {
-            _type: ContentType::ToolResult,
-            tool_result: ToolResult::default(),
-        }
+    /// Sets the context of the document.
+    pub fn with_context<S>(
+        mut self,
+        context: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.context = Some(context.into());
+        self
     }
-}
 
-impl_display_for_serialize!(ToolResultContentBlock);
+    /// Enables the citations of the document.
+    pub fn with_citations(mut self) -> Self {
+        self.citations = Some(CitationsOption::enabled());
+        self
+    }
 
-impl From<ToolResult> for ToolResultContentBlock {
-    fn from(tool_result: ToolResult) -> Self {
-        Self::new(tool_result)
+    /// Sets the cache control of the block.
+    pub fn with_cache_control(
+        mut self,
+        cache_control: CacheControl,
+    ) -> Self {
+        self.cache_control = Some(cache_control);
+        self
     }
 }
 
-impl ToolResultContentBlock {
-    /// Creates a new tool result content block.
-    pub fn new(tool_result: ToolResult) -> Self {
-        Self {
+/// The document content source.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{Content, DocumentContentSource};
+///
+/// let source = DocumentContentSource::base64_pdf("base64");
+/// let source = DocumentContentSource::plain_text("text");
+/// let source = DocumentContentSource::url("https://example.com/document.pdf");
+/// let source = DocumentContentSource::content(vec!["chunk 1".into(), "chunk 2".into()]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentContentSource {
+    /// The Base64 encoded PDF source.
+    Base64(Base64DocumentSource),
+    /// The plain text source.
+    Text(PlainTextDocumentSource),
+    /// The URL PDF source.
+    Url(UrlDocumentSource),
+    /// The custom content source that consists of chunked content blocks.
+    Content(ContentDocumentSource),
+}
+
+impl Default for DocumentContentSource {
+    fn default() -> Self {
+        Self::Base64(Base64DocumentSource::default())
+    }
+}
+
+impl_enum_struct_serialization!(
+    DocumentContentSource,
+    type,
+    Base64(Base64DocumentSource, "base64"),
+    Text(PlainTextDocumentSource, "text"),
+    Url(UrlDocumentSource, "url"),
+    Content(ContentDocumentSource, "content")
+);
+
+impl_display_for_serialize!(DocumentContentSource);
+
+impl DocumentContentSource {
+    /// Creates a new document source from Base64 encoded PDF data.
+    ///
+    /// ## Arguments
+    /// - `data` - The Base64 encoded data of the PDF.
+    pub fn base64_pdf<S>(data: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Base64(Base64DocumentSource {
+            _type: DocumentSourceType::Base64,
+            media_type: DocumentMediaType::Pdf,
+            data: data.into(),
+        })
+    }
+
+    /// Creates a new document source from plain text.
+    ///
+    /// ## Arguments
+    /// - `data` - The text of the document.
+    pub fn plain_text<S>(data: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Text(PlainTextDocumentSource {
+            _type: DocumentSourceType::Text,
+            media_type: DocumentMediaType::PlainText,
+            data: data.into(),
+        })
+    }
+
+    /// Creates a new document source from a URL of a PDF.
+    ///
+    /// ## Arguments
+    /// - `url` - The URL of the PDF.
+    pub fn url<S>(url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Url(UrlDocumentSource {
+            _type: DocumentSourceType::Url,
+            url: url.into(),
+        })
+    }
+
+    /// Creates a new custom document source from content blocks.
+    ///
+    /// ## Arguments
+    /// - `content` - The content of the document, e.g. chunked text blocks.
+    pub fn content<T>(content: T) -> Self
+    where
+        T: Into<Content>,
+    {
+        Self::Content(ContentDocumentSource {
+            _type: DocumentSourceType::Content,
+            content: content.into(),
+        })
+    }
+
+    /// Creates a new document source from raw bytes of the document.
+    ///
+    /// ## Arguments
+    /// - `media_type` - The media type of the document.
+    /// - `bytes` - The raw bytes of the document.
+    ///
+    /// ## Errors
+    /// It returns an error if the plain text is not valid UTF-8.
+    pub fn from_bytes(
+        media_type: DocumentMediaType,
+        bytes: &[u8],
+    ) -> Result<Self, DocumentLoadError> {
+        match media_type {
+            | DocumentMediaType::Pdf => Ok(Self::base64_pdf(
+                base64::engine::general_purpose::STANDARD.encode(bytes),
+            )),
+            | DocumentMediaType::PlainText => {
+                let text = String::from_utf8(bytes.to_vec())
+                    .map_err(DocumentLoadError::StringDecodingFailed)?;
+                Ok(Self::plain_text(text))
+            },
+        }
+    }
+
+    /// Loads a document source from a file,
+    /// detecting the media type by [`DocumentMediaType::from_path`].
+    ///
+    /// ## Arguments
+    /// - `path` - The path of the document file.
+    ///
+    /// ## Errors
+    /// It returns an error if the media type is not supported or reading the file failed.
+    pub fn from_path(path: &Path) -> Result<Self, DocumentLoadError> {
+        let media_type = DocumentMediaType::from_path(path)?;
+        let bytes =
+            std::fs::read(path).map_err(DocumentLoadError::ReadFileFailed)?;
+
+        Self::from_bytes(media_type, &bytes)
+    }
+}
+
+/// The Base64 encoded PDF document source.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Base64DocumentSource {
+    /// The source type. It is always `base64`.
+    #[serde(rename = "type")]
+    pub _type: DocumentSourceType,
+    /// The media type. It is always `application/pdf`.
+    pub media_type: DocumentMediaType,
+    /// The Base64 encoded data of the PDF.
+    pub data: String,
+}
+
+impl Default for Base64DocumentSource {
+    fn default() -> Self {
+        Self {
+            _type: DocumentSourceType::Base64,
+            media_type: DocumentMediaType::Pdf,
+            data: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(Base64DocumentSource);
+
+/// The plain text document source.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlainTextDocumentSource {
+    /// The source type. It is always `text`.
+    #[serde(rename = "type")]
+    pub _type: DocumentSourceType,
+    /// The media type. It is always `text/plain`.
+    pub media_type: DocumentMediaType,
+    /// The text of the document.
+    pub data: String,
+}
+
+impl Default for PlainTextDocumentSource {
+    fn default() -> Self {
+        Self {
+            _type: DocumentSourceType::Text,
+            media_type: DocumentMediaType::PlainText,
+            data: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(PlainTextDocumentSource);
+
+/// The URL PDF document source.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UrlDocumentSource {
+    /// The source type. It is always `url`.
+    #[serde(rename = "type")]
+    pub _type: DocumentSourceType,
+    /// The URL of the PDF.
+    pub url: String,
+}
+
+impl Default for UrlDocumentSource {
+    fn default() -> Self {
+        Self {
+            _type: DocumentSourceType::Url,
+            url: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(UrlDocumentSource);
+
+/// The custom content document source.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ContentDocumentSource {
+    /// The source type. It is always `content`.
+    #[serde(rename = "type")]
+    pub _type: DocumentSourceType,
+    /// The content of the document as a text or a list of content blocks.
+    pub content: Content,
+}
+
+impl Default for ContentDocumentSource {
+    fn default() -> Self {
+        Self {
+            _type: DocumentSourceType::Content,
+            content: Content::default(),
+        }
+    }
+}
+
+impl_display_for_serialize!(ContentDocumentSource);
+
+/// The source type of the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DocumentSourceType {
+    /// base64
+    #[default]
+    Base64,
+    /// text
+    Text,
+    /// url
+    Url,
+    /// content
+    Content,
+}
+
+impl Display for DocumentSourceType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | DocumentSourceType::Base64 => {
+                write!(f, "base64")
+            },
+            | DocumentSourceType::Text => {
+                write!(f, "text")
+            },
+            | DocumentSourceType::Url => {
+                write!(f, "url")
+            },
+            | DocumentSourceType::Content => {
+                write!(f, "content")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    DocumentSourceType,
+    Base64 => "base64",
+    Text => "text",
+    Url => "url",
+    Content => "content"
+);
+
+/// The media type of the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DocumentMediaType {
+    /// application/pdf
+    #[default]
+    Pdf,
+    /// text/plain
+    PlainText,
+}
+
+impl Display for DocumentMediaType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | DocumentMediaType::Pdf => {
+                write!(f, "application/pdf")
+            },
+            | DocumentMediaType::PlainText => {
+                write!(f, "text/plain")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    DocumentMediaType,
+    Pdf => "application/pdf",
+    PlainText => "text/plain"
+);
+
+impl DocumentMediaType {
+    /// Creates the media type from the extension of the path.
+    pub fn from_path(
+        path: &Path
+    ) -> Result<Self, DocumentMediaTypeParseError> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+        {
+            | Some("pdf") => Ok(Self::Pdf),
+            | Some("txt") => Ok(Self::PlainText),
+            | Some(extension) => Err(
+                DocumentMediaTypeParseError::NotSupported(
+                    extension.to_string(),
+                ),
+            ),
+            | None => Err(DocumentMediaTypeParseError::NotFound),
+        }
+    }
+}
+
+/// The tool use content block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ToolUseContentBlock {
+    /// The content type. It is always `tool_use`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The tool use.
+    #[serde(flatten)]
+    pub tool_use: ToolUse,
+}
+
+impl Default for ToolUseContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::ToolUse,
+            tool_use: ToolUse::default(),
+        }
+    }
+}
+
+impl_display_for_serialize!(ToolUseContentBlock);
+
+impl From<ToolUse> for ToolUseContentBlock {
+    fn from(tool_use: ToolUse) -> Self {
+        Self::new(tool_use)
+    }
+}
+
+impl ToolUseContentBlock {
+    /// Creates a new tool use content block.
+    pub fn new(tool_use: ToolUse) -> Self {
+        Self {
+            _type: ContentType::ToolUse,
+            tool_use,
+        }
+    }
+}
+
+/// The tool result content block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ToolResultContentBlock {
+    /// The content type. It is always `tool_result`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The tool result.
+    #[serde(flatten)]
+    pub tool_result: ToolResult,
+    /// The cache control of the block.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for ToolResultContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::ToolResult,
+            tool_result: ToolResult::default(),
+            cache_control: None,
+        }
+    }
+}
+
+impl_display_for_serialize!(ToolResultContentBlock);
+
+impl From<ToolResult> for ToolResultContentBlock {
+    fn from(tool_result: ToolResult) -> Self {
+        Self::new(tool_result)
+    }
+}
+
+impl ToolResultContentBlock {
+    /// Creates a new tool result content block.
+    pub fn new(tool_result: ToolResult) -> Self {
+        Self {
             _type: ContentType::ToolResult,
             tool_result,
+            cache_control: None,
+        }
+    }
+
+    /// Sets the cache control of the block.
+    pub fn with_cache_control(
+        mut self,
+        cache_control: CacheControl,
+    ) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+}
+
+/// The thinking content block.
+///
+/// Pass the block back unmodified with the signature in the multi-turn conversation.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ThinkingContentBlock {
+    /// The content type. It is always `thinking`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The thinking content.
+    pub thinking: String,
+    /// The signature to verify that the thinking was generated by the model.
+    pub signature: String,
+}
+
+impl Default for ThinkingContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::Thinking,
+            thinking: String::new(),
+            signature: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(ThinkingContentBlock);
+
+impl ThinkingContentBlock {
+    /// Creates a new thinking content block.
+    pub fn new<S, T>(
+        thinking: S,
+        signature: T,
+    ) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            _type: ContentType::Thinking,
+            thinking: thinking.into(),
+            signature: signature.into(),
+        }
+    }
+}
+
+/// The redacted thinking content block that is encrypted for safety reasons.
+///
+/// Pass the block back unmodified in the multi-turn conversation.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RedactedThinkingContentBlock {
+    /// The content type. It is always `redacted_thinking`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The encrypted thinking data.
+    pub data: String,
+}
+
+impl Default for RedactedThinkingContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::RedactedThinking,
+            data: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(RedactedThinkingContentBlock);
+
+impl RedactedThinkingContentBlock {
+    /// Creates a new redacted thinking content block.
+    pub fn new<S>(data: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            _type: ContentType::RedactedThinking,
+            data: data.into(),
+        }
+    }
+}
+
+/// The server tool use content block, e.g. a web search performed by the server.
+///
+/// The server executes the tool and returns the result in the same response, so the client does not need to call it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ServerToolUseContentBlock {
+    /// The content type. It is always `server_tool_use`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The server tool use.
+    #[serde(flatten)]
+    pub tool_use: ToolUse,
+}
+
+impl Default for ServerToolUseContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::ServerToolUse,
+            tool_use: ToolUse::default(),
+        }
+    }
+}
+
+impl_display_for_serialize!(ServerToolUseContentBlock);
+
+impl ServerToolUseContentBlock {
+    /// Creates a new server tool use content block.
+    pub fn new(tool_use: ToolUse) -> Self {
+        Self {
+            _type: ContentType::ServerToolUse,
+            tool_use,
+        }
+    }
+}
+
+/// The web search tool result content block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WebSearchToolResultContentBlock {
+    /// The content type. It is always `web_search_tool_result`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The ID of the server tool use this is a result for.
+    pub tool_use_id: String,
+    /// The search results or an error.
+    pub content: WebSearchToolResultContent,
+}
+
+impl Default for WebSearchToolResultContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::WebSearchToolResult,
+            tool_use_id: String::new(),
+            content: WebSearchToolResultContent::default(),
+        }
+    }
+}
+
+impl_display_for_serialize!(WebSearchToolResultContentBlock);
+
+impl WebSearchToolResultContentBlock {
+    /// Creates a new web search tool result content block.
+    pub fn new<S>(
+        tool_use_id: S,
+        content: WebSearchToolResultContent,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            _type: ContentType::WebSearchToolResult,
+            tool_use_id: tool_use_id.into(),
+            content,
+        }
+    }
+}
+
+/// The code execution tool result content block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CodeExecutionToolResultContentBlock {
+    /// The content type. It is always `code_execution_tool_result`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The ID of the server tool use this is a result for.
+    pub tool_use_id: String,
+    /// The execution result or an error.
+    pub content: CodeExecutionToolResultContent,
+}
+
+impl Default for CodeExecutionToolResultContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::CodeExecutionToolResult,
+            tool_use_id: String::new(),
+            content: CodeExecutionToolResultContent::default(),
+        }
+    }
+}
+
+impl_display_for_serialize!(CodeExecutionToolResultContentBlock);
+
+impl CodeExecutionToolResultContentBlock {
+    /// Creates a new code execution tool result content block.
+    pub fn new<S>(
+        tool_use_id: S,
+        content: CodeExecutionToolResultContent,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            _type: ContentType::CodeExecutionToolResult,
+            tool_use_id: tool_use_id.into(),
+            content,
         }
     }
 }
@@ -625,6 +1787,8 @@ impl ToolResultContentBlock {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::messages::{CharLocationCitation, PageLocationCitation};
+    use std::path::PathBuf;
 
     #[test]
     fn from_str() {
@@ -646,6 +1810,10 @@ mod tests {
     fn display_content_type() {
         assert_eq!(ContentType::Text.to_string(), "text");
         assert_eq!(ContentType::Image.to_string(), "image");
+        assert_eq!(
+            ContentType::Document.to_string(),
+            "document"
+        );
         assert_eq!(
             ContentType::TextDelta.to_string(),
             "text_delta"
@@ -670,6 +1838,10 @@ mod tests {
             serde_json::to_string(&ContentType::Image).unwrap(),
             "\"image\""
         );
+        assert_eq!(
+            serde_json::to_string(&ContentType::Document).unwrap(),
+            "\"document\""
+        );
         assert_eq!(
             serde_json::to_string(&ContentType::TextDelta).unwrap(),
             "\"text_delta\""
@@ -694,6 +1866,10 @@ mod tests {
             serde_json::from_str::<ContentType>("\"image\"").unwrap(),
             ContentType::Image
         );
+        assert_eq!(
+            serde_json::from_str::<ContentType>("\"document\"").unwrap(),
+            ContentType::Document
+        );
         assert_eq!(
             serde_json::from_str::<ContentType>("\"text_delta\"").unwrap(),
             ContentType::TextDelta
@@ -722,6 +1898,7 @@ mod tests {
             ImageSourceType::Base64.to_string(),
             "base64"
         );
+        assert_eq!(ImageSourceType::Url.to_string(), "url");
     }
 
     #[test]
@@ -852,63 +2029,186 @@ mod tests {
             ImageContentSource::base64(ImageMediaType::Jpeg, "data");
         assert_eq!(
             image_content_source,
-            ImageContentSource {
+            ImageContentSource::Base64(Base64ImageSource {
                 _type: ImageSourceType::Base64,
                 media_type: ImageMediaType::Jpeg,
                 data: "data".to_string(),
-            }
+            })
+        );
+
+        let image_content_source =
+            ImageContentSource::url("https://example.com/image.png");
+        assert_eq!(
+            image_content_source,
+            ImageContentSource::Url(UrlImageSource {
+                _type: ImageSourceType::Url,
+                url: "https://example.com/image.png".to_string(),
+            })
         );
     }
 
     #[test]
     fn default_image_content_source() {
         assert_eq!(
-            ImageContentSource::default(),
-            ImageContentSource {
-                _type: ImageSourceType::Base64,
-                media_type: ImageMediaType::Jpeg,
-                data: String::new(),
-            }
+            ImageContentSource::default(),
+            ImageContentSource::Base64(Base64ImageSource {
+                _type: ImageSourceType::Base64,
+                media_type: ImageMediaType::Jpeg,
+                data: String::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn display_image_content_source() {
+        let image_content_source =
+            ImageContentSource::Base64(Base64ImageSource {
+                _type: ImageSourceType::Base64,
+                media_type: ImageMediaType::Jpeg,
+                data: "data".to_string(),
+            });
+        assert_eq!(
+            image_content_source.to_string(),
+            "{\n  \"type\": \"base64\",\n  \"media_type\": \"image/jpeg\",\n  \"data\": \"data\"\n}"
+        );
+    }
+
+    #[test]
+    fn serialize_image_content_source() {
+        let image_content_source =
+            ImageContentSource::Base64(Base64ImageSource {
+                _type: ImageSourceType::Base64,
+                media_type: ImageMediaType::Jpeg,
+                data: "data".to_string(),
+            });
+        assert_eq!(
+            serde_json::to_string(&image_content_source).unwrap(),
+            "{\"type\":\"base64\",\"media_type\":\"image/jpeg\",\"data\":\"data\"}"
+        );
+    }
+
+    #[test]
+    fn deserialize_image_content_source() {
+        let image_content_source =
+            ImageContentSource::Base64(Base64ImageSource {
+                _type: ImageSourceType::Base64,
+                media_type: ImageMediaType::Jpeg,
+                data: "data".to_string(),
+            });
+        assert_eq!(
+            serde_json::from_str::<ImageContentSource>("{\"type\":\"base64\",\"media_type\":\"image/jpeg\",\"data\":\"data\"}").unwrap(),
+            image_content_source
+        );
+    }
+
+    #[test]
+    fn serialize_url_image_content_source() {
+        assert_eq!(
+            serde_json::to_string(&ImageContentSource::url(
+                "https://example.com/image.png"
+            ))
+            .unwrap(),
+            "{\"type\":\"url\",\"url\":\"https://example.com/image.png\"}"
+        );
+        assert_eq!(
+            serde_json::from_str::<ImageContentSource>(
+                "{\"type\":\"url\",\"url\":\"https://example.com/image.png\"}"
+            )
+            .unwrap(),
+            ImageContentSource::url("https://example.com/image.png")
+        );
+    }
+
+    #[test]
+    fn from_magic_bytes_image_media_type() {
+        assert_eq!(
+            ImageMediaType::from_magic_bytes(&[0xFF, 0xD8, 0xFF, 0xE0])
+                .unwrap(),
+            ImageMediaType::Jpeg
+        );
+        assert_eq!(
+            ImageMediaType::from_magic_bytes(&[
+                0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A
+            ])
+            .unwrap(),
+            ImageMediaType::Png
+        );
+        assert_eq!(
+            ImageMediaType::from_magic_bytes(b"GIF89a").unwrap(),
+            ImageMediaType::Gif
+        );
+        assert_eq!(
+            ImageMediaType::from_magic_bytes(b"RIFF\x00\x00\x00\x00WEBPVP8 ")
+                .unwrap(),
+            ImageMediaType::Webp
+        );
+        assert_eq!(
+            ImageMediaType::from_magic_bytes(b"BM"),
+            Err(ImageMediaTypeParseError::UnknownSignature)
+        );
+        assert_eq!(
+            ImageMediaType::from_magic_bytes(&[]),
+            Err(ImageMediaTypeParseError::UnknownSignature)
         );
     }
 
     #[test]
-    fn display_image_content_source() {
-        let image_content_source = ImageContentSource {
-            _type: ImageSourceType::Base64,
-            media_type: ImageMediaType::Jpeg,
-            data: "data".to_string(),
-        };
+    fn from_bytes_image_content_source() {
         assert_eq!(
-            image_content_source.to_string(),
-            "{\n  \"type\": \"base64\",\n  \"media_type\": \"image/jpeg\",\n  \"data\": \"data\"\n}"
+            ImageContentSource::from_bytes(b"GIF89a").unwrap(),
+            ImageContentSource::base64(ImageMediaType::Gif, "R0lGODlh")
         );
+
+        assert!(matches!(
+            ImageContentSource::from_bytes(b"not an image"),
+            Err(ImageLoadError::MediaTypeParseFailed(
+                ImageMediaTypeParseError::UnknownSignature
+            ))
+        ));
+
+        let mut too_large = vec![0xFF, 0xD8, 0xFF];
+        too_large.resize(ImageContentSource::MAX_BASE64_SIZE, 0);
+        assert!(matches!(
+            ImageContentSource::from_bytes(&too_large),
+            Err(ImageLoadError::TooLarge {
+                max: ImageContentSource::MAX_BASE64_SIZE,
+                ..
+            })
+        ));
     }
 
     #[test]
-    fn serialize_image_content_source() {
-        let image_content_source = ImageContentSource {
-            _type: ImageSourceType::Base64,
-            media_type: ImageMediaType::Jpeg,
-            data: "data".to_string(),
-        };
+    fn from_path_image_content_source() {
+        // The signature wins over the extension.
+        let path = std::env::temp_dir().join(format!(
+            "clust_image_{}.png",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"GIF89a").unwrap();
+        let source = ImageContentSource::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
         assert_eq!(
-            serde_json::to_string(&image_content_source).unwrap(),
-            "{\"type\":\"base64\",\"media_type\":\"image/jpeg\",\"data\":\"data\"}"
+            source.unwrap(),
+            ImageContentSource::base64(ImageMediaType::Gif, "R0lGODlh")
         );
-    }
 
-    #[test]
-    fn deserialize_image_content_source() {
-        let image_content_source = ImageContentSource {
-            _type: ImageSourceType::Base64,
-            media_type: ImageMediaType::Jpeg,
-            data: "data".to_string(),
-        };
+        // Falls back to the extension for an unknown signature.
+        let path = std::env::temp_dir().join(format!(
+            "clust_image_{}.webp",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"GIF").unwrap();
+        let source = ImageContentSource::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
         assert_eq!(
-            serde_json::from_str::<ImageContentSource>("{\"type\":\"base64\",\"media_type\":\"image/jpeg\",\"data\":\"data\"}").unwrap(),
-            image_content_source
+            source.unwrap(),
+            ImageContentSource::base64(ImageMediaType::Webp, "R0lG")
         );
+
+        assert!(matches!(
+            ImageContentSource::from_path(&PathBuf::from("not_found.png")),
+            Err(ImageLoadError::ReadFileFailed(_))
+        ));
     }
 
     #[test]
@@ -919,6 +2219,8 @@ mod tests {
             TextContentBlock {
                 _type: ContentType::Text,
                 text: "text".to_string(),
+                citations: None,
+                cache_control: None,
             }
         );
     }
@@ -930,6 +2232,8 @@ mod tests {
             TextContentBlock {
                 _type: ContentType::Text,
                 text: String::new(),
+                citations: None,
+                cache_control: None,
             }
         );
     }
@@ -952,6 +2256,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn text_content_block_with_cache_control() {
+        let text_content_block = TextContentBlock::new("text")
+            .with_cache_control(CacheControl::ephemeral());
+        assert_eq!(
+            text_content_block.cache_control,
+            Some(CacheControl::ephemeral())
+        );
+        assert_eq!(
+            serde_json::to_string(&text_content_block).unwrap(),
+            "{\"type\":\"text\",\"text\":\"text\",\"cache_control\":{\"type\":\"ephemeral\"}}"
+        );
+        assert_eq!(
+            serde_json::from_str::<TextContentBlock>(
+                "{\"type\":\"text\",\"text\":\"text\",\"cache_control\":{\"type\":\"ephemeral\"}}"
+            )
+            .unwrap(),
+            text_content_block
+        );
+    }
+
     #[test]
     fn deserialize_text_content_block() {
         let text_content_block = TextContentBlock::new("text".to_string());
@@ -973,6 +2298,7 @@ mod tests {
             ImageContentBlock {
                 _type: ContentType::Image,
                 source: ImageContentSource::default(),
+                cache_control: None,
             }
         );
     }
@@ -984,6 +2310,7 @@ mod tests {
             ImageContentBlock {
                 _type: ContentType::Image,
                 source: ImageContentSource::default(),
+                cache_control: None,
             }
         );
     }
@@ -1097,6 +2424,7 @@ mod tests {
                     "tool_use_id",
                     Some("content"),
                 ),
+                cache_control: None,
             }
         );
 
@@ -1108,6 +2436,7 @@ mod tests {
             ToolResultContentBlock {
                 _type: ContentType::ToolResult,
                 tool_result: ToolResult::error("tool_use_id", Some("content"),),
+                cache_control: None,
             }
         );
     }
@@ -1119,6 +2448,7 @@ mod tests {
             ToolResultContentBlock {
                 _type: ContentType::ToolResult,
                 tool_result: ToolResult::default(),
+                cache_control: None,
             }
         );
     }
@@ -1128,19 +2458,21 @@ mod tests {
         let tool_result_content_block = ToolResultContentBlock {
             _type: ContentType::ToolResult,
             tool_result: ToolResult::success("tool_use_id", Some("content")),
+            cache_control: None,
         };
         assert_eq!(
             tool_result_content_block.to_string(),
-            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": {\n    \"type\": \"text\",\n    \"text\": \"content\"\n  }\n}"
+            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": \"content\"\n}"
         );
 
         let tool_result_content_block = ToolResultContentBlock {
             _type: ContentType::ToolResult,
             tool_result: ToolResult::error("tool_use_id", Some("content")),
+            cache_control: None,
         };
         assert_eq!(
             tool_result_content_block.to_string(),
-            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": {\n    \"type\": \"text\",\n    \"text\": \"content\"\n  },\n  \"is_error\": true\n}"
+            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": \"content\",\n  \"is_error\": true\n}"
         );
     }
 
@@ -1149,19 +2481,35 @@ mod tests {
         let tool_result_content_block = ToolResultContentBlock {
             _type: ContentType::ToolResult,
             tool_result: ToolResult::success("tool_use_id", Some("content")),
+            cache_control: None,
         };
         assert_eq!(
             serde_json::to_string(&tool_result_content_block).unwrap(),
-            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":{\"type\":\"text\",\"text\":\"content\"}}"
+            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\"}"
         );
 
         let tool_result_content_block = ToolResultContentBlock {
             _type: ContentType::ToolResult,
             tool_result: ToolResult::error("tool_use_id", Some("content")),
+            cache_control: None,
         };
         assert_eq!(
             serde_json::to_string(&tool_result_content_block).unwrap(),
-            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":{\"type\":\"text\",\"text\":\"content\"},\"is_error\":true}"
+            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\",\"is_error\":true}"
+        );
+    }
+
+    #[test]
+    fn tool_result_content_block_with_cache_control() {
+        let tool_result_content_block = ToolResultContentBlock::new(
+            ToolResult::success("tool_use_id", Some("content")),
+        )
+        .with_cache_control(CacheControl::ephemeral_with_ttl(
+            crate::messages::CacheTtl::OneHour,
+        ));
+        assert_eq!(
+            serde_json::to_string(&tool_result_content_block).unwrap(),
+            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\",\"cache_control\":{\"type\":\"ephemeral\",\"ttl\":\"1h\"}}"
         );
     }
 
@@ -1170,18 +2518,20 @@ mod tests {
         let tool_result_content_block = ToolResultContentBlock {
             _type: ContentType::ToolResult,
             tool_result: ToolResult::success("tool_use_id", Some("content")),
+            cache_control: None,
         };
         assert_eq!(
-            serde_json::from_str::<ToolResultContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":{\"type\":\"text\",\"text\":\"content\"}}").unwrap(),
+            serde_json::from_str::<ToolResultContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\"}").unwrap(),
             tool_result_content_block
         );
 
         let tool_result_content_block = ToolResultContentBlock {
             _type: ContentType::ToolResult,
             tool_result: ToolResult::error("tool_use_id", Some("content")),
+            cache_control: None,
         };
         assert_eq!(
-            serde_json::from_str::<ToolResultContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":{\"type\":\"text\",\"text\":\"content\"},\"is_error\":true}").unwrap(),
+            serde_json::from_str::<ToolResultContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\",\"is_error\":true}").unwrap(),
             tool_result_content_block
         );
     }
@@ -1196,6 +2546,8 @@ mod tests {
             ContentBlock::Text(TextContentBlock {
                 _type: ContentType::Text,
                 text: "text".to_string(),
+                citations: None,
+                cache_control: None,
             })
         );
 
@@ -1207,6 +2559,7 @@ mod tests {
             ContentBlock::Image(ImageContentBlock {
                 _type: ContentType::Image,
                 source: ImageContentSource::default(),
+                cache_control: None,
             })
         );
 
@@ -1230,6 +2583,7 @@ mod tests {
             ContentBlock::ToolResult(ToolResultContentBlock {
                 _type: ContentType::ToolResult,
                 tool_result: ToolResult::error("tool_use_id", Some("content")),
+                cache_control: None,
             })
         );
     }
@@ -1274,7 +2628,7 @@ mod tests {
             ));
         assert_eq!(
             content_block.to_string(),
-            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": {\n    \"type\": \"text\",\n    \"text\": \"content\"\n  }\n}"
+            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": \"content\"\n}"
         );
 
         let content_block =
@@ -1283,7 +2637,7 @@ mod tests {
             ));
         assert_eq!(
             content_block.to_string(),
-            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": {\n    \"type\": \"text\",\n    \"text\": \"content\"\n  },\n  \"is_error\": true\n}"
+            "{\n  \"type\": \"tool_result\",\n  \"tool_use_id\": \"tool_use_id\",\n  \"content\": \"content\",\n  \"is_error\": true\n}"
         );
     }
 
@@ -1319,7 +2673,7 @@ mod tests {
             ));
         assert_eq!(
             serde_json::to_string(&content_block).unwrap(),
-            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":{\"type\":\"text\",\"text\":\"content\"}}"
+            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\"}"
         );
 
         let content_block =
@@ -1328,7 +2682,7 @@ mod tests {
             ));
         assert_eq!(
             serde_json::to_string(&content_block).unwrap(),
-            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":{\"type\":\"text\",\"text\":\"content\"},\"is_error\":true}"
+            "{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\",\"is_error\":true}"
         );
     }
 
@@ -1366,7 +2720,7 @@ mod tests {
                 ToolResult::success("tool_use_id", Some("content")),
             ));
         assert_eq!(
-            serde_json::from_str::<ContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":{\"type\":\"text\",\"text\":\"content\"}}").unwrap(),
+            serde_json::from_str::<ContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\"}").unwrap(),
             content_block
         );
 
@@ -1375,7 +2729,7 @@ mod tests {
                 ToolResult::error("tool_use_id", Some("content")),
             ));
         assert_eq!(
-            serde_json::from_str::<ContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\": {\"type\":\"text\",\"text\":\"content\"},\"is_error\":true}").unwrap(),
+            serde_json::from_str::<ContentBlock>("{\"type\":\"tool_result\",\"tool_use_id\":\"tool_use_id\",\"content\":\"content\",\"is_error\":true}").unwrap(),
             content_block
         );
     }
@@ -1536,135 +2890,379 @@ mod tests {
     #[test]
     fn from_content() {
         assert_eq!(
-            Content::from("text"),
-            Content::SingleText("text".to_string())
+            Content::from("text"),
+            Content::SingleText("text".to_string())
+        );
+
+        let content: Content = "text".into();
+        assert_eq!(
+            content,
+            Content::SingleText("text".to_string())
+        );
+
+        assert_eq!(
+            Content::from(vec![ContentBlock::from(
+                "text"
+            )]),
+            Content::MultipleBlocks(vec![ContentBlock::Text(
+                TextContentBlock::new("text")
+            )])
+        );
+
+        let content: Content = vec!["text".into()].into();
+        assert_eq!(
+            content,
+            Content::MultipleBlocks(vec![ContentBlock::Text(
+                TextContentBlock::new("text")
+            )])
+        );
+
+        assert_eq!(
+            Content::from(ImageContentSource::default()),
+            Content::MultipleBlocks(vec![ContentBlock::Image(
+                ImageContentBlock::new(ImageContentSource::default())
+            )])
+        );
+
+        let content: Content = ImageContentSource::default().into();
+        assert_eq!(
+            content,
+            Content::MultipleBlocks(vec![ContentBlock::Image(
+                ImageContentBlock::new(ImageContentSource::default())
+            )])
+        );
+    }
+
+    #[test]
+    fn flatten_into_text() {
+        assert_eq!(
+            Content::from("text")
+                .flatten_into_text()
+                .unwrap(),
+            "text"
+        );
+
+        assert_eq!(
+            Content::from(vec![
+                ContentBlock::from("text"),
+                ContentBlock::from(ImageContentSource::default()),
+            ])
+            .flatten_into_text()
+            .unwrap(),
+            "text"
+        );
+
+        assert_eq!(
+            Content::from(vec![
+                ContentBlock::from("text"),
+                ContentBlock::from("second"),
+            ])
+            .flatten_into_text()
+            .unwrap(),
+            "text"
+        );
+
+        assert!(Content::from(vec![])
+            .flatten_into_text()
+            .is_err());
+
+        assert!(
+            Content::from(ImageContentSource::default())
+                .flatten_into_text()
+                .is_err()
+        );
+
+        assert!(Content::from(vec![
+            ContentBlock::from(ImageContentSource::default()),
+            ContentBlock::from("text"),
+        ])
+        .flatten_into_text()
+        .is_err());
+    }
+
+    #[test]
+    fn flatten_into_image_source() {
+        assert!(Content::from("text")
+            .flatten_into_image_source()
+            .is_err());
+
+        assert!(Content::from(vec![
+            ContentBlock::from("text"),
+            ContentBlock::from(ImageContentSource::default()),
+        ])
+        .flatten_into_image_source()
+        .is_err());
+
+        assert!(Content::from(vec![
+            ContentBlock::from("text"),
+            ContentBlock::from("second"),
+        ])
+        .flatten_into_image_source()
+        .is_err());
+
+        assert!(Content::from(vec![])
+            .flatten_into_image_source()
+            .is_err());
+
+        assert_eq!(
+            *Content::from(ImageContentSource::default())
+                .flatten_into_image_source()
+                .unwrap(),
+            ImageContentSource::default()
+        );
+
+        assert_eq!(
+            *Content::from(vec![
+                ContentBlock::from(ImageContentSource::default()),
+                ContentBlock::from("text"),
+            ])
+            .flatten_into_image_source()
+            .unwrap(),
+            ImageContentSource::default()
+        );
+    }
+
+    #[test]
+    fn display_document_media_type() {
+        assert_eq!(
+            DocumentMediaType::Pdf.to_string(),
+            "application/pdf"
+        );
+        assert_eq!(
+            DocumentMediaType::PlainText.to_string(),
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn from_path_document_media_type() {
+        let path = PathBuf::from("document.pdf");
+        assert_eq!(
+            DocumentMediaType::from_path(&path).unwrap(),
+            DocumentMediaType::Pdf
+        );
+
+        let path = PathBuf::from("document.txt");
+        assert_eq!(
+            DocumentMediaType::from_path(&path).unwrap(),
+            DocumentMediaType::PlainText
+        );
+
+        let path = PathBuf::from("document.docx");
+        assert_eq!(
+            DocumentMediaType::from_path(&path),
+            Err(DocumentMediaTypeParseError::NotSupported(
+                "docx".to_string()
+            ))
+        );
+
+        let path = PathBuf::from("document");
+        assert_eq!(
+            DocumentMediaType::from_path(&path),
+            Err(DocumentMediaTypeParseError::NotFound)
+        );
+    }
+
+    #[test]
+    fn from_bytes_document_content_source() {
+        assert_eq!(
+            DocumentContentSource::from_bytes(
+                DocumentMediaType::Pdf,
+                b"%PDF-1.4"
+            )
+            .unwrap(),
+            DocumentContentSource::base64_pdf("JVBERi0xLjQ=")
+        );
+
+        assert_eq!(
+            DocumentContentSource::from_bytes(
+                DocumentMediaType::PlainText,
+                "text".as_bytes()
+            )
+            .unwrap(),
+            DocumentContentSource::plain_text("text")
+        );
+
+        assert!(DocumentContentSource::from_bytes(
+            DocumentMediaType::PlainText,
+            &[0xff, 0xfe]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn from_path_document_content_source() {
+        let path = std::env::temp_dir().join(format!(
+            "clust_document_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "text").unwrap();
+
+        let source = DocumentContentSource::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            source.unwrap(),
+            DocumentContentSource::plain_text("text")
+        );
+
+        assert!(matches!(
+            DocumentContentSource::from_path(&PathBuf::from("document.docx")),
+            Err(DocumentLoadError::MediaTypeParseFailed(_))
+        ));
+
+        assert!(matches!(
+            DocumentContentSource::from_path(&PathBuf::from(
+                "not_found_document.pdf"
+            )),
+            Err(DocumentLoadError::ReadFileFailed(_))
+        ));
+    }
+
+    #[test]
+    fn serialize_document_content_source() {
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::base64_pdf("data"))
+                .unwrap(),
+            "{\"type\":\"base64\",\"media_type\":\"application/pdf\",\"data\":\"data\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::plain_text("text"))
+                .unwrap(),
+            "{\"type\":\"text\",\"media_type\":\"text/plain\",\"data\":\"text\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::url(
+                "https://example.com/document.pdf"
+            ))
+            .unwrap(),
+            "{\"type\":\"url\",\"url\":\"https://example.com/document.pdf\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::content(vec![
+                ContentBlock::from("first"),
+                ContentBlock::from("second"),
+            ]))
+            .unwrap(),
+            "{\"type\":\"content\",\"content\":[{\"type\":\"text\",\"text\":\"first\"},{\"type\":\"text\",\"text\":\"second\"}]}"
+        );
+    }
+
+    #[test]
+    fn deserialize_document_content_source() {
+        assert_eq!(
+            serde_json::from_str::<DocumentContentSource>("{\"type\":\"base64\",\"media_type\":\"application/pdf\",\"data\":\"data\"}").unwrap(),
+            DocumentContentSource::base64_pdf("data")
+        );
+        assert_eq!(
+            serde_json::from_str::<DocumentContentSource>("{\"type\":\"text\",\"media_type\":\"text/plain\",\"data\":\"text\"}").unwrap(),
+            DocumentContentSource::plain_text("text")
         );
-
-        let content: Content = "text".into();
         assert_eq!(
-            content,
-            Content::SingleText("text".to_string())
+            serde_json::from_str::<DocumentContentSource>(
+                "{\"type\":\"url\",\"url\":\"https://example.com/document.pdf\"}"
+            )
+            .unwrap(),
+            DocumentContentSource::url("https://example.com/document.pdf")
         );
-
         assert_eq!(
-            Content::from(vec![ContentBlock::from(
-                "text"
-            )]),
-            Content::MultipleBlocks(vec![ContentBlock::Text(
-                TextContentBlock::new("text")
-            )])
+            serde_json::from_str::<DocumentContentSource>(
+                "{\"type\":\"content\",\"content\":\"text\"}"
+            )
+            .unwrap(),
+            DocumentContentSource::content("text")
         );
+    }
 
-        let content: Content = vec!["text".into()].into();
-        assert_eq!(
-            content,
-            Content::MultipleBlocks(vec![ContentBlock::Text(
-                TextContentBlock::new("text")
-            )])
+    #[test]
+    fn new_document_content_block() {
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::default());
+        assert_eq!(
+            document_content_block,
+            DocumentContentBlock {
+                _type: ContentType::Document,
+                source: DocumentContentSource::Base64(
+                    Base64DocumentSource::default()
+                ),
+                title: None,
+                context: None,
+                citations: None,
+                cache_control: None,
+            }
         );
 
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::default())
+                .with_title("title")
+                .with_context("context");
         assert_eq!(
-            Content::from(ImageContentSource::default()),
-            Content::MultipleBlocks(vec![ContentBlock::Image(
-                ImageContentBlock::new(ImageContentSource::default())
-            )])
+            document_content_block.title,
+            Some("title".to_string())
         );
-
-        let content: Content = ImageContentSource::default().into();
         assert_eq!(
-            content,
-            Content::MultipleBlocks(vec![ContentBlock::Image(
-                ImageContentBlock::new(ImageContentSource::default())
-            )])
+            document_content_block.context,
+            Some("context".to_string())
         );
     }
 
     #[test]
-    fn flatten_into_text() {
+    fn serialize_document_content_block() {
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::plain_text(
+                "text",
+            ));
         assert_eq!(
-            Content::from("text")
-                .flatten_into_text()
-                .unwrap(),
-            "text"
+            serde_json::to_string(&document_content_block).unwrap(),
+            "{\"type\":\"document\",\"source\":{\"type\":\"text\",\"media_type\":\"text/plain\",\"data\":\"text\"}}"
         );
 
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::plain_text(
+                "text",
+            ))
+            .with_title("title")
+            .with_context("context");
         assert_eq!(
-            Content::from(vec![
-                ContentBlock::from("text"),
-                ContentBlock::from(ImageContentSource::default()),
-            ])
-            .flatten_into_text()
-            .unwrap(),
-            "text"
+            serde_json::to_string(&document_content_block).unwrap(),
+            "{\"type\":\"document\",\"source\":{\"type\":\"text\",\"media_type\":\"text/plain\",\"data\":\"text\"},\"title\":\"title\",\"context\":\"context\"}"
         );
+    }
 
+    #[test]
+    fn deserialize_document_content_block() {
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::base64_pdf(
+                "data",
+            ))
+            .with_title("title");
         assert_eq!(
-            Content::from(vec![
-                ContentBlock::from("text"),
-                ContentBlock::from("second"),
-            ])
-            .flatten_into_text()
-            .unwrap(),
-            "text"
-        );
-
-        assert!(Content::from(vec![])
-            .flatten_into_text()
-            .is_err());
-
-        assert!(
-            Content::from(ImageContentSource::default())
-                .flatten_into_text()
-                .is_err()
+            serde_json::from_str::<ContentBlock>("{\"type\":\"document\",\"source\":{\"type\":\"base64\",\"media_type\":\"application/pdf\",\"data\":\"data\"},\"title\":\"title\"}").unwrap(),
+            ContentBlock::Document(document_content_block)
         );
-
-        assert!(Content::from(vec![
-            ContentBlock::from(ImageContentSource::default()),
-            ContentBlock::from("text"),
-        ])
-        .flatten_into_text()
-        .is_err());
     }
 
     #[test]
-    fn flatten_into_image_source() {
+    fn flatten_into_document_source() {
         assert!(Content::from("text")
-            .flatten_into_image_source()
+            .flatten_into_document_source()
             .is_err());
 
-        assert!(Content::from(vec![
-            ContentBlock::from("text"),
-            ContentBlock::from(ImageContentSource::default()),
-        ])
-        .flatten_into_image_source()
-        .is_err());
+        assert!(Content::from(vec![])
+            .flatten_into_document_source()
+            .is_err());
 
         assert!(Content::from(vec![
             ContentBlock::from("text"),
-            ContentBlock::from("second"),
+            ContentBlock::from(DocumentContentSource::default()),
         ])
-        .flatten_into_image_source()
+        .flatten_into_document_source()
         .is_err());
 
-        assert!(Content::from(vec![])
-            .flatten_into_image_source()
-            .is_err());
-
         assert_eq!(
-            *Content::from(ImageContentSource::default())
-                .flatten_into_image_source()
+            *Content::from(DocumentContentSource::plain_text("text"))
+                .flatten_into_document_source()
                 .unwrap(),
-            ImageContentSource::default()
-        );
-
-        assert_eq!(
-            *Content::from(vec![
-                ContentBlock::from(ImageContentSource::default()),
-                ContentBlock::from("text"),
-            ])
-            .flatten_into_image_source()
-            .unwrap(),
-            ImageContentSource::default()
+            DocumentContentSource::plain_text("text")
         );
     }
 
@@ -1760,4 +3358,298 @@ mod tests {
             ToolResult::success("tool_use_id", Some("content"))
         );
     }
+
+    #[test]
+    fn deserialize_text_content_block_with_citations() {
+        let text_content_block = TextContentBlock {
+            citations: Some(vec![Citation::PageLocation(
+                PageLocationCitation {
+                    cited_text: "cited".to_string(),
+                    start_page_number: 1,
+                    end_page_number: 2,
+                    ..Default::default()
+                },
+            )]),
+            ..TextContentBlock::new("text")
+        };
+        assert_eq!(
+            serde_json::from_str::<TextContentBlock>(
+                "{\"type\":\"text\",\"text\":\"text\",\"citations\":[{\"type\":\"page_location\",\"cited_text\":\"cited\",\"document_index\":0,\"document_title\":null,\"start_page_number\":1,\"end_page_number\":2}]}"
+            )
+            .unwrap(),
+            text_content_block
+        );
+    }
+
+    #[test]
+    fn document_content_block_with_citations() {
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::plain_text("text"))
+                .with_citations();
+        assert_eq!(
+            serde_json::to_string(&document_content_block).unwrap(),
+            "{\"type\":\"document\",\"source\":{\"type\":\"text\",\"media_type\":\"text/plain\",\"data\":\"text\"},\"citations\":{\"enabled\":true}}"
+        );
+    }
+
+    #[test]
+    fn render_with_footnotes() {
+        let citation = Citation::CharLocation(CharLocationCitation {
+            cited_text: "The grass is green. ".to_string(),
+            document_title: Some("Example".to_string()),
+            end_char_index: 20,
+            ..Default::default()
+        });
+        let content = Content::MultipleBlocks(vec![
+            TextContentBlock::new("According to the document, ").into(),
+            TextContentBlock {
+                citations: Some(vec![citation.clone()]),
+                ..TextContentBlock::new("the grass is green")
+            }
+            .into(),
+            TextContentBlock::new(" and ").into(),
+            TextContentBlock {
+                citations: Some(vec![citation]),
+                ..TextContentBlock::new("it is grass")
+            }
+            .into(),
+            TextContentBlock::new(".").into(),
+        ]);
+
+        assert_eq!(
+            content.render_with_footnotes(),
+            "According to the document, the grass is green[1] and it is grass[1].\n\n[1] \"The grass is green.\" - Example (document 0, characters 0-20)"
+        );
+
+        assert_eq!(
+            Content::from("text").render_with_footnotes(),
+            "text"
+        );
+    }
+
+    #[test]
+    fn thinking_content_block_round_trip() {
+        let content_block = ContentBlock::from(ThinkingContentBlock::new(
+            "Let me think.",
+            "signature",
+        ));
+        let json = "{\"type\":\"thinking\",\"thinking\":\"Let me think.\",\"signature\":\"signature\"}";
+        assert_eq!(
+            serde_json::to_string(&content_block).unwrap(),
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<ContentBlock>(json).unwrap(),
+            content_block
+        );
+
+        let content_block = ContentBlock::from(
+            RedactedThinkingContentBlock::new("encrypted"),
+        );
+        let json = "{\"type\":\"redacted_thinking\",\"data\":\"encrypted\"}";
+        assert_eq!(
+            serde_json::to_string(&content_block).unwrap(),
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<ContentBlock>(json).unwrap(),
+            content_block
+        );
+    }
+
+    #[test]
+    fn server_tool_content_blocks_round_trip() {
+        let content_block =
+            ContentBlock::from(ServerToolUseContentBlock::new(ToolUse::new(
+                "srvtoolu_1",
+                "web_search",
+                serde_json::json!({"query": "weather"}),
+            )));
+        let json = "{\"type\":\"server_tool_use\",\"id\":\"srvtoolu_1\",\"name\":\"web_search\",\"input\":{\"query\":\"weather\"}}";
+        assert_eq!(
+            serde_json::to_string(&content_block).unwrap(),
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<ContentBlock>(json).unwrap(),
+            content_block
+        );
+
+        let content_block =
+            ContentBlock::from(WebSearchToolResultContentBlock::new(
+                "srvtoolu_1",
+                WebSearchToolResultContent::Results(vec![
+                    crate::messages::WebSearchResult {
+                        url: "https://example.com".to_string(),
+                        title: "Example".to_string(),
+                        encrypted_content: "abc".to_string(),
+                        ..Default::default()
+                    },
+                ]),
+            ));
+        let json = "{\"type\":\"web_search_tool_result\",\"tool_use_id\":\"srvtoolu_1\",\"content\":[{\"type\":\"web_search_result\",\"url\":\"https://example.com\",\"title\":\"Example\",\"encrypted_content\":\"abc\"}]}";
+        assert_eq!(
+            serde_json::to_string(&content_block).unwrap(),
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<ContentBlock>(json).unwrap(),
+            content_block
+        );
+
+        let content_block =
+            ContentBlock::from(CodeExecutionToolResultContentBlock::new(
+                "srvtoolu_2",
+                CodeExecutionToolResultContent::Result(
+                    crate::messages::CodeExecutionResult {
+                        stdout: "4\n".to_string(),
+                        ..Default::default()
+                    },
+                ),
+            ));
+        let json = "{\"type\":\"code_execution_tool_result\",\"tool_use_id\":\"srvtoolu_2\",\"content\":{\"type\":\"code_execution_result\",\"stdout\":\"4\\n\",\"stderr\":\"\",\"return_code\":0,\"content\":[]}}";
+        assert_eq!(
+            serde_json::to_string(&content_block).unwrap(),
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<ContentBlock>(json).unwrap(),
+            content_block
+        );
+    }
+
+    #[test]
+    fn unknown_content_block_round_trip() {
+        let json = "{\"data\":{\"key\":\"value\"},\"type\":\"new_block\"}";
+        let content_block =
+            serde_json::from_str::<ContentBlock>(json).unwrap();
+        assert_eq!(
+            content_block,
+            ContentBlock::Unknown(serde_json::json!({
+                "type": "new_block",
+                "data": {"key": "value"},
+            }))
+        );
+        assert_eq!(
+            serde_json::to_string(&content_block).unwrap(),
+            json
+        );
+
+        assert!(serde_json::from_str::<ContentBlock>("{\"text\":\"text\"}")
+            .is_err());
+    }
+
+    #[test]
+    fn query_content() {
+        let content = Content::MultipleBlocks(vec![
+            ContentBlock::from("Let me check "),
+            ContentBlock::from(ToolUse::new(
+                "id1",
+                "tool1",
+                serde_json::Value::Null,
+            )),
+            ContentBlock::from("the weather."),
+            ContentBlock::from(ToolUse::new(
+                "id2",
+                "tool2",
+                serde_json::Value::Null,
+            )),
+            ContentBlock::from(ImageContentSource::url(
+                "https://example.com/image.png",
+            )),
+        ]);
+
+        assert_eq!(
+            content
+                .texts()
+                .collect::<Vec<_>>(),
+            vec!["Let me check ", "the weather."]
+        );
+        assert_eq!(
+            content.concatenated_text(),
+            "Let me check the weather."
+        );
+        assert_eq!(
+            content
+                .tool_uses()
+                .map(|tool_use| tool_use.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["id1", "id2"]
+        );
+        assert_eq!(content.tool_results().count(), 0);
+        assert_eq!(content.images().count(), 1);
+
+        let content = Content::from("text");
+        assert_eq!(
+            content
+                .texts()
+                .collect::<Vec<_>>(),
+            vec!["text"]
+        );
+        assert_eq!(content.tool_uses().count(), 0);
+    }
+
+    #[test]
+    fn manipulate_content() {
+        let mut content = Content::from("text");
+        content.push(ToolResult::success("id", Some("result")));
+        content.extend(vec!["a", "b"]);
+        assert_eq!(
+            content,
+            Content::MultipleBlocks(vec![
+                ContentBlock::from("text"),
+                ContentBlock::from(ToolResult::success(
+                    "id",
+                    Some("result")
+                )),
+                ContentBlock::from("a"),
+                ContentBlock::from("b"),
+            ])
+        );
+
+        content.retain(|block| matches!(block, ContentBlock::Text(_)));
+        assert_eq!(content.concatenated_text(), "textab");
+
+        let content = content.map_blocks(|block| match block {
+            | ContentBlock::Text(text) => {
+                ContentBlock::from(text.text.to_uppercase())
+            },
+            | block => block,
+        });
+        assert_eq!(content.concatenated_text(), "TEXTAB");
+        assert_eq!(content.into_blocks().len(), 3);
+
+        let mut content = Content::from("");
+        assert!(content.is_empty());
+        assert!(content.blocks_mut().is_empty());
+    }
+
+    #[test]
+    fn normalize_content() {
+        assert_eq!(
+            Content::MultipleBlocks(vec![ContentBlock::from("text")])
+                .normalize(),
+            Content::from("text")
+        );
+        assert_eq!(
+            Content::MultipleBlocks(vec![]).normalize(),
+            Content::from("")
+        );
+        assert_eq!(
+            Content::from("text").normalize(),
+            Content::from("text")
+        );
+
+        let content = Content::MultipleBlocks(vec![ContentBlock::from(
+            TextContentBlock::new("text")
+                .with_cache_control(CacheControl::ephemeral()),
+        )]);
+        assert_eq!(content.clone().normalize(), content);
+
+        let content = Content::MultipleBlocks(vec![
+            ContentBlock::from("a"),
+            ContentBlock::from("b"),
+        ]);
+        assert_eq!(content.clone().normalize(), content);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{ApiError, ClientError};
+use crate::{ApiError, ClientError, ValidationError};
 use std::fmt::Display;
 
 /// The error type for the messages API.
@@ -13,6 +13,12 @@ pub enum MessagesError {
     /// Stream option mismatch.
     #[error("Stream option mismatch")]
     StreamOptionMismatch,
+    /// The tool choice specifies a tool that is not in the tools of the request.
+    #[error(transparent)]
+    InvalidToolChoice(ValidationError<String>),
+    /// The thinking budget is not less than the maximum number of tokens of the request.
+    #[error(transparent)]
+    InvalidThinking(ValidationError<u32>),
 }
 
 /// The error type for the streaming messages.
@@ -33,6 +39,24 @@ pub enum StreamError {
     /// Chunk data deserialization error.
     #[error(transparent)]
     ChunkDataDeserializationError(#[from] serde_json::Error),
+    /// The error event sent by the API server in the stream, e.g. `overloaded_error`.
+    #[error(transparent)]
+    Api(#[from] ApiError),
+    /// The chunks do not follow the sequence of a message stream, e.g. a delta of a block that has not started.
+    #[error("Invalid chunk sequence: {0}")]
+    InvalidChunkSequence(String),
+    /// No byte has arrived in the stream for the idle timeout.
+    #[error("The stream has been idle for {0:?}")]
+    IdleTimeout(std::time::Duration),
+    /// No event has arrived in the stream for the timeout to the first event.
+    #[error("No event has arrived in the stream for {0:?}")]
+    FirstEventTimeout(std::time::Duration),
+    /// Failed to write the recording of the stream.
+    #[error("Failed to write the recording of the stream: {0}")]
+    RecordingError(std::io::Error),
+    /// The recording of the stream to replay is invalid.
+    #[error("Invalid recording of the stream: {0}")]
+    InvalidRecording(String),
 }
 
 /// The error type for parsing message chunk type.
@@ -66,7 +90,7 @@ pub enum ContentFlatteningError {
     NotFoundTargetBlock,
 }
 
-/// The error type for parsing the image media type from an extension in a path.
+/// The error type for parsing the image media type from an extension in a path or the image data.
 #[derive(Debug, PartialEq, thiserror::Error)]
 pub enum ImageMediaTypeParseError {
     /// The extension is not supported
@@ -75,6 +99,57 @@ pub enum ImageMediaTypeParseError {
     /// Extension is not found
     #[error("Extension is not found")]
     NotFound,
+    /// The signature of the image data is unknown
+    #[error("The signature of the image data is unknown")]
+    UnknownSignature,
+}
+
+/// The error type for loading an image source.
+#[derive(Debug, thiserror::Error)]
+pub enum ImageLoadError {
+    /// Failed to detect the media type.
+    #[error(transparent)]
+    MediaTypeParseFailed(#[from] ImageMediaTypeParseError),
+    /// Failed to read the image file.
+    #[error("Failed to read the image file: {0}")]
+    ReadFileFailed(std::io::Error),
+    /// The encoded image is larger than the maximum size.
+    #[error("The encoded image is too large: {size} bytes > {max} bytes")]
+    TooLarge {
+        /// The size of the encoded image in bytes.
+        size: usize,
+        /// The maximum size in bytes.
+        max: usize,
+    },
+    /// Failed to decode, resize or encode the image.
+    #[cfg(feature = "image")]
+    #[error("Failed to process the image: {0}")]
+    ImageProcessingFailed(#[from] image::ImageError),
+}
+
+/// The error type for parsing the document media type from an extension in a path.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DocumentMediaTypeParseError {
+    /// The extension is not supported
+    #[error("The extension is not supported: {0}")]
+    NotSupported(String),
+    /// Extension is not found
+    #[error("Extension is not found")]
+    NotFound,
+}
+
+/// The error type for loading a document source.
+#[derive(Debug, thiserror::Error)]
+pub enum DocumentLoadError {
+    /// Failed to parse the media type.
+    #[error(transparent)]
+    MediaTypeParseFailed(#[from] DocumentMediaTypeParseError),
+    /// Failed to read the document file.
+    #[error("Failed to read the document file: {0}")]
+    ReadFileFailed(std::io::Error),
+    /// Failed to decode the plain text document as UTF-8.
+    #[error("Failed to decode the plain text document: {0}")]
+    StringDecodingFailed(std::string::FromUtf8Error),
 }
 
 /// The error type for the tool call.
@@ -92,4 +167,40 @@ pub enum ToolCallError {
     /// Tool not found.
     #[error("Tool not found: {0}")]
     ToolNotFound(String),
+    /// The blocking call of a synchronous tool panicked or was cancelled.
+    #[error("Blocking tool call failed: {0}")]
+    BlockingCallFailed(String),
+}
+
+/// The error type for the tool runner.
+#[derive(Debug, thiserror::Error)]
+pub enum ToolRunnerError {
+    /// The error of the messages API.
+    #[error(transparent)]
+    MessagesError(#[from] MessagesError),
+    /// The error of the message stream.
+    #[error(transparent)]
+    StreamError(#[from] StreamError),
+}
+
+/// The error type for the tool list.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ToolListError {
+    /// The tool name does not match `^[a-zA-Z0-9_-]{1,64}$`.
+    #[error("Invalid tool name: {0}")]
+    InvalidName(String),
+    /// Another tool with the same name is already in the list.
+    #[error("Duplicate tool name: {0}")]
+    DuplicateName(String),
+    /// No tool with the name is in the list.
+    #[error("Tool not found: {0}")]
+    ToolNotFound(String),
+    /// The input schema of the custom tool cannot be compiled, only with the `validation` feature.
+    #[error("Invalid input schema of the tool {name}: {message}")]
+    InvalidSchema {
+        /// The name of the tool.
+        name: String,
+        /// The description of the error.
+        message: String,
+    },
 }
@@ -0,0 +1,408 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use base64::Engine;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+
+use crate::messages::{
+    Base64ImageSource, ImageContentSource, ImageLoadError, ImageMediaType,
+};
+
+/// The options of the image preprocessing.
+///
+/// The default values follow the recommended limits of [the vision guide](https://docs.anthropic.com/en/docs/build-with-claude/vision),
+/// that the API does not need to downsample the image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImagePreprocessOptions {
+    /// The maximum length of the long edge in pixels.
+    pub max_long_edge: u32,
+    /// The maximum number of pixels, i.e. width * height.
+    pub max_pixels: u32,
+    /// The media type of the re-encoded image.
+    ///
+    /// If `None`, JPEG images are re-encoded as JPEG and the others as PNG.
+    pub output_media_type: Option<ImageMediaType>,
+    /// The quality of the JPEG encoding in range: `[1, 100]`.
+    pub jpeg_quality: u8,
+}
+
+impl Default for ImagePreprocessOptions {
+    fn default() -> Self {
+        Self {
+            max_long_edge: 1568,
+            max_pixels: 1_150_000,
+            output_media_type: None,
+            jpeg_quality: 85,
+        }
+    }
+}
+
+/// The preprocessed image that is ready to send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreprocessedImage {
+    /// The image content source of the re-encoded image.
+    pub source: ImageContentSource,
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+}
+
+impl PreprocessedImage {
+    /// Estimates the number of input tokens of the image by `width * height / 750`.
+    pub fn estimated_tokens(&self) -> u32 {
+        estimate_tokens(self.width, self.height)
+    }
+}
+
+impl ImageContentSource {
+    /// Decodes the image, downscales it to the limits of the options, and re-encodes it without metadata, e.g. EXIF.
+    ///
+    /// The EXIF orientation is applied to the pixels before it is stripped.
+    ///
+    /// ## Arguments
+    /// - `bytes` - The raw bytes of the image.
+    /// - `options` - The options of the preprocessing.
+    ///
+    /// ## Errors
+    /// It returns an error if decoding or encoding failed, or the re-encoded image is still too large.
+    pub fn preprocess_bytes(
+        bytes: &[u8],
+        options: &ImagePreprocessOptions,
+    ) -> Result<PreprocessedImage, ImageLoadError> {
+        let input_media_type = ImageMediaType::from_magic_bytes(bytes)?;
+        let image = decode(bytes)?;
+
+        let (width, height) =
+            fit_to_limits(image.width(), image.height(), options);
+        let image = if (width, height) != (image.width(), image.height()) {
+            image.resize_exact(width, height, FilterType::Lanczos3)
+        } else {
+            image
+        };
+
+        let output_media_type = options
+            .output_media_type
+            .unwrap_or(match input_media_type {
+                | ImageMediaType::Jpeg => ImageMediaType::Jpeg,
+                | _ => ImageMediaType::Png,
+            });
+        let encoded = encode(&image, output_media_type, options)?;
+
+        Ok(PreprocessedImage {
+            source: Self::from_bytes_with_media_type(
+                output_media_type,
+                &encoded,
+            )?,
+            width,
+            height,
+        })
+    }
+
+    /// Loads an image file and preprocesses it by [`ImageContentSource::preprocess_bytes`].
+    ///
+    /// ## Arguments
+    /// - `path` - The path of the image file.
+    /// - `options` - The options of the preprocessing.
+    ///
+    /// ## Errors
+    /// It returns an error if reading the file failed or the preprocessing failed.
+    pub fn preprocess_path(
+        path: &Path,
+        options: &ImagePreprocessOptions,
+    ) -> Result<PreprocessedImage, ImageLoadError> {
+        let bytes =
+            std::fs::read(path).map_err(ImageLoadError::ReadFileFailed)?;
+
+        Self::preprocess_bytes(&bytes, options)
+    }
+}
+
+impl Base64ImageSource {
+    /// Reads the width and height of the image from the header of the data.
+    ///
+    /// ## Errors
+    /// It returns an error if the data is not valid Base64 or the image header could not be read.
+    pub fn dimensions(&self) -> Result<(u32, u32), ImageLoadError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&self.data)
+            .map_err(|error| {
+                ImageLoadError::ImageProcessingFailed(
+                    image::ImageError::IoError(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        error,
+                    )),
+                )
+            })?;
+
+        Ok(ImageReader::new(Cursor::new(bytes))
+            .with_guessed_format()
+            .map_err(image::ImageError::IoError)?
+            .into_dimensions()?)
+    }
+
+    /// Estimates the number of input tokens of the image by `width * height / 750`.
+    ///
+    /// ## Errors
+    /// It returns an error if the dimensions could not be read.
+    pub fn estimated_tokens(&self) -> Result<u32, ImageLoadError> {
+        let (width, height) = self.dimensions()?;
+
+        Ok(estimate_tokens(width, height))
+    }
+}
+
+fn estimate_tokens(
+    width: u32,
+    height: u32,
+) -> u32 {
+    ((width as u64 * height as u64) / 750) as u32
+}
+
+fn fit_to_limits(
+    width: u32,
+    height: u32,
+    options: &ImagePreprocessOptions,
+) -> (u32, u32) {
+    let long_edge = width.max(height) as f64;
+    let pixels = width as f64 * height as f64;
+
+    let scale = 1f64
+        .min(options.max_long_edge as f64 / long_edge)
+        .min((options.max_pixels as f64 / pixels).sqrt());
+
+    if scale >= 1.0 {
+        (width, height)
+    } else {
+        (
+            ((width as f64 * scale).floor() as u32).max(1),
+            ((height as f64 * scale).floor() as u32).max(1),
+        )
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<DynamicImage, ImageLoadError> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(image::ImageError::IoError)?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+fn encode(
+    image: &DynamicImage,
+    media_type: ImageMediaType,
+    options: &ImagePreprocessOptions,
+) -> Result<Vec<u8>, ImageLoadError> {
+    let mut buffer = Cursor::new(Vec::new());
+
+    match media_type {
+        | ImageMediaType::Jpeg => {
+            // JPEG does not support the alpha channel.
+            let encoder = JpegEncoder::new_with_quality(
+                &mut buffer,
+                options.jpeg_quality,
+            );
+            DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_with_encoder(encoder)?;
+        },
+        | ImageMediaType::Png => {
+            image.write_to(&mut buffer, ImageFormat::Png)?;
+        },
+        | ImageMediaType::Gif => {
+            image.write_to(&mut buffer, ImageFormat::Gif)?;
+        },
+        | ImageMediaType::Webp => {
+            image.write_to(&mut buffer, ImageFormat::WebP)?;
+        },
+    }
+
+    Ok(buffer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+    fn encode_test_image(
+        image: DynamicImage,
+        format: ImageFormat,
+    ) -> Vec<u8> {
+        let mut buffer = Cursor::new(Vec::new());
+        image
+            .write_to(&mut buffer, format)
+            .unwrap();
+        buffer.into_inner()
+    }
+
+    #[test]
+    fn default_options() {
+        let options = ImagePreprocessOptions::default();
+        assert_eq!(options.max_long_edge, 1568);
+        assert_eq!(options.max_pixels, 1_150_000);
+        assert_eq!(options.output_media_type, None);
+        assert_eq!(options.jpeg_quality, 85);
+    }
+
+    #[test]
+    fn fit_to_limits_by_long_edge() {
+        let options = ImagePreprocessOptions {
+            max_long_edge: 100,
+            max_pixels: u32::MAX,
+            ..Default::default()
+        };
+        assert_eq!(
+            fit_to_limits(400, 200, &options),
+            (100, 50)
+        );
+        assert_eq!(
+            fit_to_limits(200, 400, &options),
+            (50, 100)
+        );
+        assert_eq!(
+            fit_to_limits(80, 40, &options),
+            (80, 40)
+        );
+    }
+
+    #[test]
+    fn fit_to_limits_by_pixels() {
+        let options = ImagePreprocessOptions::default();
+        // 12 megapixels photo.
+        let (width, height) = fit_to_limits(4000, 3000, &options);
+        assert!(width <= 1568);
+        assert!(width * height <= 1_150_000);
+        assert_eq!((width, height), (1238, 928));
+    }
+
+    #[test]
+    fn estimate_tokens_of_image() {
+        assert_eq!(estimate_tokens(1000, 1000), 1333);
+        assert_eq!(estimate_tokens(0, 1000), 0);
+    }
+
+    #[test]
+    fn preprocess_jpeg() {
+        let bytes = encode_test_image(
+            DynamicImage::ImageRgb8(RgbImage::from_pixel(
+                3200,
+                1600,
+                Rgb([255, 0, 0]),
+            )),
+            ImageFormat::Jpeg,
+        );
+
+        let preprocessed = ImageContentSource::preprocess_bytes(
+            &bytes,
+            &ImagePreprocessOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(preprocessed.width, 1516);
+        assert_eq!(preprocessed.height, 758);
+        assert_eq!(
+            preprocessed.estimated_tokens(),
+            1516 * 758 / 750
+        );
+        match preprocessed.source {
+            | ImageContentSource::Base64(source) => {
+                assert_eq!(source.media_type, ImageMediaType::Jpeg);
+                assert_eq!(
+                    source.dimensions().unwrap(),
+                    (1516, 758)
+                );
+                assert_eq!(
+                    source
+                        .estimated_tokens()
+                        .unwrap(),
+                    1516 * 758 / 750
+                );
+            },
+            | _ => panic!("unexpected image source"),
+        }
+    }
+
+    #[test]
+    fn preprocess_small_png_keeps_size() {
+        let bytes = encode_test_image(
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+                64,
+                32,
+                Rgba([0, 0, 255, 128]),
+            )),
+            ImageFormat::Png,
+        );
+
+        let preprocessed = ImageContentSource::preprocess_bytes(
+            &bytes,
+            &ImagePreprocessOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            (preprocessed.width, preprocessed.height),
+            (64, 32)
+        );
+        match preprocessed.source {
+            | ImageContentSource::Base64(source) => {
+                assert_eq!(source.media_type, ImageMediaType::Png);
+            },
+            | _ => panic!("unexpected image source"),
+        }
+    }
+
+    #[test]
+    fn preprocess_png_into_jpeg() {
+        let bytes = encode_test_image(
+            DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+                64,
+                32,
+                Rgba([0, 0, 255, 128]),
+            )),
+            ImageFormat::Png,
+        );
+
+        let preprocessed = ImageContentSource::preprocess_bytes(
+            &bytes,
+            &ImagePreprocessOptions {
+                output_media_type: Some(ImageMediaType::Jpeg),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match preprocessed.source {
+            | ImageContentSource::Base64(source) => {
+                assert_eq!(source.media_type, ImageMediaType::Jpeg);
+            },
+            | _ => panic!("unexpected image source"),
+        }
+    }
+
+    #[test]
+    fn preprocess_invalid_image() {
+        assert!(matches!(
+            ImageContentSource::preprocess_bytes(
+                b"not an image",
+                &ImagePreprocessOptions::default(),
+            ),
+            Err(ImageLoadError::MediaTypeParseFailed(_))
+        ));
+
+        assert!(matches!(
+            ImageContentSource::preprocess_bytes(
+                &[0xFF, 0xD8, 0xFF, 0x00],
+                &ImagePreprocessOptions::default(),
+            ),
+            Err(ImageLoadError::ImageProcessingFailed(_))
+        ));
+    }
+}
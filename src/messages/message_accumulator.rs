@@ -0,0 +1,521 @@
+use crate::messages::chunk_stream::ToolUseAccumulator;
+use crate::messages::{
+    ContentBlock, ContentBlockDelta, MessageChunk, MessagesResponseBody,
+    StreamError,
+};
+
+/// Accumulates the chunks of a message stream into the complete message.
+///
+/// The accumulated message has the same shape as the response of the non-streaming API:
+/// the text, the tool use input, the thinking and the citations are accumulated into the content blocks,
+/// and the delta usage is merged into the usage.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{
+///     ContentBlockDeltaChunk, ContentBlockStartChunk, ContentBlockStopChunk,
+///     MessageAccumulator, MessageChunk, MessageStartChunk,
+///     MessageStopChunk, MessagesResponseBody, TextContentBlock,
+/// };
+///
+/// let mut accumulator = MessageAccumulator::new();
+/// let chunks = vec![
+///     MessageChunk::MessageStart(MessageStartChunk::new(
+///         MessagesResponseBody::default(),
+///     )),
+///     MessageChunk::ContentBlockStart(ContentBlockStartChunk::new(
+///         0,
+///         TextContentBlock::new("").into(),
+///     )),
+///     MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+///         0,
+///         "Hello".into(),
+///     )),
+///     MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+///         0,
+///         ", world!".into(),
+///     )),
+///     MessageChunk::ContentBlockStop(ContentBlockStopChunk::new(0)),
+///     MessageChunk::MessageStop(MessageStopChunk::new()),
+/// ];
+///
+/// for chunk in &chunks {
+///     accumulator.accumulate(chunk).unwrap();
+/// }
+///
+/// assert!(accumulator.is_complete());
+/// assert_eq!(
+///     accumulator
+///         .into_message()
+///         .content
+///         .concatenated_text(),
+///     "Hello, world!"
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct MessageAccumulator {
+    message: Option<MessagesResponseBody>,
+    tool_uses: ToolUseAccumulator,
+    is_complete: bool,
+}
+
+impl MessageAccumulator {
+    /// Creates a new accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulates the chunk into the message.
+    ///
+    /// ## Arguments
+    /// - `chunk` - The next chunk of the message stream.
+    ///
+    /// ## Errors
+    /// - `StreamError::InvalidChunkSequence` if the chunk does not follow the previous chunks.
+    ///
+    /// The input of a tool use that is not valid JSON, e.g. cut off by `max_tokens`, is kept as the start block.
+    pub fn accumulate(
+        &mut self,
+        chunk: &MessageChunk,
+    ) -> Result<(), StreamError> {
+        let tool_use = self
+            .tool_uses
+            .accumulate(chunk)
+            .map(|(tool_use, _)| tool_use);
+
+        if let MessageChunk::MessageStart(start) = chunk {
+            if self.message.is_some() {
+                return Err(StreamError::InvalidChunkSequence(
+                    "message_start has already arrived".to_string(),
+                ));
+            }
+            self.message = Some(start.message.clone());
+            return Ok(());
+        }
+
+        if let MessageChunk::Ping(_) = chunk {
+            return Ok(());
+        }
+
+        let message = self
+            .message
+            .as_mut()
+            .ok_or_else(|| {
+                StreamError::InvalidChunkSequence(format!(
+                    "message_start has not arrived before: {}",
+                    chunk
+                ))
+            })?;
+
+        match chunk {
+            | MessageChunk::ContentBlockStart(start) => {
+                let blocks = message.content.blocks_mut();
+                if start.index as usize != blocks.len() {
+                    return Err(StreamError::InvalidChunkSequence(format!(
+                        "content block of index {} starts after {} blocks",
+                        start.index,
+                        blocks.len()
+                    )));
+                }
+                blocks.push(start.content_block.clone());
+            },
+            | MessageChunk::ContentBlockDelta(delta) => {
+                let block = message
+                    .content
+                    .blocks_mut()
+                    .get_mut(delta.index as usize)
+                    .ok_or_else(|| {
+                        StreamError::InvalidChunkSequence(format!(
+                            "content block of index {} has not started",
+                            delta.index
+                        ))
+                    })?;
+
+                match (block, &delta.delta) {
+                    | (
+                        ContentBlock::Text(block),
+                        ContentBlockDelta::TextDelta(delta),
+                    ) => {
+                        block.text.push_str(&delta.text);
+                    },
+                    | (
+                        ContentBlock::Text(block),
+                        ContentBlockDelta::CitationsDelta(delta),
+                    ) => {
+                        block
+                            .citations
+                            .get_or_insert_with(Vec::new)
+                            .push(delta.citation.clone());
+                    },
+                    | (
+                        ContentBlock::Thinking(block),
+                        ContentBlockDelta::ThinkingDelta(delta),
+                    ) => {
+                        block
+                            .thinking
+                            .push_str(&delta.thinking);
+                    },
+                    | (
+                        ContentBlock::Thinking(block),
+                        ContentBlockDelta::SignatureDelta(delta),
+                    ) => {
+                        block
+                            .signature
+                            .push_str(&delta.signature);
+                    },
+                    // The input of the tool use is set at the end of the block.
+                    | (
+                        ContentBlock::ToolUse(_)
+                        | ContentBlock::ServerToolUse(_),
+                        ContentBlockDelta::InputJsonDelta(_),
+                    ) => {},
+                    // Skip the delta added to the API after this version.
+                    | (_, ContentBlockDelta::Unknown(_)) => {},
+                    | (block, delta) => {
+                        return Err(StreamError::InvalidChunkSequence(
+                            format!(
+                                "delta {} does not match the content block: {}",
+                                delta, block
+                            ),
+                        ));
+                    },
+                }
+            },
+            | MessageChunk::ContentBlockStop(stop) => {
+                let block = message
+                    .content
+                    .blocks_mut()
+                    .get_mut(stop.index as usize)
+                    .ok_or_else(|| {
+                        StreamError::InvalidChunkSequence(format!(
+                            "content block of index {} has not started",
+                            stop.index
+                        ))
+                    })?;
+
+                if let Some(tool_use) = tool_use {
+                    match block {
+                        | ContentBlock::ToolUse(block) => {
+                            block.tool_use = tool_use;
+                        },
+                        | ContentBlock::ServerToolUse(block) => {
+                            block.tool_use = tool_use;
+                        },
+                        | _ => {},
+                    }
+                }
+            },
+            | MessageChunk::MessageDelta(delta) => {
+                message.stop_reason = delta.delta.stop_reason.clone();
+                message.stop_sequence = delta
+                    .delta
+                    .stop_sequence
+                    .clone();
+
+                // The delta usage is cumulative.
+                message.usage.output_tokens = delta.usage.output_tokens;
+                if delta
+                    .usage
+                    .cache_creation_input_tokens
+                    .is_some()
+                {
+                    message
+                        .usage
+                        .cache_creation_input_tokens = delta
+                        .usage
+                        .cache_creation_input_tokens;
+                }
+                if delta
+                    .usage
+                    .cache_read_input_tokens
+                    .is_some()
+                {
+                    message.usage.cache_read_input_tokens =
+                        delta.usage.cache_read_input_tokens;
+                }
+            },
+            | MessageChunk::MessageStop(_) => {
+                self.is_complete = true;
+            },
+            | MessageChunk::MessageStart(_) | MessageChunk::Ping(_) => {},
+        }
+
+        Ok(())
+    }
+
+    /// The snapshot of the message accumulated so far, or `None` before `message_start`.
+    pub fn snapshot(&self) -> Option<&MessagesResponseBody> {
+        self.message.as_ref()
+    }
+
+    /// Whether `message_stop` has arrived.
+    pub fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    /// Takes the message accumulated so far.
+    ///
+    /// It returns the default message before `message_start`.
+    pub fn into_message(self) -> MessagesResponseBody {
+        self.message
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::*;
+
+    fn accumulate(source: &str) -> Result<MessageAccumulator, StreamError> {
+        let mut decoder = SseDecoder::new();
+        decoder.feed(source.as_bytes())?;
+
+        let mut accumulator = MessageAccumulator::new();
+        while let Some(event) = decoder.next_event() {
+            if let Some(chunk) = MessageChunk::from_event(&event)? {
+                accumulator.accumulate(&chunk)?;
+            }
+        }
+
+        Ok(accumulator)
+    }
+
+    #[test]
+    fn accumulate_text_and_tool_use() {
+        let source = r#"event: message_start
+data: {"type": "message_start", "message": {"id": "msg_1", "type": "message", "role": "assistant", "content": [], "model": "claude-3-opus-20240229", "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 25, "output_tokens": 1}}}
+
+event: content_block_start
+data: {"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}
+
+event: ping
+data: {"type": "ping"}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Let me check"}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": " the weather."}}
+
+event: content_block_stop
+data: {"type": "content_block_stop", "index": 0}
+
+event: content_block_start
+data: {"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_01", "name": "get_weather", "input": {}}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"location\":"}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": " \"Tokyo\"}"}}
+
+event: content_block_stop
+data: {"type": "content_block_stop", "index": 1}
+
+event: message_delta
+data: {"type": "message_delta", "delta": {"stop_reason": "tool_use", "stop_sequence": null}, "usage": {"output_tokens": 42, "cache_read_input_tokens": 10}}
+
+"#;
+
+        let mut accumulator = accumulate(source).unwrap();
+        assert!(!accumulator.is_complete());
+        accumulator
+            .accumulate(&MessageChunk::MessageStop(MessageStopChunk::new()))
+            .unwrap();
+        assert!(accumulator.is_complete());
+
+        assert_eq!(
+            accumulator.into_message(),
+            MessagesResponseBody {
+                id: "msg_1".to_string(),
+                content: vec![
+                    TextContentBlock::new("Let me check the weather.").into(),
+                    ToolUseContentBlock::new(ToolUse::new(
+                        "toolu_01",
+                        "get_weather",
+                        serde_json::json!({"location": "Tokyo"}),
+                    ))
+                    .into(),
+                ]
+                .into(),
+                model: ClaudeModel::Claude3Opus20240229,
+                stop_reason: Some(StopReason::ToolUse),
+                usage: Usage {
+                    input_tokens: 25,
+                    output_tokens: 42,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: Some(10),
+                },
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn accumulate_tool_use_cut_off_by_max_tokens() {
+        let source = r#"event: message_start
+data: {"type": "message_start", "message": {"id": "msg_1", "type": "message", "role": "assistant", "content": [], "model": "claude-3-opus-20240229", "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 25, "output_tokens": 1}}}
+
+event: content_block_start
+data: {"type": "content_block_start", "index": 0, "content_block": {"type": "tool_use", "id": "toolu_01", "name": "get_weather", "input": {}}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "input_json_delta", "partial_json": "{\"location\":"}}
+
+event: content_block_stop
+data: {"type": "content_block_stop", "index": 0}
+
+event: message_delta
+data: {"type": "message_delta", "delta": {"stop_reason": "max_tokens", "stop_sequence": null}, "usage": {"output_tokens": 10}}
+
+event: message_stop
+data: {"type": "message_stop"}
+
+"#;
+
+        let accumulator = accumulate(source).unwrap();
+        assert!(accumulator.is_complete());
+
+        let message = accumulator.into_message();
+        assert_eq!(message.stop_reason, Some(StopReason::MaxTokens));
+        assert_eq!(
+            message.content,
+            vec![ToolUseContentBlock::new(ToolUse::new(
+                "toolu_01",
+                "get_weather",
+                serde_json::json!({}),
+            ))
+            .into()]
+            .into()
+        );
+    }
+
+    #[test]
+    fn accumulate_thinking_and_citations() {
+        let source = r#"event: message_start
+data: {"type": "message_start", "message": {"id": "msg_1", "type": "message", "role": "assistant", "content": [], "model": "claude-3-opus-20240229", "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 25, "output_tokens": 1}}}
+
+event: content_block_start
+data: {"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": "", "signature": ""}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Let me think."}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "signature"}}
+
+event: content_block_stop
+data: {"type": "content_block_stop", "index": 0}
+
+event: content_block_start
+data: {"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 1, "delta": {"type": "citations_delta", "citation": {"type": "char_location", "cited_text": "The grass is green.", "document_index": 0, "document_title": "Example", "start_char_index": 0, "end_char_index": 20}}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "The grass is green."}}
+
+event: content_block_stop
+data: {"type": "content_block_stop", "index": 1}
+
+event: message_stop
+data: {"type": "message_stop"}
+
+"#;
+
+        let accumulator = accumulate(source).unwrap();
+        assert!(accumulator.is_complete());
+
+        let mut message = accumulator.into_message();
+        let blocks = message.content.blocks_mut();
+        assert_eq!(
+            blocks[0],
+            ContentBlock::Thinking(ThinkingContentBlock {
+                thinking: "Let me think.".to_string(),
+                signature: "signature".to_string(),
+                ..Default::default()
+            })
+        );
+        match &blocks[1] {
+            | ContentBlock::Text(block) => {
+                assert_eq!(block.text, "The grass is green.");
+                assert_eq!(
+                    block
+                        .citations
+                        .as_ref()
+                        .map(Vec::len),
+                    Some(1)
+                );
+            },
+            | _ => panic!("Unexpected content block"),
+        }
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut accumulator = MessageAccumulator::new();
+        assert_eq!(accumulator.snapshot(), None);
+
+        accumulator
+            .accumulate(&MessageChunk::MessageStart(
+                MessageStartChunk::new(MessagesResponseBody::default()),
+            ))
+            .unwrap();
+        accumulator
+            .accumulate(&MessageChunk::ContentBlockStart(
+                ContentBlockStartChunk::new(
+                    0,
+                    TextContentBlock::new("").into(),
+                ),
+            ))
+            .unwrap();
+        accumulator
+            .accumulate(&MessageChunk::ContentBlockDelta(
+                ContentBlockDeltaChunk::new(0, "Hello".into()),
+            ))
+            .unwrap();
+
+        assert_eq!(
+            accumulator
+                .snapshot()
+                .unwrap()
+                .content
+                .concatenated_text(),
+            "Hello"
+        );
+    }
+
+    #[test]
+    fn invalid_chunk_sequence() {
+        let mut accumulator = MessageAccumulator::new();
+        assert!(matches!(
+            accumulator.accumulate(&MessageChunk::ContentBlockDelta(
+                ContentBlockDeltaChunk::new(0, "Hello".into()),
+            )),
+            Err(StreamError::InvalidChunkSequence(_))
+        ));
+
+        accumulator
+            .accumulate(&MessageChunk::MessageStart(
+                MessageStartChunk::new(MessagesResponseBody::default()),
+            ))
+            .unwrap();
+        assert!(matches!(
+            accumulator.accumulate(&MessageChunk::ContentBlockDelta(
+                ContentBlockDeltaChunk::new(0, "Hello".into()),
+            )),
+            Err(StreamError::InvalidChunkSequence(_))
+        ));
+        assert!(matches!(
+            accumulator.accumulate(&MessageChunk::ContentBlockStart(
+                ContentBlockStartChunk::new(
+                    1,
+                    TextContentBlock::new("").into(),
+                ),
+            )),
+            Err(StreamError::InvalidChunkSequence(_))
+        ));
+    }
+}
@@ -1,13 +1,17 @@
+use reqwest::StatusCode;
 use serde_json_fmt::JsonFormat;
 use std::fmt::{Debug, Display};
 use std::str::FromStr;
 
 use crate::macros::{
     impl_display_for_serialize, impl_enum_string_serialization,
+    impl_enum_struct_serialization,
 };
+use crate::ApiError;
 use crate::messages::{
-    ContentType, MessageChunkTypeError, MessagesResponseBody, StopReason,
-    StopSequence, StreamError, TextContentBlock,
+    Citation, ContentBlock, ContentType, MessageChunkTypeError,
+    MessagesResponseBody, SseEvent, StopReason, StopSequence, StreamError,
+    ToolUse,
 };
 
 /// The stream chunk of messages.
@@ -123,83 +127,64 @@ impl Display for MessageChunk {
 }
 
 impl MessageChunk {
-    pub(crate) fn parse(source: &str) -> Result<MessageChunk, StreamError> {
-        let lines = source
-            .lines()
-            .collect::<Vec<&str>>();
-
-        // Check length
-        if lines.len() != 2 {
-            return Err(StreamError::ParseChunkStringError(
-                format!(
-                    "Chunk must be two lines but not: {}",
-                    source
-                ),
-            ));
-        }
-
-        // Parse the event segment to the chunk type.
-        let first_line = lines[0];
-        let event = first_line
-            .strip_prefix("event: ")
-            .ok_or_else(|| {
-                StreamError::ParseChunkStringError(format!(
-                    "First line must start with 'event: ', but not: {}",
-                    source
-                ))
-            })?;
-        let chunk_type = MessageChunkType::from_str(event)
-            .map_err(StreamError::MessageChunkTypeError)?;
-
-        // Parse the data segment to the chunk data.
-        let second_line = lines[1];
-        let data = second_line
-            .strip_prefix("data: ")
-            .ok_or_else(|| {
-                StreamError::ParseChunkStringError(format!(
-                    "Second line must start with 'data: ', but not: {}",
-                    source
-                ))
-            })?;
+    /// Deserializes the chunk from the server-sent event, or returns `None` for an event of an unknown type to be skipped.
+    pub(crate) fn from_event(
+        event: &SseEvent,
+    ) -> Result<Option<MessageChunk>, StreamError> {
+        let chunk_type = match MessageChunkType::from_str(&event.event) {
+            | Ok(chunk_type) => chunk_type,
+            // Skip the event added to the API after this version.
+            | Err(_) => return Ok(None),
+        };
+        let data = event.data.as_str();
 
         // Deserialize the chunk data.
         match chunk_type {
             | MessageChunkType::MessageStart => {
                 let message = serde_json::from_str(data)
                     .map_err(StreamError::ChunkDataDeserializationError)?;
-                Ok(MessageChunk::MessageStart(message))
+                Ok(Some(MessageChunk::MessageStart(message)))
             },
             | MessageChunkType::ContentBlockStart => {
                 let content_block = serde_json::from_str(data)
                     .map_err(StreamError::ChunkDataDeserializationError)?;
-                Ok(MessageChunk::ContentBlockStart(
+                Ok(Some(MessageChunk::ContentBlockStart(
                     content_block,
-                ))
+                )))
             },
             | MessageChunkType::Ping => {
                 let ping = serde_json::from_str(data)
                     .map_err(StreamError::ChunkDataDeserializationError)?;
-                Ok(MessageChunk::Ping(ping))
+                Ok(Some(MessageChunk::Ping(ping)))
             },
             | MessageChunkType::ContentBlockDelta => {
                 let delta = serde_json::from_str(data)
                     .map_err(StreamError::ChunkDataDeserializationError)?;
-                Ok(MessageChunk::ContentBlockDelta(delta))
+                Ok(Some(MessageChunk::ContentBlockDelta(delta)))
             },
             | MessageChunkType::ContentBlockStop => {
                 let stop = serde_json::from_str(data)
                     .map_err(StreamError::ChunkDataDeserializationError)?;
-                Ok(MessageChunk::ContentBlockStop(stop))
+                Ok(Some(MessageChunk::ContentBlockStop(stop)))
             },
             | MessageChunkType::MessageDelta => {
                 let delta = serde_json::from_str(data)
                     .map_err(StreamError::ChunkDataDeserializationError)?;
-                Ok(MessageChunk::MessageDelta(delta))
+                Ok(Some(MessageChunk::MessageDelta(delta)))
             },
             | MessageChunkType::MessageStop => {
                 let stop = serde_json::from_str(data)
                     .map_err(StreamError::ChunkDataDeserializationError)?;
-                Ok(MessageChunk::MessageStop(stop))
+                Ok(Some(MessageChunk::MessageStop(stop)))
+            },
+            | MessageChunkType::Error => {
+                let response = serde_json::from_str(data)
+                    .map_err(StreamError::ChunkDataDeserializationError)?;
+                // The status code of the streaming response has been OK.
+                Err(StreamError::Api(ApiError::new(
+                    StatusCode::OK,
+                    response,
+                )))
             },
         }
     }
@@ -222,6 +207,8 @@ pub enum MessageChunkType {
     MessageDelta,
     /// message_stop
     MessageStop,
+    /// error
+    Error,
 }
 
 impl Display for MessageChunkType {
@@ -243,6 +230,7 @@ impl Display for MessageChunkType {
             },
             | MessageChunkType::MessageDelta => write!(f, "message_delta"),
             | MessageChunkType::MessageStop => write!(f, "message_stop"),
+            | MessageChunkType::Error => write!(f, "error"),
         }
     }
 }
@@ -259,6 +247,7 @@ impl FromStr for MessageChunkType {
             | "content_block_stop" => Ok(MessageChunkType::ContentBlockStop),
             | "message_delta" => Ok(MessageChunkType::MessageDelta),
             | "message_stop" => Ok(MessageChunkType::MessageStop),
+            | "error" => Ok(MessageChunkType::Error),
             | _ => Err(MessageChunkTypeError {
                 chunk_type: s.to_string(),
             }),
@@ -274,7 +263,8 @@ impl_enum_string_serialization!(
     ContentBlockDelta => "content_block_delta",
     ContentBlockStop => "content_block_stop",
     MessageDelta => "message_delta",
-    MessageStop => "message_stop"
+    MessageStop => "message_stop",
+    Error => "error"
 );
 
 /// The message start chunk.
@@ -316,8 +306,8 @@ pub struct ContentBlockStartChunk {
     pub _type: MessageChunkType,
     /// The index.
     pub index: u32,
-    /// The text content block of start.
-    pub content_block: TextContentBlock,
+    /// The content block of start.
+    pub content_block: ContentBlock,
 }
 
 impl Default for ContentBlockStartChunk {
@@ -336,7 +326,7 @@ impl ContentBlockStartChunk {
     /// Creates a new `ContentBlockStart` instance.
     pub fn new(
         index: u32,
-        content_block: TextContentBlock,
+        content_block: ContentBlock,
     ) -> Self {
         Self {
             _type: MessageChunkType::ContentBlockStart,
@@ -381,8 +371,8 @@ pub struct ContentBlockDeltaChunk {
     pub _type: MessageChunkType,
     /// The index.
     pub index: u32,
-    /// The text delta content block.
-    pub delta: TextDeltaContentBlock,
+    /// The delta of the content block.
+    pub delta: ContentBlockDelta,
 }
 
 impl Default for ContentBlockDeltaChunk {
@@ -401,7 +391,7 @@ impl ContentBlockDeltaChunk {
     /// Creates a new `ContentBlockDelta` instance.
     pub fn new(
         index: u32,
-        delta: TextDeltaContentBlock,
+        delta: ContentBlockDelta,
     ) -> Self {
         Self {
             _type: MessageChunkType::ContentBlockDelta,
@@ -419,6 +409,17 @@ pub struct ContentBlockStopChunk {
     pub _type: MessageChunkType,
     /// The index.
     pub index: u32,
+    /// The tool use with the input accumulated from the `input_json_delta`s of the block.
+    ///
+    /// It is set by the stream only at the end of a tool use or server tool use block and is not serialized.
+    #[serde(skip)]
+    pub tool_use: Option<ToolUse>,
+    /// The raw accumulated `partial_json` of the tool use when it is not valid JSON,
+    /// e.g. cut off by the `max_tokens` stop reason.
+    ///
+    /// The `tool_use` keeps the input of the start block then. It is not serialized.
+    #[serde(skip)]
+    pub incomplete_input: Option<String>,
 }
 
 impl Default for ContentBlockStopChunk {
@@ -426,6 +427,8 @@ impl Default for ContentBlockStopChunk {
         Self {
             _type: MessageChunkType::ContentBlockStop,
             index: Default::default(),
+            tool_use: None,
+            incomplete_input: None,
         }
     }
 }
@@ -438,6 +441,8 @@ impl ContentBlockStopChunk {
         Self {
             _type: MessageChunkType::ContentBlockStop,
             index,
+            tool_use: None,
+            incomplete_input: None,
         }
     }
 }
@@ -507,6 +512,62 @@ impl MessageStopChunk {
     }
 }
 
+/// The delta of a content block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentBlockDelta {
+    /// The text delta.
+    TextDelta(TextDeltaContentBlock),
+    /// The citation delta that is added to the current text block.
+    CitationsDelta(CitationsDeltaContentBlock),
+    /// The thinking delta.
+    ThinkingDelta(ThinkingDeltaContentBlock),
+    /// The signature of the current thinking block.
+    SignatureDelta(SignatureDeltaContentBlock),
+    /// The partial JSON of the input of the current tool use block.
+    InputJsonDelta(InputJsonDeltaContentBlock),
+    /// A delta of an unknown type that is kept as the raw JSON.
+    Unknown(serde_json::Value),
+}
+
+impl Default for ContentBlockDelta {
+    fn default() -> Self {
+        Self::TextDelta(TextDeltaContentBlock::default())
+    }
+}
+
+impl_enum_struct_serialization!(
+    ContentBlockDelta,
+    type,
+    TextDelta(TextDeltaContentBlock, "text_delta"),
+    CitationsDelta(CitationsDeltaContentBlock, "citations_delta"),
+    ThinkingDelta(ThinkingDeltaContentBlock, "thinking_delta"),
+    SignatureDelta(SignatureDeltaContentBlock, "signature_delta"),
+    InputJsonDelta(InputJsonDeltaContentBlock, "input_json_delta");
+    _ => Unknown
+);
+
+impl_display_for_serialize!(ContentBlockDelta);
+
+impl From<String> for ContentBlockDelta {
+    fn from(text: String) -> Self {
+        Self::TextDelta(text.into())
+    }
+}
+
+impl From<&str> for ContentBlockDelta {
+    fn from(text: &str) -> Self {
+        Self::TextDelta(text.into())
+    }
+}
+
+impl From<Citation> for ContentBlockDelta {
+    fn from(citation: Citation) -> Self {
+        Self::CitationsDelta(CitationsDeltaContentBlock::new(
+            citation,
+        ))
+    }
+}
+
 /// The text delta content block.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TextDeltaContentBlock {
@@ -542,7 +603,7 @@ impl From<&str> for TextDeltaContentBlock {
 
 impl TextDeltaContentBlock {
     /// Creates a new text delta content block.
-    pub(crate) fn new<S>(text: S) -> Self
+    pub fn new<S>(text: S) -> Self
     where
         S: Into<String>,
     {
@@ -553,6 +614,141 @@ impl TextDeltaContentBlock {
     }
 }
 
+/// The citations delta content block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CitationsDeltaContentBlock {
+    /// The content type. It is always `citations_delta`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The citation.
+    pub citation: Citation,
+}
+
+impl Default for CitationsDeltaContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::CitationsDelta,
+            citation: Citation::default(),
+        }
+    }
+}
+
+impl_display_for_serialize!(CitationsDeltaContentBlock);
+
+impl CitationsDeltaContentBlock {
+    /// Creates a new citations delta content block.
+    pub fn new(citation: Citation) -> Self {
+        Self {
+            _type: ContentType::CitationsDelta,
+            citation,
+        }
+    }
+}
+
+/// The thinking delta content block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ThinkingDeltaContentBlock {
+    /// The content type. It is always `thinking_delta`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The thinking delta content.
+    pub thinking: String,
+}
+
+impl Default for ThinkingDeltaContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::ThinkingDelta,
+            thinking: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(ThinkingDeltaContentBlock);
+
+impl ThinkingDeltaContentBlock {
+    /// Creates a new thinking delta content block.
+    pub fn new<S>(thinking: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            _type: ContentType::ThinkingDelta,
+            thinking: thinking.into(),
+        }
+    }
+}
+
+/// The input JSON delta content block of a tool use.
+///
+/// The partial JSON strings of a block are concatenated into the complete input at the end of the block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InputJsonDeltaContentBlock {
+    /// The content type. It is always `input_json_delta`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The partial JSON string of the input.
+    pub partial_json: String,
+}
+
+impl Default for InputJsonDeltaContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::InputJsonDelta,
+            partial_json: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(InputJsonDeltaContentBlock);
+
+impl InputJsonDeltaContentBlock {
+    /// Creates a new input JSON delta content block.
+    pub fn new<S>(partial_json: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            _type: ContentType::InputJsonDelta,
+            partial_json: partial_json.into(),
+        }
+    }
+}
+
+/// The signature delta content block.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SignatureDeltaContentBlock {
+    /// The content type. It is always `signature_delta`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The signature of the thinking block.
+    pub signature: String,
+}
+
+impl Default for SignatureDeltaContentBlock {
+    fn default() -> Self {
+        Self {
+            _type: ContentType::SignatureDelta,
+            signature: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(SignatureDeltaContentBlock);
+
+impl SignatureDeltaContentBlock {
+    /// Creates a new signature delta content block.
+    pub fn new<S>(signature: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            _type: ContentType::SignatureDelta,
+            signature: signature.into(),
+        }
+    }
+}
+
 /// The stream stop information.
 #[derive(
     Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
@@ -581,6 +777,12 @@ impl_display_for_serialize!(StreamStop);
 pub struct DeltaUsage {
     /// The number of output tokens which were used.
     pub output_tokens: u32,
+    /// The cumulative number of input tokens used to create the cache entry.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// The cumulative number of input tokens read from the cache.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_read_input_tokens: Option<u32>,
 }
 
 impl_display_for_serialize!(DeltaUsage);
@@ -589,6 +791,16 @@ impl_display_for_serialize!(DeltaUsage);
 mod tests {
     use super::super::*;
     use super::*;
+    use crate::messages::SseDecoder;
+
+    fn parse(source: &str) -> Result<Option<MessageChunk>, StreamError> {
+        let mut decoder = SseDecoder::new();
+        decoder
+            .feed(source.as_bytes())
+            .unwrap();
+        decoder.feed(b"\n\n").unwrap();
+        MessageChunk::from_event(&decoder.next_event().unwrap())
+    }
 
     #[test]
     fn from_str_chunk_type() {
@@ -736,6 +948,8 @@ mod tests {
             DeltaUsage::default(),
             DeltaUsage {
                 output_tokens: Default::default(),
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             }
         );
     }
@@ -744,6 +958,8 @@ mod tests {
     fn display_delta_usage() {
         let usage = DeltaUsage {
             output_tokens: 1,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
         };
         assert_eq!(
             usage.to_string(),
@@ -755,6 +971,8 @@ mod tests {
     fn serialize_delta_usage() {
         let usage = DeltaUsage {
             output_tokens: 1,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
         };
         assert_eq!(
             serde_json::to_string(&usage).unwrap(),
@@ -766,12 +984,27 @@ mod tests {
     fn deserialize_delta_usage() {
         let usage = DeltaUsage {
             output_tokens: 1,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
         };
         assert_eq!(
             serde_json::from_str::<DeltaUsage>(r#"{"output_tokens":1}"#)
                 .unwrap(),
             usage
         );
+
+        let usage = DeltaUsage {
+            output_tokens: 1,
+            cache_creation_input_tokens: Some(2),
+            cache_read_input_tokens: Some(3),
+        };
+        assert_eq!(
+            serde_json::from_str::<DeltaUsage>(
+                r#"{"output_tokens":1,"cache_creation_input_tokens":2,"cache_read_input_tokens":3}"#
+            )
+            .unwrap(),
+            usage
+        );
     }
 
     #[test]
@@ -850,6 +1083,8 @@ mod tests {
                 usage: Usage {
                     input_tokens: 1,
                     output_tokens: 2,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 },
             },
         };
@@ -874,6 +1109,8 @@ mod tests {
                 usage: Usage {
                     input_tokens: 1,
                     output_tokens: 2,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 },
             },
         };
@@ -898,6 +1135,8 @@ mod tests {
                 usage: Usage {
                     input_tokens: 1,
                     output_tokens: 2,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 },
             },
         };
@@ -927,10 +1166,10 @@ mod tests {
         let content_block_start = ContentBlockStartChunk {
             _type: MessageChunkType::ContentBlockStart,
             index: 1,
-            content_block: TextContentBlock {
+            content_block: ContentBlock::Text(TextContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         assert_eq!(
             content_block_start.to_string(),
@@ -943,10 +1182,10 @@ mod tests {
         let content_block_start = ContentBlockStartChunk {
             _type: MessageChunkType::ContentBlockStart,
             index: 1,
-            content_block: TextContentBlock {
+            content_block: ContentBlock::Text(TextContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         assert_eq!(
             serde_json::to_string(&content_block_start).unwrap(),
@@ -959,10 +1198,10 @@ mod tests {
         let content_block_start = ContentBlockStartChunk {
             _type: MessageChunkType::ContentBlockStart,
             index: 1,
-            content_block: TextContentBlock {
+            content_block: ContentBlock::Text(TextContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         assert_eq!(
             serde_json::from_str::<ContentBlockStartChunk>(
@@ -1027,10 +1266,10 @@ mod tests {
         let content_block_delta = ContentBlockDeltaChunk {
             _type: MessageChunkType::ContentBlockDelta,
             index: 1,
-            delta: TextDeltaContentBlock {
+            delta: ContentBlockDelta::TextDelta(TextDeltaContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         assert_eq!(
             content_block_delta.to_string(),
@@ -1043,10 +1282,10 @@ mod tests {
         let content_block_delta = ContentBlockDeltaChunk {
             _type: MessageChunkType::ContentBlockDelta,
             index: 1,
-            delta: TextDeltaContentBlock {
+            delta: ContentBlockDelta::TextDelta(TextDeltaContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         assert_eq!(
             serde_json::to_string(&content_block_delta).unwrap(),
@@ -1059,10 +1298,10 @@ mod tests {
         let content_block_delta = ContentBlockDeltaChunk {
             _type: MessageChunkType::ContentBlockDelta,
             index: 1,
-            delta: TextDeltaContentBlock {
+            delta: ContentBlockDelta::TextDelta(TextDeltaContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         assert_eq!(
             serde_json::from_str::<ContentBlockDeltaChunk>(
@@ -1080,6 +1319,8 @@ mod tests {
             ContentBlockStopChunk {
                 _type: MessageChunkType::ContentBlockStop,
                 index: Default::default(),
+                tool_use: None,
+                incomplete_input: None,
             }
         );
     }
@@ -1089,6 +1330,8 @@ mod tests {
         let content_block_stop = ContentBlockStopChunk {
             _type: MessageChunkType::ContentBlockStop,
             index: 1,
+            tool_use: None,
+            incomplete_input: None,
         };
         assert_eq!(
             content_block_stop.to_string(),
@@ -1101,6 +1344,8 @@ mod tests {
         let content_block_stop = ContentBlockStopChunk {
             _type: MessageChunkType::ContentBlockStop,
             index: 1,
+            tool_use: None,
+            incomplete_input: None,
         };
         assert_eq!(
             serde_json::to_string(&content_block_stop).unwrap(),
@@ -1113,6 +1358,8 @@ mod tests {
         let content_block_stop = ContentBlockStopChunk {
             _type: MessageChunkType::ContentBlockStop,
             index: 1,
+            tool_use: None,
+            incomplete_input: None,
         };
         assert_eq!(
             serde_json::from_str::<ContentBlockStopChunk>(
@@ -1145,6 +1392,8 @@ mod tests {
             },
             usage: DeltaUsage {
                 output_tokens: 1,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         };
         assert_eq!(
@@ -1163,6 +1412,8 @@ mod tests {
             },
             usage: DeltaUsage {
                 output_tokens: 1,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         };
         assert_eq!(
@@ -1181,6 +1432,8 @@ mod tests {
             },
             usage: DeltaUsage {
                 output_tokens: 1,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         };
         assert_eq!(
@@ -1247,29 +1500,33 @@ mod tests {
                 usage: Usage {
                     input_tokens: 1,
                     output_tokens: 2,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 },
             },
         };
         let content_block_start = ContentBlockStartChunk {
             _type: MessageChunkType::ContentBlockStart,
             index: 1,
-            content_block: TextContentBlock {
+            content_block: ContentBlock::Text(TextContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         let ping = PingChunk::default();
         let content_block_delta = ContentBlockDeltaChunk {
             _type: MessageChunkType::ContentBlockDelta,
             index: 1,
-            delta: TextDeltaContentBlock {
+            delta: ContentBlockDelta::TextDelta(TextDeltaContentBlock {
                 text: "text".to_string(),
                 ..Default::default()
-            },
+            }),
         };
         let content_block_stop = ContentBlockStopChunk {
             _type: MessageChunkType::ContentBlockStop,
             index: 1,
+            tool_use: None,
+            incomplete_input: None,
         };
         let message_delta = MessageDeltaChunk {
             _type: MessageChunkType::MessageDelta,
@@ -1279,6 +1536,8 @@ mod tests {
             },
             usage: DeltaUsage {
                 output_tokens: 1,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         };
         let message_stop = MessageStopChunk::default();
@@ -1322,10 +1581,11 @@ mod tests {
     #[test]
     fn parse_stream_chunk() {
         assert_eq!(
-            MessageChunk::parse(
+            parse(
                 r#"event: message_start
 data: {"type": "message_start", "message": {"id": "msg_1nZdL29xx5MUA1yADyHTEsnR8uuvGzszyY", "type": "message", "role": "assistant", "content": [], "model": "claude-3-opus-20240229", "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 25, "output_tokens": 1}}}"#
             )
+            .unwrap()
             .unwrap(),
             MessageChunk::MessageStart(MessageStartChunk {
                 _type: MessageChunkType::MessageStart,
@@ -1340,66 +1600,74 @@ data: {"type": "message_start", "message": {"id": "msg_1nZdL29xx5MUA1yADyHTEsnR8
                     usage: Usage {
                         input_tokens: 25,
                         output_tokens: 1,
+                        cache_creation_input_tokens: None,
+                        cache_read_input_tokens: None,
                     },
                 },
             })
         );
 
         assert_eq!(
-            MessageChunk::parse(r#"event: content_block_start
-data: {"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}"#).unwrap(),
+            parse(r#"event: content_block_start
+data: {"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}"#).unwrap().unwrap(),
             MessageChunk::ContentBlockStart(ContentBlockStartChunk {
                 _type: MessageChunkType::ContentBlockStart,
                 index: 0,
-                content_block: TextContentBlock {
+                content_block: ContentBlock::Text(TextContentBlock {
                     text: "".to_string(),
                     ..Default::default()
-                },
+                }),
             })
         );
 
         assert_eq!(
-            MessageChunk::parse(
+            parse(
                 r#"event: ping
 data: {"type": "ping"}"#
             )
+            .unwrap()
             .unwrap(),
             MessageChunk::Ping(PingChunk::default())
         );
 
         assert_eq!(
-            MessageChunk::parse(
+            parse(
                 r#"event: content_block_delta
 data: {"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hello"}}"#
             )
+            .unwrap()
             .unwrap(),
             MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk {
                 _type: MessageChunkType::ContentBlockDelta,
                 index: 0,
-                delta: TextDeltaContentBlock {
+                delta: ContentBlockDelta::TextDelta(TextDeltaContentBlock {
                     text: "Hello".to_string(),
                     ..Default::default()
-                },
+                }),
             })
         );
 
         assert_eq!(
-            MessageChunk::parse(
+            parse(
                 r#"event: content_block_stop
 data: {"type": "content_block_stop", "index": 0}"#
             )
+            .unwrap()
             .unwrap(),
             MessageChunk::ContentBlockStop(ContentBlockStopChunk {
                 _type: MessageChunkType::ContentBlockStop,
                 index: 0,
+                tool_use: None,
+                incomplete_input: None,
             })
         );
 
         assert_eq!(
-            MessageChunk::parse(
+            parse(
                r#"event: message_delta
 data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_sequence": null}, "usage": {"output_tokens": 15}}"#
             )
+            .unwrap()
             .unwrap(),
             MessageChunk::MessageDelta(MessageDeltaChunk {
                 _type: MessageChunkType::MessageDelta,
@@ -1409,20 +1677,26 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
                 },
                 usage: DeltaUsage {
                     output_tokens: 15,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 },
             })
         );
 
         assert_eq!(
-            MessageChunk::parse(
+            parse(
                 r#"event: message_stop
 data: {"type": "message_stop"}"#
             )
+            .unwrap()
             .unwrap(),
             MessageChunk::MessageStop(MessageStopChunk::default())
         );
 
-        assert!(MessageChunk::parse("event: unknown\ndata: {}").is_err());
+        assert_eq!(
+            parse("event: unknown\ndata: {}").unwrap(),
+            None
+        );
     }
 
     #[test]
@@ -1495,4 +1769,149 @@ data: {"type": "message_stop"}"#
             TextDeltaContentBlock::new("text")
         );
     }
+
+    #[test]
+    fn deserialize_citations_delta() {
+        let content_block_delta = ContentBlockDeltaChunk::new(
+            0,
+            Citation::CharLocation(CharLocationCitation {
+                cited_text: "The grass is green.".to_string(),
+                document_title: Some("Example".to_string()),
+                end_char_index: 20,
+                ..Default::default()
+            })
+            .into(),
+        );
+        assert_eq!(
+            parse(
+                r#"event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "citations_delta", "citation": {"type": "char_location", "cited_text": "The grass is green.", "document_index": 0, "document_title": "Example", "start_char_index": 0, "end_char_index": 20}}}"#
+            )
+            .unwrap()
+            .unwrap(),
+            MessageChunk::ContentBlockDelta(content_block_delta)
+        );
+    }
+
+    #[test]
+    fn from_content_block_delta() {
+        assert_eq!(
+            ContentBlockDelta::from("text"),
+            ContentBlockDelta::TextDelta(TextDeltaContentBlock::new("text"))
+        );
+        assert_eq!(
+            ContentBlockDelta::default(),
+            ContentBlockDelta::TextDelta(TextDeltaContentBlock::default())
+        );
+    }
+
+    #[test]
+    fn parse_thinking_chunks() {
+        assert_eq!(
+            parse(
+                r#"event: content_block_start
+data: {"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": "", "signature": ""}}"#
+            )
+            .unwrap()
+            .unwrap(),
+            MessageChunk::ContentBlockStart(ContentBlockStartChunk::new(
+                0,
+                ThinkingContentBlock::default().into(),
+            ))
+        );
+
+        assert_eq!(
+            parse(
+                r#"event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Let me think."}}"#
+            )
+            .unwrap()
+            .unwrap(),
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                0,
+                ThinkingDeltaContentBlock::new("Let me think.").into(),
+            ))
+        );
+
+        assert_eq!(
+            parse(
+                r#"event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "signature"}}"#
+            )
+            .unwrap()
+            .unwrap(),
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                0,
+                SignatureDeltaContentBlock::new("signature").into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_unknown_chunks() {
+        assert_eq!(
+            parse(
+                r#"event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "new_delta", "value": 1}}"#
+            )
+            .unwrap()
+            .unwrap(),
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                0,
+                ContentBlockDelta::Unknown(serde_json::json!({
+                    "type": "new_delta",
+                    "value": 1,
+                })),
+            ))
+        );
+
+        assert_eq!(
+            parse(
+                r#"event: content_block_start
+data: {"type": "content_block_start", "index": 0, "content_block": {"type": "new_block"}}"#
+            )
+            .unwrap()
+            .unwrap(),
+            MessageChunk::ContentBlockStart(ContentBlockStartChunk::new(
+                0,
+                ContentBlock::Unknown(serde_json::json!({"type": "new_block"})),
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_error_chunk() {
+        let error = parse(
+            r#"event: error
+data: {"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}"#,
+        )
+        .unwrap_err();
+        match error {
+            | StreamError::Api(error) => {
+                assert_eq!(
+                    error._type,
+                    crate::ApiErrorType::OverloadedError
+                );
+                assert!(error._type.is_retryable());
+                assert_eq!(error.response.error.message, "Overloaded");
+            },
+            | _ => panic!("unexpected error"),
+        }
+
+        let error = parse(
+            r#"event: error
+data: {"type": "error", "error": {"type": "invalid_request_error", "message": "Invalid"}}"#,
+        )
+        .unwrap_err();
+        match error {
+            | StreamError::Api(error) => {
+                assert_eq!(
+                    error._type,
+                    crate::ApiErrorType::InvalidRequestError
+                );
+                assert!(!error._type.is_retryable());
+            },
+            | _ => panic!("unexpected error"),
+        }
+    }
 }
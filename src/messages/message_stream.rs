@@ -0,0 +1,769 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use crate::messages::{
+    Content, ContentBlock, ContentBlockDelta, MessageAccumulator,
+    MessageChunk, MessagesResponseBody, SseEvent, StreamError, ToolUse,
+};
+use crate::{ApiErrorBody, ApiErrorResponse};
+
+/// Extension methods for the stream of message chunks.
+///
+/// ## Example
+/// ```rust,no_run
+/// use clust::messages::{
+///     ClaudeModel, MaxTokens, Message, MessageStreamExt,
+///     MessagesRequestBody, StreamOption,
+/// };
+/// use clust::Client;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = Client::from_env()?;
+///     let request_body = MessagesRequestBody {
+///         model: ClaudeModel::Claude3Sonnet20240229,
+///         messages: vec![Message::user("Hello, Claude!")],
+///         max_tokens: MaxTokens::new(1024, ClaudeModel::Claude3Sonnet20240229)?,
+///         stream: Some(StreamOption::ReturnStream),
+///         ..Default::default()
+///     };
+///
+///     let message = client
+///         .create_a_message_stream(request_body)
+///         .await?
+///         .collect_message()
+///         .await?;
+///
+///     println!("Result:\n{}", message);
+///
+///     Ok(())
+/// }
+/// ```
+pub trait MessageStreamExt:
+    Stream<Item = Result<MessageChunk, StreamError>>
+{
+    /// Accumulates all chunks of the stream into the complete message.
+    ///
+    /// ## Errors
+    /// It returns the first error of the stream or the accumulation,
+    /// or `StreamError::InvalidChunkSequence` if the stream ends before `message_stop`.
+    fn collect_message(self) -> CollectMessage<Self>
+    where
+        Self: Sized,
+    {
+        CollectMessage {
+            stream: self,
+            accumulator: MessageAccumulator::new(),
+        }
+    }
+
+    /// Maps the stream into the stream of the text deltas.
+    ///
+    /// The chunks are accumulated, so the complete message is available by `TextStream::into_message` at the end.
+    ///
+    /// ## Errors
+    /// The stream yields the errors of the stream or the accumulation,
+    /// or `StreamError::InvalidChunkSequence` if the stream ends before `message_stop`.
+    fn text_stream(self) -> TextStream<Self>
+    where
+        Self: Sized,
+    {
+        TextStream {
+            stream: self,
+            accumulator: MessageAccumulator::new(),
+            is_terminated: false,
+        }
+    }
+
+    /// Handles the events of the stream by the handler and accumulates the chunks into the complete message.
+    ///
+    /// ## Arguments
+    /// - `handler` - The handler of the events.
+    ///
+    /// ## Errors
+    /// It returns the first error of the stream or the accumulation after `MessageStreamHandler::on_error`,
+    /// or `StreamError::InvalidChunkSequence` if the stream ends before `message_stop`.
+    fn handle<H>(
+        self,
+        handler: H,
+    ) -> HandleMessage<Self, H>
+    where
+        Self: Sized,
+        H: MessageStreamHandler,
+    {
+        HandleMessage {
+            stream: self,
+            accumulator: MessageAccumulator::new(),
+            handler,
+        }
+    }
+
+    /// Encodes the stream into the bytes of the server-sent events to relay to downstream clients.
+    ///
+    /// Each chunk is encoded as an event terminated by a blank line.
+    /// An error of the stream is encoded as an `error` event in the same shape as the API, and ends the stream.
+    fn sse_bytes(self) -> SseBytes<Self>
+    where
+        Self: Sized,
+    {
+        SseBytes {
+            stream: self,
+            is_terminated: false,
+        }
+    }
+}
+
+impl<S> MessageStreamExt for S where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + ?Sized
+{
+}
+
+/// The future of `MessageStreamExt::collect_message`.
+#[pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CollectMessage<S> {
+    #[pin]
+    stream: S,
+    accumulator: MessageAccumulator,
+}
+
+impl<S> Future for CollectMessage<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>>,
+{
+    type Output = Result<MessagesResponseBody, StreamError>;
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            match this
+                .stream
+                .as_mut()
+                .poll_next(cx)
+            {
+                | Poll::Ready(Some(Ok(chunk))) => {
+                    this.accumulator
+                        .accumulate(&chunk)?;
+                },
+                | Poll::Ready(Some(Err(error))) => {
+                    return Poll::Ready(Err(error));
+                },
+                | Poll::Ready(None) => {
+                    let accumulator = std::mem::take(this.accumulator);
+                    return if accumulator.is_complete() {
+                        Poll::Ready(Ok(accumulator.into_message()))
+                    } else {
+                        Poll::Ready(Err(StreamError::InvalidChunkSequence(
+                            "the stream ended before message_stop"
+                                .to_string(),
+                        )))
+                    };
+                },
+                | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// The stream of the text deltas of `MessageStreamExt::text_stream`.
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct TextStream<S> {
+    #[pin]
+    stream: S,
+    accumulator: MessageAccumulator,
+    is_terminated: bool,
+}
+
+impl<S> TextStream<S> {
+    /// The snapshot of the message accumulated so far, or `None` before `message_start`.
+    pub fn snapshot(&self) -> Option<&MessagesResponseBody> {
+        self.accumulator.snapshot()
+    }
+
+    /// Whether `message_stop` has arrived.
+    pub fn is_complete(&self) -> bool {
+        self.accumulator.is_complete()
+    }
+
+    /// Takes the message accumulated so far.
+    pub fn into_message(self) -> MessagesResponseBody {
+        self.accumulator.into_message()
+    }
+}
+
+impl<S> Stream for TextStream<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>>,
+{
+    type Item = Result<String, StreamError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.is_terminated {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match this
+                .stream
+                .as_mut()
+                .poll_next(cx)
+            {
+                | Poll::Ready(Some(Ok(chunk))) => {
+                    this.accumulator
+                        .accumulate(&chunk)?;
+                    if let MessageChunk::ContentBlockDelta(delta) = chunk {
+                        if let ContentBlockDelta::TextDelta(delta) =
+                            delta.delta
+                        {
+                            return Poll::Ready(Some(Ok(delta.text)));
+                        }
+                    }
+                },
+                | Poll::Ready(Some(Err(error))) => {
+                    return Poll::Ready(Some(Err(error)));
+                },
+                | Poll::Ready(None) => {
+                    *this.is_terminated = true;
+                    return if this.accumulator.is_complete() {
+                        Poll::Ready(None)
+                    } else {
+                        Poll::Ready(Some(Err(
+                            StreamError::InvalidChunkSequence(
+                                "the stream ended before message_stop"
+                                    .to_string(),
+                            ),
+                        )))
+                    };
+                },
+                | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// The stream of the bytes of the server-sent events of `MessageStreamExt::sse_bytes`.
+///
+/// It never fails, so it can be used as the body of an HTTP response.
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct SseBytes<S> {
+    #[pin]
+    stream: S,
+    is_terminated: bool,
+}
+
+impl<S> Stream for SseBytes<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>>,
+{
+    type Item = Result<Bytes, Infallible>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.is_terminated {
+            return Poll::Ready(None);
+        }
+
+        match this.stream.poll_next(cx) {
+            | Poll::Ready(Some(Ok(chunk))) => {
+                // The display of the chunk is the event and the data fields.
+                Poll::Ready(Some(Ok(Bytes::from(format!("{}\n\n", chunk)))))
+            },
+            | Poll::Ready(Some(Err(error))) => {
+                *this.is_terminated = true;
+                Poll::Ready(Some(Ok(Bytes::from(
+                    error_event(&error).encode(),
+                ))))
+            },
+            | Poll::Ready(None) => {
+                *this.is_terminated = true;
+                Poll::Ready(None)
+            },
+            | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// The `error` event of the error in the same shape as the API.
+fn error_event(error: &StreamError) -> SseEvent {
+    let response = match error {
+        | StreamError::Api(error) => error.response.clone(),
+        | error => ApiErrorResponse {
+            _type: "error".to_string(),
+            error: ApiErrorBody {
+                _type: "api_error".to_string(),
+                message: error.to_string(),
+            },
+        },
+    };
+
+    SseEvent::new(
+        "error",
+        serde_json::to_string(&response).unwrap_or_default(),
+    )
+}
+
+/// A handler of the events of a message stream for `MessageStreamExt::handle`.
+///
+/// All methods do nothing by default.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{MessageStreamHandler, MessagesResponseBody, ToolUse};
+///
+/// struct Printer;
+///
+/// impl MessageStreamHandler for Printer {
+///     fn on_text(&mut self, text: &str) {
+///         print!("{}", text);
+///     }
+///
+///     fn on_tool_use_complete(&mut self, tool_use: &ToolUse) {
+///         println!("\nTool use: {}", tool_use);
+///     }
+///
+///     fn on_message_complete(&mut self, message: &MessagesResponseBody) {
+///         println!("\nUsage: {}", message.usage);
+///     }
+/// }
+/// ```
+pub trait MessageStreamHandler {
+    /// Called at each text delta.
+    #[allow(unused_variables)]
+    fn on_text(
+        &mut self,
+        text: &str,
+    ) {
+    }
+
+    /// Called at the end of each tool use or server tool use block with the complete input.
+    #[allow(unused_variables)]
+    fn on_tool_use_complete(
+        &mut self,
+        tool_use: &ToolUse,
+    ) {
+    }
+
+    /// Called at `message_stop` with the complete message.
+    #[allow(unused_variables)]
+    fn on_message_complete(
+        &mut self,
+        message: &MessagesResponseBody,
+    ) {
+    }
+
+    /// Called at the error of the stream or the accumulation.
+    #[allow(unused_variables)]
+    fn on_error(
+        &mut self,
+        error: &StreamError,
+    ) {
+    }
+}
+
+impl<H> MessageStreamHandler for &mut H
+where
+    H: MessageStreamHandler + ?Sized,
+{
+    fn on_text(
+        &mut self,
+        text: &str,
+    ) {
+        (**self).on_text(text)
+    }
+
+    fn on_tool_use_complete(
+        &mut self,
+        tool_use: &ToolUse,
+    ) {
+        (**self).on_tool_use_complete(tool_use)
+    }
+
+    fn on_message_complete(
+        &mut self,
+        message: &MessagesResponseBody,
+    ) {
+        (**self).on_message_complete(message)
+    }
+
+    fn on_error(
+        &mut self,
+        error: &StreamError,
+    ) {
+        (**self).on_error(error)
+    }
+}
+
+/// The future of `MessageStreamExt::handle`.
+#[pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct HandleMessage<S, H> {
+    #[pin]
+    stream: S,
+    accumulator: MessageAccumulator,
+    handler: H,
+}
+
+impl<S, H> HandleMessage<S, H>
+where
+    H: MessageStreamHandler,
+{
+    fn handle_chunk(
+        accumulator: &mut MessageAccumulator,
+        handler: &mut H,
+        chunk: MessageChunk,
+    ) -> Result<(), StreamError> {
+        accumulator.accumulate(&chunk)?;
+
+        match chunk {
+            | MessageChunk::ContentBlockDelta(delta) => {
+                if let ContentBlockDelta::TextDelta(delta) = &delta.delta {
+                    handler.on_text(&delta.text);
+                }
+            },
+            | MessageChunk::ContentBlockStop(stop) => {
+                if let Some(tool_use) =
+                    completed_tool_use(accumulator, stop.index)
+                {
+                    handler.on_tool_use_complete(tool_use);
+                }
+            },
+            | MessageChunk::MessageStop(_) => {
+                if let Some(message) = accumulator.snapshot() {
+                    handler.on_message_complete(message);
+                }
+            },
+            | _ => {},
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, H> Future for HandleMessage<S, H>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>>,
+    H: MessageStreamHandler,
+{
+    type Output = Result<MessagesResponseBody, StreamError>;
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            let result = match this
+                .stream
+                .as_mut()
+                .poll_next(cx)
+            {
+                | Poll::Ready(Some(Ok(chunk))) => Self::handle_chunk(
+                    this.accumulator,
+                    this.handler,
+                    chunk,
+                ),
+                | Poll::Ready(Some(Err(error))) => Err(error),
+                | Poll::Ready(None) => {
+                    let accumulator = std::mem::take(this.accumulator);
+                    if accumulator.is_complete() {
+                        return Poll::Ready(Ok(accumulator.into_message()));
+                    }
+                    Err(StreamError::InvalidChunkSequence(
+                        "the stream ended before message_stop".to_string(),
+                    ))
+                },
+                | Poll::Pending => return Poll::Pending,
+            };
+
+            if let Err(error) = result {
+                this.handler.on_error(&error);
+                return Poll::Ready(Err(error));
+            }
+        }
+    }
+}
+
+/// The tool use of the completed block at the index, if any.
+fn completed_tool_use(
+    accumulator: &MessageAccumulator,
+    index: u32,
+) -> Option<&ToolUse> {
+    let message = accumulator.snapshot()?;
+    match &message.content {
+        | Content::MultipleBlocks(blocks) => {
+            match blocks.get(index as usize)? {
+                | ContentBlock::ToolUse(block) => Some(&block.tool_use),
+                | ContentBlock::ServerToolUse(block) => Some(&block.tool_use),
+                | _ => None,
+            }
+        },
+        | Content::SingleText(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::*;
+
+    #[tokio::test]
+    async fn collect_message() {
+        let chunks = vec![
+            MessageChunk::MessageStart(MessageStartChunk::new(
+                MessagesResponseBody::default(),
+            )),
+            MessageChunk::ContentBlockStart(ContentBlockStartChunk::new(
+                0,
+                TextContentBlock::new("").into(),
+            )),
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                0,
+                "Hello!".into(),
+            )),
+            MessageChunk::ContentBlockStop(ContentBlockStopChunk::new(0)),
+            MessageChunk::MessageStop(MessageStopChunk::new()),
+        ];
+
+        let message = futures_util::stream::iter(
+            chunks
+                .clone()
+                .into_iter()
+                .map(Ok),
+        )
+        .collect_message()
+        .await
+        .unwrap();
+        assert_eq!(
+            message,
+            MessagesResponseBody {
+                content: vec![TextContentBlock::new("Hello!").into()].into(),
+                ..Default::default()
+            }
+        );
+
+        let result = futures_util::stream::iter(
+            chunks
+                .into_iter()
+                .take(3)
+                .map(Ok),
+        )
+        .collect_message()
+        .await;
+        assert!(matches!(
+            result,
+            Err(StreamError::InvalidChunkSequence(_))
+        ));
+    }
+
+    fn tool_use_chunks() -> Vec<MessageChunk> {
+        vec![
+            MessageChunk::MessageStart(MessageStartChunk::new(
+                MessagesResponseBody::default(),
+            )),
+            MessageChunk::ContentBlockStart(ContentBlockStartChunk::new(
+                0,
+                TextContentBlock::new("").into(),
+            )),
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                0,
+                "Let me".into(),
+            )),
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                0,
+                " check.".into(),
+            )),
+            MessageChunk::ContentBlockStop(ContentBlockStopChunk::new(0)),
+            MessageChunk::ContentBlockStart(ContentBlockStartChunk::new(
+                1,
+                ToolUseContentBlock::new(ToolUse::new(
+                    "toolu_01",
+                    "get_weather",
+                    serde_json::json!({}),
+                ))
+                .into(),
+            )),
+            MessageChunk::ContentBlockDelta(ContentBlockDeltaChunk::new(
+                1,
+                ContentBlockDelta::InputJsonDelta(
+                    InputJsonDeltaContentBlock::new("{\"location\": \"Tokyo\"}"),
+                ),
+            )),
+            MessageChunk::ContentBlockStop(ContentBlockStopChunk::new(1)),
+            MessageChunk::MessageStop(MessageStopChunk::new()),
+        ]
+    }
+
+    #[tokio::test]
+    async fn sse_bytes() {
+        use futures_util::StreamExt;
+
+        let chunks = vec![
+            Ok(MessageChunk::Ping(PingChunk::new())),
+            Ok(MessageChunk::MessageStop(MessageStopChunk::new())),
+            Err(StreamError::IdleTimeout(std::time::Duration::from_secs(
+                1,
+            ))),
+            Ok(MessageChunk::Ping(PingChunk::new())),
+        ];
+
+        let bytes = futures_util::stream::iter(chunks)
+            .sse_bytes()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            "event: ping\ndata: {\"type\": \"ping\"}\n\nevent: message_stop\ndata: {\"type\": \"message_stop\"}\n\nevent: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"api_error\",\"message\":\"The stream has been idle for 1s\"}}\n\n"
+        );
+
+        // The bytes are decoded into the same chunks and the error.
+        let mut stream = StreamReplay::new(&bytes, RecordingFormat::Raw)
+            .unwrap()
+            .into_stream();
+        assert_eq!(
+            stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap(),
+            MessageChunk::Ping(PingChunk::new())
+        );
+        assert_eq!(
+            stream
+                .next()
+                .await
+                .unwrap()
+                .unwrap(),
+            MessageChunk::MessageStop(MessageStopChunk::new())
+        );
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(StreamError::Api(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn text_stream() {
+        use futures_util::StreamExt;
+
+        let mut stream =
+            futures_util::stream::iter(tool_use_chunks().into_iter().map(Ok))
+                .text_stream();
+
+        let mut texts = Vec::new();
+        while let Some(text) = stream.next().await {
+            texts.push(text.unwrap());
+        }
+        assert_eq!(texts, vec!["Let me", " check."]);
+        assert!(stream.is_complete());
+        assert_eq!(
+            stream
+                .into_message()
+                .content
+                .tool_uses()
+                .count(),
+            1
+        );
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        texts: Vec<String>,
+        tool_uses: Vec<ToolUse>,
+        messages: usize,
+        errors: usize,
+    }
+
+    impl MessageStreamHandler for Recorder {
+        fn on_text(
+            &mut self,
+            text: &str,
+        ) {
+            self.texts.push(text.to_string());
+        }
+
+        fn on_tool_use_complete(
+            &mut self,
+            tool_use: &ToolUse,
+        ) {
+            self.tool_uses.push(tool_use.clone());
+        }
+
+        fn on_message_complete(
+            &mut self,
+            _message: &MessagesResponseBody,
+        ) {
+            self.messages += 1;
+        }
+
+        fn on_error(
+            &mut self,
+            _error: &StreamError,
+        ) {
+            self.errors += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn handle() {
+        let mut recorder = Recorder::default();
+        let message =
+            futures_util::stream::iter(tool_use_chunks().into_iter().map(Ok))
+                .handle(&mut recorder)
+                .await
+                .unwrap();
+
+        assert_eq!(recorder.texts, vec!["Let me", " check."]);
+        assert_eq!(
+            recorder.tool_uses,
+            vec![ToolUse::new(
+                "toolu_01",
+                "get_weather",
+                serde_json::json!({"location": "Tokyo"}),
+            )]
+        );
+        assert_eq!(recorder.messages, 1);
+        assert_eq!(recorder.errors, 0);
+        assert_eq!(
+            message
+                .content
+                .concatenated_text(),
+            "Let me check."
+        );
+
+        let mut recorder = Recorder::default();
+        let result = futures_util::stream::iter(
+            tool_use_chunks()
+                .into_iter()
+                .skip(1)
+                .map(Ok),
+        )
+        .handle(&mut recorder)
+        .await;
+        assert!(result.is_err());
+        assert_eq!(recorder.errors, 1);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::macros::impl_display_for_serialize;
 use crate::messages::{
-    ClaudeModel, MaxTokens, Message, Metadata, StopSequence, StreamOption,
-    SystemPrompt, Temperature, ToolDefinition, TopK, TopP,
+    ClaudeModel, MaxTokens, Message, MessagesError, Metadata, StopSequence,
+    StreamOption, SystemPrompt, Temperature, Thinking, ToolChoice,
+    ToolDefinition, TopK, TopP,
 };
 use crate::ValidationError;
 
@@ -68,6 +69,11 @@ pub struct MessagesRequestBody {
     /// - input_schema: JSON schema for the tool input shape that the model will produce in tool_use output content blocks.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<ToolDefinition>>,
+    /// How the model should use the provided tools.
+    ///
+    /// The model can use a specific tool, any available tool, decide by itself, or not use tools at all.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub tool_choice: Option<ToolChoice>,
     /// Use nucleus sampling.
     ///
     /// In nucleus sampling, we compute the cumulative distribution over all the options for each subsequent token in decreasing probability order and cut it off once it reaches a particular probability specified by top_p. You should either alter temperature or top_p, but not both.
@@ -82,10 +88,46 @@ pub struct MessagesRequestBody {
     /// Recommended for advanced use cases only. You usually only need to use temperature.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_k: Option<TopK>,
+    /// Configuration for enabling Claude's extended thinking.
+    ///
+    /// When enabled, responses include thinking content blocks showing Claude's thinking process before the final answer. Requires a minimum budget of 1,024 tokens and counts towards your max_tokens limit.
+    ///
+    /// See [extended thinking](https://docs.anthropic.com/en/docs/build-with-claude/extended-thinking) for details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Thinking>,
 }
 
 impl_display_for_serialize!(MessagesRequestBody);
 
+impl MessagesRequestBody {
+    /// Validates the fields that depend on each other, which is also done before sending the request.
+    ///
+    /// ## Errors
+    /// - `MessagesError::InvalidToolChoice` if the tool choice specifies a tool that is not in the tools.
+    /// - `MessagesError::InvalidThinking` if the thinking budget is not less than the maximum number of tokens.
+    pub fn validate(&self) -> Result<(), MessagesError> {
+        if let Some(tool_choice) = &self.tool_choice {
+            tool_choice
+                .validate(
+                    self.tools
+                        .as_deref()
+                        .unwrap_or_default(),
+                )
+                .map_err(MessagesError::InvalidToolChoice)?;
+        }
+
+        if let Some(budget_tokens) = self
+            .thinking
+            .and_then(|thinking| thinking.budget_tokens())
+        {
+            Thinking::enabled(budget_tokens, self.max_tokens)
+                .map_err(MessagesError::InvalidThinking)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// A builder for the `MessagesRequestBody`.
 ///
 /// ## Example
@@ -226,6 +268,20 @@ impl MessagesRequestBuilder {
         self
     }
 
+    /// Sets the tool choice.
+    ///
+    /// The tool of the choice is validated against the tools by [`MessagesRequestBody::validate`] before sending the request.
+    ///
+    /// ## Arguments
+    /// - `tool_choice` - How the model should use the provided tools.
+    pub fn tool_choice(
+        mut self,
+        tool_choice: ToolChoice,
+    ) -> Self {
+        self.request_body.tool_choice = Some(tool_choice);
+        self
+    }
+
     /// Sets the top p.
     pub fn top_p(
         mut self,
@@ -244,6 +300,25 @@ impl MessagesRequestBuilder {
         self
     }
 
+    /// Enables the extended thinking with the budget tokens.
+    ///
+    /// ## Arguments
+    /// - `budget_tokens` - The number of tokens that the model can use for the thinking.
+    ///
+    /// ## Errors
+    /// It returns a validation error if the budget is less than 1,024 or not less than the current maximum number of tokens of the request.
+    /// The budget is validated again against the final maximum number of tokens by [`MessagesRequestBody::validate`] before sending the request.
+    pub fn thinking(
+        mut self,
+        budget_tokens: u32,
+    ) -> Result<Self, ValidationError<u32>> {
+        self.request_body.thinking = Some(Thinking::enabled(
+            budget_tokens,
+            self.request_body.max_tokens,
+        )?);
+        Ok(self)
+    }
+
     /// Builds the MessagesRequestBody.
     pub fn build(self) -> MessagesRequestBody {
         self.request_body
@@ -341,8 +416,10 @@ mod tests {
             stream: Some(StreamOption::ReturnOnce),
             temperature: Some(Temperature::new(0.5).unwrap()),
             tools: None,
+            tool_choice: None,
             top_p: Some(TopP::new(0.5).unwrap()),
             top_k: Some(TopK::new(50)),
+            thinking: None,
         };
         assert_eq!(
             serde_json::to_string(&messages_request_body).unwrap(),
@@ -373,8 +450,10 @@ mod tests {
             stream: Some(StreamOption::ReturnOnce),
             temperature: Some(Temperature::new(0.5).unwrap()),
             tools: None,
+            tool_choice: None,
             top_p: Some(TopP::new(0.5).unwrap()),
             top_k: Some(TopK::new(50)),
+            thinking: None,
         };
         assert_eq!(
             serde_json::from_str::<MessagesRequestBody>("{\"model\":\"claude-3-sonnet-20240229\",\"messages\":[],\"system\":\"system-prompt\",\"max_tokens\":16,\"metadata\":{\"user_id\":\"metadata\"},\"stop_sequences\":[\"stop-sequence\"],\"stream\":false,\"temperature\":0.5,\"top_p\":0.5,\"top_k\":50}").unwrap(),
@@ -400,11 +479,11 @@ mod tests {
                 )])
                 .stream(StreamOption::ReturnOnce)
                 .temperature(Temperature::new(0.5).unwrap())
-                .tools(vec![ToolDefinition {
-                    name: "tool".into(),
-                    description: Some("tool description".into()),
-                    input_schema: serde_json::Value::Null,
-                }])
+                .tools(vec![ToolDefinition::new(
+                    "tool",
+                    Some("tool description"),
+                    serde_json::Value::Null,
+                )])
                 .top_p(TopP::new(0.5).unwrap())
                 .top_k(TopK::new(50))
                 .build();
@@ -444,11 +523,11 @@ mod tests {
         );
         assert_eq!(
             messages_request_body.tools,
-            Some(vec![ToolDefinition {
-                name: "tool".into(),
-                description: Some("tool description".into()),
-                input_schema: serde_json::Value::Null,
-            }])
+            Some(vec![ToolDefinition::new(
+                "tool",
+                Some("tool description"),
+                serde_json::Value::Null,
+            )])
         );
         assert_eq!(
             messages_request_body.top_p,
@@ -478,11 +557,11 @@ mod tests {
             )])
             .stream(StreamOption::ReturnOnce)
             .temperature(Temperature::new(0.5).unwrap())
-            .tools(vec![ToolDefinition {
-                name: "tool".into(),
-                description: Some("tool description".into()),
-                input_schema: serde_json::Value::Null,
-            }])
+            .tools(vec![ToolDefinition::new(
+                "tool",
+                Some("tool description"),
+                serde_json::Value::Null,
+            )])
             .top_p(TopP::new(0.5).unwrap())
             .top_k(TopK::new(50))
             .build();
@@ -522,11 +601,11 @@ mod tests {
         );
         assert_eq!(
             messages_request_body.tools,
-            Some(vec![ToolDefinition {
-                name: "tool".into(),
-                description: Some("tool description".into()),
-                input_schema: serde_json::Value::Null,
-            }])
+            Some(vec![ToolDefinition::new(
+                "tool",
+                Some("tool description"),
+                serde_json::Value::Null,
+            )])
         );
         assert_eq!(
             messages_request_body.top_p,
@@ -537,4 +616,135 @@ mod tests {
             Some(TopK::new(50))
         );
     }
+
+    #[test]
+    fn builder_with_thinking() {
+        let request_body =
+            MessagesRequestBuilder::new(ClaudeModel::Claude35Sonnet20240620)
+                .thinking(2048)
+                .unwrap()
+                .build();
+        assert_eq!(
+            request_body.thinking,
+            Some(Thinking::enabled(2048, MaxTokens::default()).unwrap())
+        );
+        assert_eq!(
+            serde_json::to_value(&request_body).unwrap()["thinking"],
+            serde_json::json!({"type": "enabled", "budget_tokens": 2048})
+        );
+
+        assert!(MessagesRequestBuilder::new_with_max_tokens(
+            ClaudeModel::Claude35Sonnet20240620,
+            2048
+        )
+        .unwrap()
+        .thinking(2048)
+        .is_err());
+    }
+
+    #[test]
+    fn validate_thinking() {
+        assert!(MessagesRequestBuilder::new(
+            ClaudeModel::Claude35Sonnet20240620
+        )
+        .thinking(2048)
+        .unwrap()
+        .build()
+        .validate()
+        .is_ok());
+
+        // The maximum number of tokens set after the thinking is also validated.
+        assert!(matches!(
+            MessagesRequestBuilder::new(ClaudeModel::Claude35Sonnet20240620)
+                .thinking(2048)
+                .unwrap()
+                .max_tokens(
+                    MaxTokens::new(2048, ClaudeModel::Claude35Sonnet20240620)
+                        .unwrap()
+                )
+                .build()
+                .validate(),
+            Err(MessagesError::InvalidThinking(_))
+        ));
+
+        assert!(MessagesRequestBody {
+            thinking: Some(Thinking::disabled()),
+            ..Default::default()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn builder_with_tool_choice() {
+        let tools = vec![ToolDefinition::new(
+            "tool",
+            Some("tool description"),
+            serde_json::Value::Null,
+        )];
+
+        let request_body =
+            MessagesRequestBuilder::new(ClaudeModel::Claude35Sonnet20240620)
+                .tools(tools.clone())
+                .tool_choice(ToolChoice::tool("tool"))
+                .build();
+        assert_eq!(
+            request_body.tool_choice,
+            Some(ToolChoice::tool("tool"))
+        );
+        assert_eq!(
+            serde_json::to_value(&request_body).unwrap()["tool_choice"],
+            serde_json::json!({"type": "tool", "name": "tool"})
+        );
+
+        assert!(request_body.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_tool_choice() {
+        let tools = vec![ToolDefinition::new(
+            "tool",
+            Some("tool description"),
+            serde_json::Value::Null,
+        )];
+
+        // The order of the tools and the tool choice does not matter.
+        assert!(MessagesRequestBuilder::new(
+            ClaudeModel::Claude35Sonnet20240620
+        )
+        .tool_choice(ToolChoice::tool("tool"))
+        .tools(tools.clone())
+        .build()
+        .validate()
+        .is_ok());
+        assert!(matches!(
+            MessagesRequestBuilder::new(ClaudeModel::Claude35Sonnet20240620)
+                .tool_choice(ToolChoice::tool("unknown"))
+                .tools(tools.clone())
+                .build()
+                .validate(),
+            Err(MessagesError::InvalidToolChoice(_))
+        ));
+        assert!(matches!(
+            MessagesRequestBuilder::new(ClaudeModel::Claude35Sonnet20240620)
+                .tool_choice(ToolChoice::tool("tool"))
+                .build()
+                .validate(),
+            Err(MessagesError::InvalidToolChoice(_))
+        ));
+        assert!(MessagesRequestBuilder::new(
+            ClaudeModel::Claude35Sonnet20240620
+        )
+        .tool_choice(ToolChoice::none())
+        .build()
+        .validate()
+        .is_ok());
+
+        // The fields set directly are also validated.
+        let request_body = MessagesRequestBody {
+            tool_choice: Some(ToolChoice::tool("tool")),
+            ..Default::default()
+        };
+        assert!(request_body.validate().is_err());
+    }
 }
@@ -85,25 +85,20 @@ impl MessagesResponseBody {
 }
 
 /// The object type for message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MessageObjectType {
     /// message
+    #[default]
     Message,
 }
 
-impl Default for MessageObjectType {
-    fn default() -> Self {
-        Self::Message
-    }
-}
-
 impl Display for MessageObjectType {
     fn fmt(
         &self,
         f: &mut Formatter<'_>,
     ) -> std::fmt::Result {
         match self {
-            | MessageObjectType::Message => write!(f, "{}", "message"),
+            | MessageObjectType::Message => write!(f, "message"),
         }
     }
 }
@@ -132,6 +127,8 @@ mod tests {
             usage: Usage {
                 input_tokens: 1,
                 output_tokens: 2,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         };
         assert_eq!(
@@ -153,6 +150,8 @@ mod tests {
             usage: Usage {
                 input_tokens: 1,
                 output_tokens: 2,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         };
         assert_eq!(
@@ -176,6 +175,8 @@ mod tests {
             usage: Usage {
                 input_tokens: 1,
                 output_tokens: 2,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         };
         assert_eq!(
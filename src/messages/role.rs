@@ -2,20 +2,15 @@ use crate::macros::impl_enum_string_serialization;
 use std::fmt::Display;
 
 /// The role of the message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Role {
     /// The user role.
+    #[default]
     User,
     /// The assistant role.
     Assistant,
 }
 
-impl Default for Role {
-    fn default() -> Self {
-        Self::User
-    }
-}
-
 impl Display for Role {
     fn fmt(
         &self,
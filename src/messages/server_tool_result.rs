@@ -0,0 +1,345 @@
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+    impl_enum_struct_serialization,
+};
+use std::fmt::Display;
+
+/// The content of a web search tool result: the search results or an error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebSearchToolResultContent {
+    /// The search results.
+    Results(Vec<WebSearchResult>),
+    /// The error of the web search.
+    Error(WebSearchToolResultError),
+}
+
+impl Default for WebSearchToolResultContent {
+    fn default() -> Self {
+        Self::Results(Vec::new())
+    }
+}
+
+impl From<Vec<WebSearchResult>> for WebSearchToolResultContent {
+    fn from(results: Vec<WebSearchResult>) -> Self {
+        Self::Results(results)
+    }
+}
+
+impl From<WebSearchToolResultError> for WebSearchToolResultContent {
+    fn from(error: WebSearchToolResultError) -> Self {
+        Self::Error(error)
+    }
+}
+
+impl serde::Serialize for WebSearchToolResultContent {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            | WebSearchToolResultContent::Results(results) => {
+                results.serialize(serializer)
+            },
+            | WebSearchToolResultContent::Error(error) => {
+                error.serialize(serializer)
+            },
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WebSearchToolResultContent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        if value.is_array() {
+            serde_json::from_value(value)
+                .map(WebSearchToolResultContent::Results)
+                .map_err(serde::de::Error::custom)
+        } else {
+            serde_json::from_value(value)
+                .map(WebSearchToolResultContent::Error)
+                .map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+impl_display_for_serialize!(WebSearchToolResultContent);
+
+/// A result of the web search.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WebSearchResult {
+    /// The result type. It is always `web_search_result`.
+    #[serde(rename = "type")]
+    pub _type: ServerToolResultType,
+    /// The URL of the source page.
+    pub url: String,
+    /// The title of the source page.
+    pub title: String,
+    /// The encrypted content that must be passed back in the multi-turn conversation for citations.
+    pub encrypted_content: String,
+    /// When the site was last updated.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub page_age: Option<String>,
+}
+
+impl Default for WebSearchResult {
+    fn default() -> Self {
+        Self {
+            _type: ServerToolResultType::WebSearchResult,
+            url: String::new(),
+            title: String::new(),
+            encrypted_content: String::new(),
+            page_age: None,
+        }
+    }
+}
+
+impl_display_for_serialize!(WebSearchResult);
+
+/// An error of the web search, e.g. `max_uses_exceeded`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WebSearchToolResultError {
+    /// The result type. It is always `web_search_tool_result_error`.
+    #[serde(rename = "type")]
+    pub _type: ServerToolResultType,
+    /// The error code.
+    pub error_code: String,
+}
+
+impl Default for WebSearchToolResultError {
+    fn default() -> Self {
+        Self {
+            _type: ServerToolResultType::WebSearchToolResultError,
+            error_code: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(WebSearchToolResultError);
+
+/// The content of a code execution tool result: the execution result or an error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodeExecutionToolResultContent {
+    /// The result of the execution.
+    Result(CodeExecutionResult),
+    /// The error of the code execution tool.
+    Error(CodeExecutionToolResultError),
+}
+
+impl Default for CodeExecutionToolResultContent {
+    fn default() -> Self {
+        Self::Result(CodeExecutionResult::default())
+    }
+}
+
+impl_enum_struct_serialization!(
+    CodeExecutionToolResultContent,
+    type,
+    Result(CodeExecutionResult, "code_execution_result"),
+    Error(
+        CodeExecutionToolResultError,
+        "code_execution_tool_result_error"
+    )
+);
+
+impl_display_for_serialize!(CodeExecutionToolResultContent);
+
+/// A result of the code execution.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CodeExecutionResult {
+    /// The result type. It is always `code_execution_result`.
+    #[serde(rename = "type")]
+    pub _type: ServerToolResultType,
+    /// The standard output of the execution.
+    pub stdout: String,
+    /// The standard error of the execution.
+    pub stderr: String,
+    /// The exit code of the execution.
+    pub return_code: i32,
+    /// The files created by the execution.
+    #[serde(default)]
+    pub content: Vec<CodeExecutionOutput>,
+}
+
+impl Default for CodeExecutionResult {
+    fn default() -> Self {
+        Self {
+            _type: ServerToolResultType::CodeExecutionResult,
+            stdout: String::new(),
+            stderr: String::new(),
+            return_code: 0,
+            content: Vec::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(CodeExecutionResult);
+
+/// A file created by the code execution.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CodeExecutionOutput {
+    /// The result type. It is always `code_execution_output`.
+    #[serde(rename = "type")]
+    pub _type: ServerToolResultType,
+    /// The ID of the file.
+    pub file_id: String,
+}
+
+impl Default for CodeExecutionOutput {
+    fn default() -> Self {
+        Self {
+            _type: ServerToolResultType::CodeExecutionOutput,
+            file_id: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(CodeExecutionOutput);
+
+/// An error of the code execution tool, e.g. `execution_time_exceeded`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CodeExecutionToolResultError {
+    /// The result type. It is always `code_execution_tool_result_error`.
+    #[serde(rename = "type")]
+    pub _type: ServerToolResultType,
+    /// The error code.
+    pub error_code: String,
+}
+
+impl Default for CodeExecutionToolResultError {
+    fn default() -> Self {
+        Self {
+            _type: ServerToolResultType::CodeExecutionToolResultError,
+            error_code: String::new(),
+        }
+    }
+}
+
+impl_display_for_serialize!(CodeExecutionToolResultError);
+
+/// The type of the items in the server tool results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ServerToolResultType {
+    /// web_search_result
+    #[default]
+    WebSearchResult,
+    /// web_search_tool_result_error
+    WebSearchToolResultError,
+    /// code_execution_result
+    CodeExecutionResult,
+    /// code_execution_output
+    CodeExecutionOutput,
+    /// code_execution_tool_result_error
+    CodeExecutionToolResultError,
+}
+
+impl Display for ServerToolResultType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | ServerToolResultType::WebSearchResult => {
+                write!(f, "web_search_result")
+            },
+            | ServerToolResultType::WebSearchToolResultError => {
+                write!(f, "web_search_tool_result_error")
+            },
+            | ServerToolResultType::CodeExecutionResult => {
+                write!(f, "code_execution_result")
+            },
+            | ServerToolResultType::CodeExecutionOutput => {
+                write!(f, "code_execution_output")
+            },
+            | ServerToolResultType::CodeExecutionToolResultError => {
+                write!(f, "code_execution_tool_result_error")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    ServerToolResultType,
+    WebSearchResult => "web_search_result",
+    WebSearchToolResultError => "web_search_tool_result_error",
+    CodeExecutionResult => "code_execution_result",
+    CodeExecutionOutput => "code_execution_output",
+    CodeExecutionToolResultError => "code_execution_tool_result_error"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_web_search_tool_result_content() {
+        assert_eq!(
+            serde_json::from_str::<WebSearchToolResultContent>(
+                "[{\"type\":\"web_search_result\",\"url\":\"https://example.com\",\"title\":\"Example\",\"encrypted_content\":\"abc\",\"page_age\":\"April 30, 2025\"}]"
+            )
+            .unwrap(),
+            WebSearchToolResultContent::Results(vec![WebSearchResult {
+                url: "https://example.com".to_string(),
+                title: "Example".to_string(),
+                encrypted_content: "abc".to_string(),
+                page_age: Some("April 30, 2025".to_string()),
+                ..Default::default()
+            }])
+        );
+        assert_eq!(
+            serde_json::from_str::<WebSearchToolResultContent>(
+                "{\"type\":\"web_search_tool_result_error\",\"error_code\":\"max_uses_exceeded\"}"
+            )
+            .unwrap(),
+            WebSearchToolResultContent::Error(WebSearchToolResultError {
+                error_code: "max_uses_exceeded".to_string(),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_web_search_tool_result_content() {
+        assert_eq!(
+            serde_json::to_string(&WebSearchToolResultContent::default())
+                .unwrap(),
+            "[]"
+        );
+    }
+
+    #[test]
+    fn deserialize_code_execution_tool_result_content() {
+        assert_eq!(
+            serde_json::from_str::<CodeExecutionToolResultContent>(
+                "{\"type\":\"code_execution_result\",\"stdout\":\"4\\n\",\"stderr\":\"\",\"return_code\":0,\"content\":[{\"type\":\"code_execution_output\",\"file_id\":\"file_1\"}]}"
+            )
+            .unwrap(),
+            CodeExecutionToolResultContent::Result(CodeExecutionResult {
+                stdout: "4\n".to_string(),
+                content: vec![CodeExecutionOutput {
+                    file_id: "file_1".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            serde_json::from_str::<CodeExecutionToolResultContent>(
+                "{\"type\":\"code_execution_tool_result_error\",\"error_code\":\"unavailable\"}"
+            )
+            .unwrap(),
+            CodeExecutionToolResultContent::Error(
+                CodeExecutionToolResultError {
+                    error_code: "unavailable".to_string(),
+                    ..Default::default()
+                }
+            )
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bytes::{Buf, Bytes, BytesMut};
+
+use crate::messages::StreamError;
+
+/// An event of the server-sent events.
+///
+/// See [the event stream interpretation](https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SseEvent {
+    /// The type of the event. It is `message` if the `event` field is not specified.
+    pub event: String,
+    /// The data of the event. The multiple `data` fields are joined with `\n`.
+    pub data: String,
+    /// The last event ID, if any.
+    pub id: Option<String>,
+}
+
+impl SseEvent {
+    /// Creates a new event.
+    pub fn new<S, T>(
+        event: S,
+        data: T,
+    ) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            event: event.into(),
+            data: data.into(),
+            id: None,
+        }
+    }
+
+    /// Encodes the event into the event stream format terminated by a blank line.
+    ///
+    /// The data of multiple lines is split into multiple `data` fields.
+    pub fn encode(&self) -> String {
+        let mut encoded = String::with_capacity(self.data.len() + 32);
+
+        if !self.event.is_empty() && self.event != "message" {
+            encoded.push_str("event: ");
+            encoded.push_str(&self.event);
+            encoded.push('\n');
+        }
+        if let Some(id) = &self.id {
+            encoded.push_str("id: ");
+            encoded.push_str(id);
+            encoded.push('\n');
+        }
+        for line in self
+            .data
+            .replace("\r\n", "\n")
+            .split(['\r', '\n'])
+        {
+            encoded.push_str("data: ");
+            encoded.push_str(line);
+            encoded.push('\n');
+        }
+        encoded.push('\n');
+
+        encoded
+    }
+}
+
+/// A decoder of the server-sent events that follows the WHATWG event stream rules.
+///
+/// - The lines can end with `\r\n`, `\n` or `\r`, even across the fed bytes.
+/// - The comment lines starting with `:` are ignored.
+/// - The space after the colon of a field is optional.
+/// - The multiple `data` fields of an event are joined with `\n`.
+/// - The `id` and `retry` fields are kept over the events.
+/// - The incomplete event at the end of the stream is discarded.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::SseDecoder;
+///
+/// let mut decoder = SseDecoder::new();
+/// decoder
+///     .feed(b": keep-alive\r\nevent:ping\r\ndata: {\"type\":")
+///     .unwrap();
+/// assert_eq!(decoder.next_event(), None);
+///
+/// decoder
+///     .feed(b" \"ping\"}\r\n\r\n")
+///     .unwrap();
+/// let event = decoder.next_event().unwrap();
+/// assert_eq!(event.event, "ping");
+/// assert_eq!(event.data, "{\"type\": \"ping\"}");
+/// ```
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: BytesMut,
+    scanned: usize,
+    bom_checked: bool,
+    after_carriage_return: bool,
+    event_type: String,
+    data: String,
+    last_event_id: String,
+    retry: Option<Duration>,
+    events: VecDeque<SseEvent>,
+}
+
+impl SseDecoder {
+    /// Creates a new decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the bytes of the stream.
+    ///
+    /// ## Errors
+    /// It returns `StreamError::StringDecodingError` if a line is not valid UTF-8.
+    /// The line is discarded, and the rest of the fed bytes are decoded by the next call.
+    pub fn feed(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(), StreamError> {
+        self.buffer
+            .extend_from_slice(bytes);
+
+        if !self.bom_checked {
+            if self.buffer.len() < UTF8_BOM.len()
+                && UTF8_BOM.starts_with(&self.buffer)
+            {
+                return Ok(());
+            }
+            if self
+                .buffer
+                .starts_with(UTF8_BOM)
+            {
+                self.buffer
+                    .advance(UTF8_BOM.len());
+            }
+            self.bom_checked = true;
+        }
+
+        while let Some(line) = self.next_line() {
+            // The line terminators are ASCII, so a line never splits a multi-byte character.
+            let Ok(text) = std::str::from_utf8(&line) else {
+                // Copy the line only to build the error of the invalid bytes.
+                return String::from_utf8(line.to_vec())
+                    .map(|_| ())
+                    .map_err(StreamError::StringDecodingError);
+            };
+            self.process_line(text);
+        }
+
+        Ok(())
+    }
+
+    /// Takes the next dispatched event, if any.
+    pub fn next_event(&mut self) -> Option<SseEvent> {
+        self.events.pop_front()
+    }
+
+    /// The last event ID.
+    pub fn last_event_id(&self) -> &str {
+        &self.last_event_id
+    }
+
+    /// The reconnection time specified by the `retry` field.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    fn next_line(&mut self) -> Option<Bytes> {
+        // Skip the line feed of `\r\n` split across the fed bytes.
+        if self.after_carriage_return && !self.buffer.is_empty() {
+            if self.buffer[0] == b'\n' {
+                self.buffer.advance(1);
+            }
+            self.after_carriage_return = false;
+        }
+
+        // Resume the scan from the bytes not scanned yet.
+        let end = match self.buffer[self.scanned..]
+            .iter()
+            .position(|byte| *byte == b'\n' || *byte == b'\r')
+        {
+            | Some(position) => self.scanned + position,
+            | None => {
+                self.scanned = self.buffer.len();
+                return None;
+            },
+        };
+        self.scanned = 0;
+
+        let line = self
+            .buffer
+            .split_to(end)
+            .freeze();
+        let terminator = self.buffer[0];
+        self.buffer.advance(1);
+
+        if terminator == b'\r' {
+            match self.buffer.first() {
+                | Some(b'\n') => self.buffer.advance(1),
+                | Some(_) => {},
+                | None => self.after_carriage_return = true,
+            }
+        }
+
+        Some(line)
+    }
+
+    fn process_line(
+        &mut self,
+        line: &str,
+    ) {
+        if line.is_empty() {
+            self.dispatch();
+            return;
+        }
+
+        if line.starts_with(':') {
+            return;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            | Some((field, value)) => (
+                field,
+                value
+                    .strip_prefix(' ')
+                    .unwrap_or(value),
+            ),
+            | None => (line, ""),
+        };
+
+        match field {
+            | "event" => {
+                self.event_type = value.to_string();
+            },
+            | "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            },
+            | "id" => {
+                if !value.contains('\0') {
+                    self.last_event_id = value.to_string();
+                }
+            },
+            | "retry" => {
+                if !value.is_empty()
+                    && value
+                        .bytes()
+                        .all(|byte| byte.is_ascii_digit())
+                {
+                    if let Ok(milliseconds) = value.parse() {
+                        self.retry = Some(Duration::from_millis(milliseconds));
+                    }
+                }
+            },
+            | _ => {},
+        }
+    }
+
+    fn dispatch(&mut self) {
+        let event_type = std::mem::take(&mut self.event_type);
+        if self.data.is_empty() {
+            return;
+        }
+
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+
+        self.events.push_back(SseEvent {
+            event: if event_type.is_empty() {
+                "message".to_string()
+            } else {
+                event_type
+            },
+            data,
+            id: if self.last_event_id.is_empty() {
+                None
+            } else {
+                Some(self.last_event_id.clone())
+            },
+        });
+    }
+}
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(source: &[u8]) -> Vec<SseEvent> {
+        let mut decoder = SseDecoder::new();
+        decoder.feed(source).unwrap();
+        std::iter::from_fn(|| decoder.next_event()).collect()
+    }
+
+    fn event(
+        event: &str,
+        data: &str,
+    ) -> SseEvent {
+        SseEvent {
+            event: event.to_string(),
+            data: data.to_string(),
+            id: None,
+        }
+    }
+
+    #[test]
+    fn decode_line_endings() {
+        let expected = vec![
+            event("ping", "{}"),
+            event("message_stop", "{}"),
+        ];
+        assert_eq!(
+            decode(b"event: ping\ndata: {}\n\nevent: message_stop\ndata: {}\n\n"),
+            expected
+        );
+        assert_eq!(
+            decode(b"event: ping\r\ndata: {}\r\n\r\nevent: message_stop\r\ndata: {}\r\n\r\n"),
+            expected
+        );
+        assert_eq!(
+            decode(b"event: ping\rdata: {}\r\revent: message_stop\rdata: {}\r\r"),
+            expected
+        );
+    }
+
+    #[test]
+    fn decode_split_bytes() {
+        let source = "\u{FEFF}event: ping\r\ndata: {\"text\": \"こんにちは\"}\r\n\r\n";
+        for size in 1..source.len() {
+            let mut decoder = SseDecoder::new();
+            let mut events = Vec::new();
+            for bytes in source.as_bytes().chunks(size) {
+                decoder.feed(bytes).unwrap();
+                events.extend(std::iter::from_fn(|| decoder.next_event()));
+            }
+            assert_eq!(
+                events,
+                vec![event("ping", "{\"text\": \"こんにちは\"}")],
+                "chunk size: {}",
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn decode_fields() {
+        assert_eq!(
+            decode(b": keep-alive\n\nevent:ping\ndata:{}\n\n"),
+            vec![event("ping", "{}")]
+        );
+        assert_eq!(
+            decode(b"data: first\ndata\ndata:  third\n\n"),
+            vec![event("message", "first\n\n third")]
+        );
+        assert_eq!(
+            decode(b"event: ping\n\nunknown: field\ndata: {}\n\n"),
+            vec![event("message", "{}")]
+        );
+    }
+
+    #[test]
+    fn decode_id_and_retry() {
+        let mut decoder = SseDecoder::new();
+        decoder
+            .feed(b"id: 1\nretry: 3000\ndata: a\n\ndata: b\n\nid\nretry: x\ndata: c\n\n")
+            .unwrap();
+        assert_eq!(
+            std::iter::from_fn(|| decoder.next_event())
+                .map(|event| event.id)
+                .collect::<Vec<_>>(),
+            vec![Some("1".to_string()), Some("1".to_string()), None]
+        );
+        assert_eq!(decoder.last_event_id(), "");
+        assert_eq!(
+            decoder.retry(),
+            Some(Duration::from_millis(3000))
+        );
+    }
+
+    #[test]
+    fn encode() {
+        assert_eq!(
+            SseEvent::new("ping", "{}").encode(),
+            "event: ping\ndata: {}\n\n"
+        );
+        assert_eq!(
+            SseEvent::new("message", "").encode(),
+            "data: \n\n"
+        );
+
+        let event = SseEvent {
+            id: Some("1".to_string()),
+            ..SseEvent::new("delta", "first\r\nsecond\rthird\n")
+        };
+        assert_eq!(
+            event.encode(),
+            "event: delta\nid: 1\ndata: first\ndata: second\ndata: third\ndata: \n\n"
+        );
+        assert_eq!(
+            decode(event.encode().as_bytes()),
+            vec![SseEvent {
+                data: "first\nsecond\nthird\n".to_string(),
+                ..event
+            }]
+        );
+    }
+
+    #[test]
+    fn discard_incomplete_event() {
+        assert_eq!(decode(b"event: ping\ndata: {}\n"), vec![]);
+        assert_eq!(decode(b"event: ping\ndata: {}"), vec![]);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut decoder = SseDecoder::new();
+        assert!(matches!(
+            decoder.feed(b"event: ping\ndata: \xFF\xFE\n\ndata: {}\n\n"),
+            Err(StreamError::StringDecodingError(_))
+        ));
+        assert_eq!(decoder.next_event(), None);
+
+        // The rest of the fed bytes are decoded by the next call.
+        decoder.feed(b"").unwrap();
+        assert_eq!(
+            decoder.next_event(),
+            Some(event("message", "{}"))
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use axum::body::Body;
+use axum::http::{header, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use futures_core::Stream;
+
+use crate::messages::{MessageChunk, MessageStreamExt, StreamError};
+
+/// The `axum` response that relays a message stream to the client as the server-sent events.
+///
+/// The chunks are encoded by `MessageStreamExt::sse_bytes`, so an error of the stream is sent as an `error` event.
+///
+/// ## Example
+/// ```rust,no_run
+/// use axum::http::StatusCode;
+/// use axum::response::{IntoResponse, Response};
+/// use axum::routing::post;
+/// use axum::Router;
+/// use clust::messages::{
+///     ClaudeModel, MaxTokens, Message, MessagesRequestBody, SseResponse,
+///     StreamOption,
+/// };
+/// use clust::Client;
+///
+/// async fn relay() -> Response {
+///     let client = Client::from_env().unwrap();
+///     let model = ClaudeModel::Claude3Sonnet20240229;
+///     let request_body = MessagesRequestBody {
+///         model,
+///         messages: vec![Message::user("Hello, Claude!")],
+///         max_tokens: MaxTokens::new(1024, model).unwrap(),
+///         stream: Some(StreamOption::ReturnStream),
+///         ..Default::default()
+///     };
+///
+///     match client
+///         .create_a_message_stream(request_body)
+///         .await
+///     {
+///         | Ok(stream) => SseResponse::new(stream).into_response(),
+///         | Err(error) => {
+///             (StatusCode::BAD_GATEWAY, error.to_string()).into_response()
+///         },
+///     }
+/// }
+///
+/// let app: Router = Router::new().route("/messages", post(relay));
+/// ```
+pub struct SseResponse<S> {
+    stream: S,
+}
+
+impl<S> SseResponse<S> {
+    /// Creates a new response of the message stream.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+        }
+    }
+}
+
+impl<S> IntoResponse for SseResponse<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + Send + 'static,
+{
+    fn into_response(self) -> Response {
+        let mut response =
+            Response::new(Body::from_stream(self.stream.sse_bytes()));
+
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("no-cache"),
+        );
+        // Disable the response buffering of the reverse proxies, e.g. nginx.
+        headers.insert(
+            "x-accel-buffering",
+            HeaderValue::from_static("no"),
+        );
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{MessageStopChunk, PingChunk};
+
+    #[tokio::test]
+    async fn into_response() {
+        let chunks = vec![
+            Ok(MessageChunk::Ping(PingChunk::new())),
+            Ok(MessageChunk::MessageStop(MessageStopChunk::new())),
+        ];
+
+        let response =
+            SseResponse::new(futures_util::stream::iter(chunks)).into_response();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "no-cache"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            body,
+            "event: ping\ndata: {\"type\": \"ping\"}\n\nevent: message_stop\ndata: {\"type\": \"message_stop\"}\n\n"
+        );
+    }
+}
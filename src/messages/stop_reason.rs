@@ -9,11 +9,15 @@ use std::fmt::Display;
 /// - "max_tokens": we exceeded the requested max_tokens or the model's maximum
 /// - "stop_sequence": one of your provided custom stop_sequences was generated
 /// - "tool_use": Claude wants to use an external tool
+/// - "pause_turn": a long-running turn with server tools was paused and can be continued by sending the response back
+/// - "refusal": Claude refused to respond for safety reasons
+///
+/// An unknown value is kept in [`StopReason::Other`] so that the response can be deserialized when a new value is added.
 ///
 /// Note that these values are different from those in /v1/complete, where end_turn and stop_sequence were not differentiated.
 ///
 /// In non-streaming mode this value is always non-null. In streaming mode, it is null in the message_start event and non-null otherwise.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum StopReason {
     /// The model reached a natural stopping point.
     EndTurn,
@@ -23,6 +27,12 @@ pub enum StopReason {
     StopSequence,
     /// Claude wants to use an external tool.
     ToolUse,
+    /// A long-running turn was paused.
+    PauseTurn,
+    /// Claude refused to respond.
+    Refusal,
+    /// An unknown stop reason.
+    Other(String),
 }
 
 impl Display for StopReason {
@@ -43,6 +53,15 @@ impl Display for StopReason {
             | StopReason::ToolUse => {
                 write!(f, "tool_use")
             },
+            | StopReason::PauseTurn => {
+                write!(f, "pause_turn")
+            },
+            | StopReason::Refusal => {
+                write!(f, "refusal")
+            },
+            | StopReason::Other(value) => {
+                write!(f, "{}", value)
+            },
         }
     }
 }
@@ -52,7 +71,10 @@ impl_enum_string_serialization!(
     EndTurn => "end_turn",
     MaxTokens => "max_tokens",
     StopSequence => "stop_sequence",
-    ToolUse => "tool_use"
+    ToolUse => "tool_use",
+    PauseTurn => "pause_turn",
+    Refusal => "refusal";
+    _ => Other
 );
 
 #[cfg(test)]
@@ -117,5 +139,28 @@ mod tests {
             serde_json::from_str::<StopReason>("\"tool_use\"").unwrap(),
             StopReason::ToolUse
         );
+        assert_eq!(
+            serde_json::from_str::<StopReason>("\"pause_turn\"").unwrap(),
+            StopReason::PauseTurn
+        );
+        assert_eq!(
+            serde_json::from_str::<StopReason>("\"refusal\"").unwrap(),
+            StopReason::Refusal
+        );
+    }
+
+    #[test]
+    fn other() {
+        let stop_reason =
+            serde_json::from_str::<StopReason>("\"new_reason\"").unwrap();
+        assert_eq!(
+            stop_reason,
+            StopReason::Other("new_reason".to_string())
+        );
+        assert_eq!(stop_reason.to_string(), "new_reason");
+        assert_eq!(
+            serde_json::to_string(&stop_reason).unwrap(),
+            "\"new_reason\""
+        );
     }
 }
@@ -2,20 +2,15 @@ use crate::macros::impl_enum_bool_serialization;
 use std::fmt::Display;
 
 /// Whether to incrementally stream the response using server-sent events.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum StreamOption {
     /// "false": Returns the response once.
+    #[default]
     ReturnOnce,
     /// "true": Returns the response in a stream.
     ReturnStream,
 }
 
-impl Default for StreamOption {
-    fn default() -> Self {
-        Self::ReturnOnce
-    }
-}
-
 impl Display for StreamOption {
     fn fmt(
         &self,
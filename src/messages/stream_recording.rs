@@ -0,0 +1,509 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use base64::Engine;
+use bytes::Bytes;
+use futures_core::Stream;
+use pin_project::pin_project;
+use tokio::time::{Instant, Sleep};
+
+use crate::messages::chunk_stream::ChunkStream;
+use crate::messages::{MessageChunk, StreamError};
+
+/// The format of a recording of the raw server-sent events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RecordingFormat {
+    /// The raw bytes of the event stream as they are.
+    #[default]
+    Raw,
+    /// The JSON lines of the received frames with the elapsed time from the start of the stream:
+    /// `{"offset_ms":12,"bytes":"<base64>"}`.
+    Timed,
+}
+
+/// A recorder that writes the raw bytes of a message stream to the writer.
+///
+/// The writes are buffered and flushed at the end of the stream or when the recorder is dropped,
+/// so a file writer does not block the stream on every frame.
+///
+/// See `clust::Client::create_a_message_stream_with_recorder`.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{RecordingFormat, StreamRecorder};
+///
+/// let file = std::io::sink();
+/// let recorder = StreamRecorder::new(file, RecordingFormat::Timed);
+/// ```
+#[derive(Debug)]
+pub struct StreamRecorder<W>
+where
+    W: Write,
+{
+    writer: BufWriter<W>,
+    format: RecordingFormat,
+    start: Option<Instant>,
+}
+
+impl<W> StreamRecorder<W>
+where
+    W: Write,
+{
+    /// Creates a new recorder.
+    ///
+    /// ## Arguments
+    /// - `writer` - The writer of the recording.
+    /// - `format` - The format of the recording.
+    pub fn new(
+        writer: W,
+        format: RecordingFormat,
+    ) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+            format,
+            start: None,
+        }
+    }
+
+    /// Flushes the buffered recording and takes the writer.
+    ///
+    /// ## Errors
+    /// It returns an error if the buffered recording cannot be written.
+    pub fn into_inner(self) -> std::io::Result<W> {
+        self.writer
+            .into_inner()
+            .map_err(|error| error.into_error())
+    }
+
+    /// Starts the timer of the offsets.
+    pub(crate) fn start(&mut self) {
+        self.start = Some(Instant::now());
+    }
+
+    /// Records the frame of the stream.
+    pub(crate) fn record(
+        &mut self,
+        bytes: &[u8],
+    ) -> std::io::Result<()> {
+        match self.format {
+            | RecordingFormat::Raw => {
+                self.writer.write_all(bytes)?;
+            },
+            | RecordingFormat::Timed => {
+                let start = *self
+                    .start
+                    .get_or_insert_with(Instant::now);
+                let frame = RecordedFrame {
+                    offset_ms: start.elapsed().as_millis() as u64,
+                    bytes: base64::engine::general_purpose::STANDARD
+                        .encode(bytes),
+                };
+                serde_json::to_writer(&mut self.writer, &frame)?;
+                self.writer.write_all(b"\n")?;
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the buffered recording to the writer.
+    pub(crate) fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// A frame of the timed recording.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RecordedFrame {
+    /// The elapsed milliseconds from the start of the stream.
+    offset_ms: u64,
+    /// The base64 encoded bytes of the frame.
+    bytes: String,
+}
+
+/// The byte stream that tees the bytes to the recorder.
+///
+/// It ends after a recording error, because the bytes of the frame are not passed to the decoder.
+#[pin_project]
+pub(crate) struct RecordingStream<S, W>
+where
+    W: Write,
+{
+    #[pin]
+    stream: S,
+    recorder: StreamRecorder<W>,
+    is_terminated: bool,
+}
+
+impl<S, W> RecordingStream<S, W>
+where
+    W: Write,
+{
+    /// Creates a new recording stream and starts the timer of the recorder.
+    pub(crate) fn new(
+        stream: S,
+        mut recorder: StreamRecorder<W>,
+    ) -> Self {
+        recorder.start();
+        Self {
+            stream,
+            recorder,
+            is_terminated: false,
+        }
+    }
+}
+
+impl<S, E, W> Stream for RecordingStream<S, W>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: Into<StreamError>,
+    W: Write,
+{
+    type Item = Result<Bytes, StreamError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.is_terminated {
+            return Poll::Ready(None);
+        }
+
+        match this.stream.poll_next(cx) {
+            | Poll::Ready(Some(Ok(bytes))) => {
+                if let Err(error) = this.recorder.record(&bytes) {
+                    *this.is_terminated = true;
+                    return Poll::Ready(Some(Err(
+                        StreamError::RecordingError(error),
+                    )));
+                }
+                Poll::Ready(Some(Ok(bytes)))
+            },
+            | Poll::Ready(Some(Err(error))) => {
+                Poll::Ready(Some(Err(error.into())))
+            },
+            | Poll::Ready(None) => {
+                *this.is_terminated = true;
+                match this.recorder.flush() {
+                    | Ok(()) => Poll::Ready(None),
+                    | Err(error) => Poll::Ready(Some(Err(
+                        StreamError::RecordingError(error),
+                    ))),
+                }
+            },
+            | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A replay of a recorded message stream through the same parser as `clust::Client::create_a_message_stream`.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{MessageChunk, PingChunk, RecordingFormat, StreamReplay};
+/// use tokio_stream::StreamExt;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let recording = "event: ping\r\ndata: {\"type\": \"ping\"}\r\n\r\n";
+///
+///     let mut stream = StreamReplay::new(recording.as_bytes(), RecordingFormat::Raw)
+///         .unwrap()
+///         .into_stream();
+///
+///     assert_eq!(
+///         stream.next().await.unwrap().unwrap(),
+///         MessageChunk::Ping(PingChunk::new())
+///     );
+///     assert!(stream.next().await.is_none());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamReplay {
+    frames: Vec<(Duration, Bytes)>,
+    original_timing: bool,
+}
+
+impl StreamReplay {
+    /// Creates a new replay from the recording.
+    ///
+    /// ## Arguments
+    /// - `recording` - The recording written by `StreamRecorder`, or a captured event stream for `RecordingFormat::Raw`.
+    /// - `format` - The format of the recording.
+    ///
+    /// ## Errors
+    /// It returns `StreamError::InvalidRecording` if a line of the timed recording is invalid.
+    pub fn new(
+        recording: &[u8],
+        format: RecordingFormat,
+    ) -> Result<Self, StreamError> {
+        let frames = match format {
+            | RecordingFormat::Raw => {
+                vec![(Duration::ZERO, Bytes::copy_from_slice(recording))]
+            },
+            | RecordingFormat::Timed => recording
+                .split(|byte| *byte == b'\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    let frame: RecordedFrame = serde_json::from_slice(line)
+                        .map_err(|error| {
+                            StreamError::InvalidRecording(error.to_string())
+                        })?;
+                    let bytes = base64::engine::general_purpose::STANDARD
+                        .decode(frame.bytes)
+                        .map_err(|error| {
+                            StreamError::InvalidRecording(error.to_string())
+                        })?;
+                    Ok((
+                        Duration::from_millis(frame.offset_ms),
+                        Bytes::from(bytes),
+                    ))
+                })
+                .collect::<Result<Vec<_>, StreamError>>()?,
+        };
+
+        Ok(Self {
+            frames,
+            original_timing: false,
+        })
+    }
+
+    /// Replays the frames at the original offsets of the timed recording.
+    pub fn with_original_timing(mut self) -> Self {
+        self.original_timing = true;
+        self
+    }
+
+    /// Converts the replay into the stream of message chunks.
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<MessageChunk, StreamError>> + Unpin {
+        ChunkStream::new(ReplayByteStream {
+            frames: self.frames.into(),
+            original_timing: self.original_timing,
+            start: None,
+            delay: None,
+        })
+    }
+}
+
+/// The byte stream of the recorded frames.
+struct ReplayByteStream {
+    frames: VecDeque<(Duration, Bytes)>,
+    original_timing: bool,
+    start: Option<Instant>,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl Stream for ReplayByteStream {
+    type Item = Result<Bytes, StreamError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if self.original_timing {
+            let start = *self
+                .start
+                .get_or_insert_with(Instant::now);
+            let offset = match self.frames.front() {
+                | Some((offset, _)) => *offset,
+                | None => return Poll::Ready(None),
+            };
+
+            let delay = self
+                .delay
+                .get_or_insert_with(|| {
+                    Box::pin(tokio::time::sleep_until(start + offset))
+                });
+            if delay
+                .as_mut()
+                .poll(cx)
+                .is_pending()
+            {
+                return Poll::Pending;
+            }
+            self.delay = None;
+        }
+
+        Poll::Ready(
+            self.frames
+                .pop_front()
+                .map(|(_, bytes)| Ok(bytes)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{MessageStopChunk, PingChunk};
+    use futures_util::StreamExt;
+
+    const EVENTS: &str = "event: ping\ndata: {\"type\": \"ping\"}\n\nevent: message_stop\ndata: {\"type\": \"message_stop\"}\n\n";
+
+    #[tokio::test(start_paused = true)]
+    async fn record_and_replay() {
+        let frames = vec![
+            (0, &EVENTS[..20]),
+            (5, &EVENTS[20..40]),
+            (10, &EVENTS[40..]),
+        ];
+        let input_stream = futures_util::stream::unfold(
+            frames.into_iter(),
+            |mut frames| async move {
+                let (delay, frame) = frames.next()?;
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                Some((
+                    Ok::<_, reqwest::Error>(Bytes::from(frame)),
+                    frames,
+                ))
+            },
+        );
+
+        let mut raw = Vec::new();
+        let mut timed = Vec::new();
+        let recording_stream = RecordingStream::new(
+            RecordingStream::new(
+                Box::pin(input_stream),
+                StreamRecorder::new(&mut timed, RecordingFormat::Timed),
+            ),
+            StreamRecorder::new(&mut raw, RecordingFormat::Raw),
+        );
+        let chunks = ChunkStream::new(recording_stream)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(chunks.len(), 2);
+
+        assert_eq!(raw, EVENTS.as_bytes());
+        let lines = String::from_utf8(timed.clone()).unwrap();
+        assert_eq!(lines.lines().count(), 3);
+        assert!(lines.starts_with("{\"offset_ms\":0,\"bytes\":"));
+
+        for format in [RecordingFormat::Raw, RecordingFormat::Timed] {
+            let recording = match format {
+                | RecordingFormat::Raw => &raw,
+                | RecordingFormat::Timed => &timed,
+            };
+            let chunks = StreamReplay::new(recording, format)
+                .unwrap()
+                .into_stream()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+                .await;
+            assert_eq!(
+                chunks,
+                vec![
+                    MessageChunk::Ping(PingChunk::new()),
+                    MessageChunk::MessageStop(MessageStopChunk::new()),
+                ]
+            );
+        }
+
+        let start = Instant::now();
+        let mut stream = StreamReplay::new(&timed, RecordingFormat::Timed)
+            .unwrap()
+            .with_original_timing()
+            .into_stream();
+        stream.next().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(5));
+        stream.next().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(15));
+    }
+
+    #[tokio::test]
+    async fn terminate_after_recording_error() {
+        /// A writer that always fails.
+        struct FailingWriter;
+
+        impl Write for FailingWriter {
+            fn write(
+                &mut self,
+                _buf: &[u8],
+            ) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::Other.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        // A frame larger than the buffer is written through.
+        let frame = Bytes::from(EVENTS.repeat(1024));
+        let input_stream = futures_util::stream::iter(vec![
+            Ok::<_, StreamError>(frame.clone()),
+            Ok(frame),
+        ]);
+
+        let mut recording_stream = RecordingStream::new(
+            input_stream,
+            StreamRecorder::new(FailingWriter, RecordingFormat::Raw),
+        );
+        assert!(matches!(
+            recording_stream.next().await,
+            Some(Err(StreamError::RecordingError(_)))
+        ));
+        assert!(recording_stream.next().await.is_none());
+    }
+
+    #[test]
+    fn buffer_recording() {
+        /// A writer that counts the writes.
+        struct CountingWriter {
+            writes: usize,
+            bytes: Vec<u8>,
+        }
+
+        impl Write for CountingWriter {
+            fn write(
+                &mut self,
+                buf: &[u8],
+            ) -> std::io::Result<usize> {
+                self.writes += 1;
+                self.bytes.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut recorder = StreamRecorder::new(
+            CountingWriter {
+                writes: 0,
+                bytes: Vec::new(),
+            },
+            RecordingFormat::Raw,
+        );
+        for frame in EVENTS.as_bytes().chunks(8) {
+            recorder.record(frame).unwrap();
+        }
+
+        let writer = recorder.into_inner().unwrap();
+        assert_eq!(writer.writes, 1);
+        assert_eq!(writer.bytes, EVENTS.as_bytes());
+    }
+
+    #[test]
+    fn invalid_recording() {
+        assert!(matches!(
+            StreamReplay::new(b"event: ping\n", RecordingFormat::Timed),
+            Err(StreamError::InvalidRecording(_))
+        ));
+        assert!(matches!(
+            StreamReplay::new(
+                b"{\"offset_ms\":0,\"bytes\":\"!\"}\n",
+                RecordingFormat::Timed
+            ),
+            Err(StreamError::InvalidRecording(_))
+        ));
+    }
+}
@@ -1,45 +1,75 @@
 use std::fmt::Display;
 
+use crate::macros::impl_enum_with_string_or_array_serialization;
+use crate::messages::{CacheControl, TextContentBlock};
+
 /// System prompt.
 ///
 /// A system prompt is a way of providing context and instructions to Claude, such as specifying a particular goal or role.
 /// See our [guide to system prompts](https://docs.anthropic.com/claude/docs/system-prompts).
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-#[serde(transparent)]
-pub struct SystemPrompt {
-    value: String,
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{CacheControl, SystemPrompt, TextContentBlock};
+///
+/// let system_prompt = SystemPrompt::new("system-prompt");
+/// let system_prompt = SystemPrompt::blocks(vec![
+///     TextContentBlock::new("long-instructions")
+///         .with_cache_control(CacheControl::ephemeral()),
+///     TextContentBlock::new("short-instructions"),
+/// ]);
+/// let system_prompt = SystemPrompt::new("long-system-prompt")
+///     .with_cache_control(CacheControl::ephemeral());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SystemPrompt {
+    /// The system prompt as a single text.
+    Text(String),
+    /// The system prompt as text blocks, e.g. to specify the cache control.
+    Blocks(Vec<TextContentBlock>),
 }
 
 impl Default for SystemPrompt {
     fn default() -> Self {
-        Self {
-            value: String::new(),
-        }
+        Self::Text(String::new())
     }
 }
 
+impl_enum_with_string_or_array_serialization!(
+    SystemPrompt,
+    Text(String),
+    Blocks(TextContentBlock)
+);
+
 impl Display for SystemPrompt {
     fn fmt(
         &self,
         f: &mut std::fmt::Formatter<'_>,
     ) -> std::fmt::Result {
-        write!(f, "{}", self.value)
-    }
-}
-
-impl From<String> for SystemPrompt {
-    fn from(value: String) -> Self {
-        Self {
-            value,
+        match self {
+            | SystemPrompt::Text(text) => {
+                write!(f, "{}", text)
+            },
+            | SystemPrompt::Blocks(blocks) => {
+                let texts = blocks
+                    .iter()
+                    .map(|block| block.text.as_str())
+                    .collect::<Vec<_>>();
+                write!(f, "{}", texts.join("\n"))
+            },
         }
     }
 }
 
 impl From<&str> for SystemPrompt {
     fn from(value: &str) -> Self {
-        Self {
-            value: value.to_string(),
-        }
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<TextContentBlock> for SystemPrompt {
+    fn from(block: TextContentBlock) -> Self {
+        Self::Blocks(vec![block])
     }
 }
 
@@ -49,9 +79,31 @@ impl SystemPrompt {
     where
         S: Into<String>,
     {
-        Self {
-            value: value.into(),
+        Self::Text(value.into())
+    }
+
+    /// Creates a new system prompt from text blocks.
+    pub fn blocks(blocks: Vec<TextContentBlock>) -> Self {
+        Self::Blocks(blocks)
+    }
+
+    /// Sets the cache control to the last block of the system prompt.
+    ///
+    /// A single text is converted into a text block.
+    pub fn with_cache_control(
+        self,
+        cache_control: CacheControl,
+    ) -> Self {
+        let mut blocks = match self {
+            | SystemPrompt::Text(text) => vec![TextContentBlock::new(text)],
+            | SystemPrompt::Blocks(blocks) => blocks,
+        };
+
+        if let Some(last) = blocks.last_mut() {
+            last.cache_control = Some(cache_control);
         }
+
+        Self::Blocks(blocks)
     }
 }
 
@@ -62,12 +114,27 @@ mod tests {
     #[test]
     fn new() {
         let system_prompt = SystemPrompt::new("system-prompt");
-        assert_eq!(system_prompt.value, "system-prompt");
+        assert_eq!(
+            system_prompt,
+            SystemPrompt::Text("system-prompt".to_string())
+        );
+
+        let system_prompt =
+            SystemPrompt::blocks(vec![TextContentBlock::new("system-prompt")]);
+        assert_eq!(
+            system_prompt,
+            SystemPrompt::Blocks(vec![TextContentBlock::new(
+                "system-prompt"
+            )])
+        );
     }
 
     #[test]
     fn default() {
-        assert_eq!(SystemPrompt::default().value, "");
+        assert_eq!(
+            SystemPrompt::default(),
+            SystemPrompt::Text(String::new())
+        );
     }
 
     #[test]
@@ -77,6 +144,42 @@ mod tests {
             system_prompt.to_string(),
             "system-prompt"
         );
+
+        let system_prompt = SystemPrompt::blocks(vec![
+            TextContentBlock::new("first"),
+            TextContentBlock::new("second"),
+        ]);
+        assert_eq!(
+            system_prompt.to_string(),
+            "first\nsecond"
+        );
+    }
+
+    #[test]
+    fn with_cache_control() {
+        let system_prompt = SystemPrompt::new("system-prompt")
+            .with_cache_control(CacheControl::ephemeral());
+        assert_eq!(
+            system_prompt,
+            SystemPrompt::Blocks(vec![TextContentBlock::new(
+                "system-prompt"
+            )
+            .with_cache_control(CacheControl::ephemeral())])
+        );
+
+        let system_prompt = SystemPrompt::blocks(vec![
+            TextContentBlock::new("first"),
+            TextContentBlock::new("second"),
+        ])
+        .with_cache_control(CacheControl::ephemeral());
+        assert_eq!(
+            system_prompt,
+            SystemPrompt::Blocks(vec![
+                TextContentBlock::new("first"),
+                TextContentBlock::new("second")
+                    .with_cache_control(CacheControl::ephemeral()),
+            ])
+        );
     }
 
     #[test]
@@ -86,6 +189,13 @@ mod tests {
             serde_json::to_string(&system_prompt).unwrap(),
             "\"system-prompt\""
         );
+
+        let system_prompt = SystemPrompt::new("system-prompt")
+            .with_cache_control(CacheControl::ephemeral());
+        assert_eq!(
+            serde_json::to_string(&system_prompt).unwrap(),
+            "[{\"type\":\"text\",\"text\":\"system-prompt\",\"cache_control\":{\"type\":\"ephemeral\"}}]"
+        );
     }
 
     #[test]
@@ -95,5 +205,15 @@ mod tests {
             serde_json::from_str::<SystemPrompt>("\"system-prompt\"").unwrap(),
             system_prompt
         );
+
+        let system_prompt = SystemPrompt::new("system-prompt")
+            .with_cache_control(CacheControl::ephemeral());
+        assert_eq!(
+            serde_json::from_str::<SystemPrompt>(
+                "[{\"type\":\"text\",\"text\":\"system-prompt\",\"cache_control\":{\"type\":\"ephemeral\"}}]"
+            )
+            .unwrap(),
+            system_prompt
+        );
     }
 }
@@ -0,0 +1,185 @@
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+use crate::messages::MaxTokens;
+use crate::ValidationError;
+use std::fmt::Display;
+
+/// The minimum number of tokens of the thinking budget.
+pub const MIN_THINKING_BUDGET_TOKENS: u32 = 1024;
+
+/// The configuration of the extended thinking.
+///
+/// See [extended thinking](https://docs.anthropic.com/en/docs/build-with-claude/extended-thinking).
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{MaxTokens, Thinking};
+///
+/// let thinking = Thinking::enabled(2048, MaxTokens::default()).unwrap();
+/// assert_eq!(thinking.budget_tokens(), Some(2048));
+///
+/// let thinking = Thinking::disabled();
+/// assert_eq!(thinking.budget_tokens(), None);
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct Thinking {
+    /// The type of the thinking configuration.
+    #[serde(rename = "type")]
+    _type: ThinkingType,
+    /// The number of tokens that the model can use for the thinking.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    budget_tokens: Option<u32>,
+}
+
+impl_display_for_serialize!(Thinking);
+
+impl Thinking {
+    /// Creates a new enabled thinking configuration.
+    ///
+    /// ## Arguments
+    /// - `budget_tokens` - The number of tokens that the model can use for the thinking.
+    /// - `max_tokens` - The maximum number of tokens of the request, which includes the thinking.
+    ///
+    /// ## Errors
+    /// It returns a validation error if the budget is less than [`MIN_THINKING_BUDGET_TOKENS`] or not less than the maximum number of tokens.
+    pub fn enabled(
+        budget_tokens: u32,
+        max_tokens: MaxTokens,
+    ) -> Result<Self, ValidationError<u32>> {
+        if budget_tokens < MIN_THINKING_BUDGET_TOKENS
+            || budget_tokens >= max_tokens.value()
+        {
+            return Err(ValidationError {
+                _type: "Thinking".to_string(),
+                expected: format!(
+                    "The budget tokens must be in range: [{}, {}).",
+                    MIN_THINKING_BUDGET_TOKENS, max_tokens,
+                ),
+                actual: budget_tokens,
+            });
+        }
+
+        Ok(Self {
+            _type: ThinkingType::Enabled,
+            budget_tokens: Some(budget_tokens),
+        })
+    }
+
+    /// Creates a new disabled thinking configuration.
+    pub fn disabled() -> Self {
+        Self {
+            _type: ThinkingType::Disabled,
+            budget_tokens: None,
+        }
+    }
+
+    /// Whether the thinking is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self._type == ThinkingType::Enabled
+    }
+
+    /// The number of tokens that the model can use for the thinking.
+    pub fn budget_tokens(&self) -> Option<u32> {
+        self.budget_tokens
+    }
+}
+
+/// The type of the thinking configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ThinkingType {
+    /// enabled
+    Enabled,
+    /// disabled
+    #[default]
+    Disabled,
+}
+
+impl Display for ThinkingType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | ThinkingType::Enabled => {
+                write!(f, "enabled")
+            },
+            | ThinkingType::Disabled => {
+                write!(f, "disabled")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    ThinkingType,
+    Enabled => "enabled",
+    Disabled => "disabled"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::ClaudeModel;
+
+    #[test]
+    fn enabled() {
+        let max_tokens =
+            MaxTokens::new(4096, ClaudeModel::Claude35Sonnet20240620).unwrap();
+
+        let thinking = Thinking::enabled(1024, max_tokens).unwrap();
+        assert!(thinking.is_enabled());
+        assert_eq!(thinking.budget_tokens(), Some(1024));
+
+        assert!(Thinking::enabled(1023, max_tokens).is_err());
+        assert!(Thinking::enabled(4096, max_tokens).is_err());
+        assert!(Thinking::enabled(4095, max_tokens).is_ok());
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Thinking::default(), Thinking::disabled());
+        assert!(!Thinking::default().is_enabled());
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_json::to_string(
+                &Thinking::enabled(2048, MaxTokens::default()).unwrap()
+            )
+            .unwrap(),
+            "{\"type\":\"enabled\",\"budget_tokens\":2048}"
+        );
+        assert_eq!(
+            serde_json::to_string(&Thinking::disabled()).unwrap(),
+            "{\"type\":\"disabled\"}"
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            serde_json::from_str::<Thinking>(
+                "{\"type\":\"enabled\",\"budget_tokens\":2048}"
+            )
+            .unwrap(),
+            Thinking::enabled(2048, MaxTokens::default()).unwrap()
+        );
+        assert_eq!(
+            serde_json::from_str::<Thinking>("{\"type\":\"disabled\"}")
+                .unwrap(),
+            Thinking::disabled()
+        );
+    }
+}
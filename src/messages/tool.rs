@@ -1,5 +1,14 @@
-use crate::macros::impl_display_for_serialize;
-use crate::messages::{TextContentBlock, ToolCallError};
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_struct_serialization,
+    impl_enum_with_string_or_array_serialization,
+};
+use crate::messages::tool_index::ToolIndex;
+use crate::messages::{
+    BashTool, CacheControl, CodeExecutionTool, ComputerUseTool,
+    ContentFlatteningError, DocumentContentBlock, ImageContentBlock,
+    TextContentBlock, TextEditorTool, ToolCallError, ToolListError,
+    WebSearchTool,
+};
 use std::future::Future;
 
 /// A tool that can be used by assistant.
@@ -24,23 +33,255 @@ pub trait AsyncTool {
     ) -> impl Future<Output = Result<ToolResult, ToolCallError>> + Send;
 }
 
-/// A tool definition that can be used by assistant.
+/// A tool definition that can be used by assistant: a custom tool or an Anthropic-defined tool.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{ToolDefinition, WebSearchTool};
+///
+/// let tools = vec![
+///     ToolDefinition::new(
+///         "get_weather",
+///         Some("Get the current weather in a given location."),
+///         serde_json::json!({"type": "object"}),
+///     ),
+///     ToolDefinition::from(WebSearchTool::new().with_max_uses(3)),
+/// ];
+/// assert_eq!(tools[1].name(), "web_search");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolDefinition {
+    /// A custom tool defined by the input schema.
+    Custom(CustomToolDefinition),
+    /// The bash tool.
+    Bash(BashTool),
+    /// The text editor tool.
+    TextEditor(TextEditorTool),
+    /// The computer use tool.
+    ComputerUse(ComputerUseTool),
+    /// The web search tool, executed on the server side.
+    WebSearch(WebSearchTool),
+    /// The code execution tool, executed on the server side.
+    CodeExecution(CodeExecutionTool),
+}
+
+impl Default for ToolDefinition {
+    fn default() -> Self {
+        Self::Custom(CustomToolDefinition::default())
+    }
+}
+
+impl_display_for_serialize!(ToolDefinition);
+
+impl From<CustomToolDefinition> for ToolDefinition {
+    fn from(tool: CustomToolDefinition) -> Self {
+        Self::Custom(tool)
+    }
+}
+
+impl From<BashTool> for ToolDefinition {
+    fn from(tool: BashTool) -> Self {
+        Self::Bash(tool)
+    }
+}
+
+impl From<TextEditorTool> for ToolDefinition {
+    fn from(tool: TextEditorTool) -> Self {
+        Self::TextEditor(tool)
+    }
+}
+
+impl From<ComputerUseTool> for ToolDefinition {
+    fn from(tool: ComputerUseTool) -> Self {
+        Self::ComputerUse(tool)
+    }
+}
+
+impl From<WebSearchTool> for ToolDefinition {
+    fn from(tool: WebSearchTool) -> Self {
+        Self::WebSearch(tool)
+    }
+}
+
+impl From<CodeExecutionTool> for ToolDefinition {
+    fn from(tool: CodeExecutionTool) -> Self {
+        Self::CodeExecution(tool)
+    }
+}
+
+impl serde::Serialize for ToolDefinition {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            | ToolDefinition::Custom(tool) => tool.serialize(serializer),
+            | ToolDefinition::Bash(tool) => tool.serialize(serializer),
+            | ToolDefinition::TextEditor(tool) => tool.serialize(serializer),
+            | ToolDefinition::ComputerUse(tool) => tool.serialize(serializer),
+            | ToolDefinition::WebSearch(tool) => tool.serialize(serializer),
+            | ToolDefinition::CodeExecution(tool) => {
+                tool.serialize(serializer)
+            },
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ToolDefinition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        fn from_value<T, E>(value: serde_json::Value) -> Result<T, E>
+        where
+            T: serde::de::DeserializeOwned,
+            E: serde::de::Error,
+        {
+            serde_json::from_value(value).map_err(E::custom)
+        }
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        // The type of an Anthropic-defined tool is versioned, e.g. `bash_20250124`.
+        match value
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+        {
+            | None | Some("custom") => {
+                from_value(value).map(ToolDefinition::Custom)
+            },
+            | Some(tag) if tag.starts_with("bash_") => {
+                from_value(value).map(ToolDefinition::Bash)
+            },
+            | Some(tag) if tag.starts_with("text_editor_") => {
+                from_value(value).map(ToolDefinition::TextEditor)
+            },
+            | Some(tag) if tag.starts_with("computer_") => {
+                from_value(value).map(ToolDefinition::ComputerUse)
+            },
+            | Some(tag) if tag.starts_with("web_search_") => {
+                from_value(value).map(ToolDefinition::WebSearch)
+            },
+            | Some(tag) if tag.starts_with("code_execution_") => {
+                from_value(value).map(ToolDefinition::CodeExecution)
+            },
+            | Some(tag) => Err(serde::de::Error::custom(format!(
+                "unknown tag: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+impl ToolDefinition {
+    /// Creates a new custom `ToolDefinition`.
+    pub fn new<S, T>(
+        name: S,
+        description: Option<T>,
+        input_schema: serde_json::Value,
+    ) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self::Custom(CustomToolDefinition::new(
+            name,
+            description,
+            input_schema,
+        ))
+    }
+
+    /// The name of the tool.
+    pub fn name(&self) -> &str {
+        match self {
+            | ToolDefinition::Custom(tool) => &tool.name,
+            | ToolDefinition::Bash(tool) => &tool.name,
+            | ToolDefinition::TextEditor(tool) => &tool.name,
+            | ToolDefinition::ComputerUse(tool) => &tool.name,
+            | ToolDefinition::WebSearch(tool) => &tool.name,
+            | ToolDefinition::CodeExecution(tool) => &tool.name,
+        }
+    }
+
+    /// Whether the tool is executed on the server side.
+    ///
+    /// The results of server tools are returned in the response, so the client must not call them.
+    pub fn is_server_tool(&self) -> bool {
+        matches!(
+            self,
+            ToolDefinition::WebSearch(_) | ToolDefinition::CodeExecution(_)
+        )
+    }
+
+    /// The cache control of the tool definition.
+    pub fn cache_control(&self) -> Option<&CacheControl> {
+        match self {
+            | ToolDefinition::Custom(tool) => tool.cache_control.as_ref(),
+            | ToolDefinition::Bash(tool) => tool.cache_control.as_ref(),
+            | ToolDefinition::TextEditor(tool) => tool.cache_control.as_ref(),
+            | ToolDefinition::ComputerUse(tool) => tool.cache_control.as_ref(),
+            | ToolDefinition::WebSearch(tool) => tool.cache_control.as_ref(),
+            | ToolDefinition::CodeExecution(tool) => {
+                tool.cache_control.as_ref()
+            },
+        }
+    }
+
+    /// Sets the cache control of the tool definition.
+    pub fn with_cache_control(
+        mut self,
+        cache_control: CacheControl,
+    ) -> Self {
+        self.set_cache_control(Some(cache_control));
+        self
+    }
+
+    pub(crate) fn set_cache_control(
+        &mut self,
+        cache_control: Option<CacheControl>,
+    ) {
+        match self {
+            | ToolDefinition::Custom(tool) => tool.cache_control = cache_control,
+            | ToolDefinition::Bash(tool) => tool.cache_control = cache_control,
+            | ToolDefinition::TextEditor(tool) => {
+                tool.cache_control = cache_control
+            },
+            | ToolDefinition::ComputerUse(tool) => {
+                tool.cache_control = cache_control
+            },
+            | ToolDefinition::WebSearch(tool) => {
+                tool.cache_control = cache_control
+            },
+            | ToolDefinition::CodeExecution(tool) => {
+                tool.cache_control = cache_control
+            },
+        }
+    }
+}
+
+/// A custom tool definition described by the JSON schema of the input.
 #[derive(
     Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
 )]
-pub struct ToolDefinition {
+pub struct CustomToolDefinition {
     /// Name of the tool.
     pub name: String,
     /// Optional, but strongly-recommended description of the tool.
     pub description: Option<String>,
     /// JSON schema for the tool input shape that the model will produce in tool_use output content blocks.
     pub input_schema: serde_json::Value,
+    /// The cache control of the tool definition.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_control: Option<CacheControl>,
 }
 
-impl_display_for_serialize!(ToolDefinition);
+impl_display_for_serialize!(CustomToolDefinition);
 
-impl ToolDefinition {
-    /// Creates a new `ToolDefinition`.
+impl CustomToolDefinition {
+    /// Creates a new `CustomToolDefinition`.
     pub fn new<S, T>(
         name: S,
         description: Option<T>,
@@ -54,8 +295,18 @@ impl ToolDefinition {
             name: name.into(),
             description: description.map(Into::into),
             input_schema,
+            cache_control: None,
         }
     }
+
+    /// Sets the cache control of the tool definition.
+    pub fn with_cache_control(
+        mut self,
+        cache_control: CacheControl,
+    ) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
 }
 
 /// A tool use request.
@@ -107,9 +358,9 @@ impl ToolUse {
 pub struct ToolResult {
     /// The id of the tool use request this is a result for.
     pub tool_use_id: String,
-    /// The result of the tool, as a string (e.g. "content": "65 degrees") or list of nested content blocks (e.g. "content": [{"type": "text", "text": "65 degrees"}]\). During beta, only the text type content blocks are supported for tool_result content.
+    /// The result of the tool, as a string (e.g. "content": "65 degrees") or list of nested text, image or document content blocks (e.g. "content": [{"type": "text", "text": "65 degrees"}]\).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<TextContentBlock>,
+    pub content: Option<ToolResultContent>,
     /// Set to true if the tool execution resulted in an error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_error: Option<bool>,
@@ -125,7 +376,7 @@ impl ToolResult {
     ) -> Self
     where
         S: Into<String>,
-        T: Into<TextContentBlock>,
+        T: Into<ToolResultContent>,
     {
         Self {
             tool_use_id: tool_use_id.into(),
@@ -153,7 +404,7 @@ impl ToolResult {
     ) -> Self
     where
         S: Into<String>,
-        T: Into<TextContentBlock>,
+        T: Into<ToolResultContent>,
     {
         Self {
             tool_use_id: tool_use_id.into(),
@@ -175,42 +426,239 @@ impl ToolResult {
     }
 }
 
-/// A list of tools that can be called by the assistant.
+/// The content of a tool result.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{ImageContentBlock, ImageContentSource, TextContentBlock, ToolResultBlock, ToolResultContent};
+///
+/// let content = ToolResultContent::from("65 degrees");
+/// let content = ToolResultContent::from(vec![
+///     ToolResultBlock::from(TextContentBlock::new("The chart of temperatures:")),
+///     ToolResultBlock::from(ImageContentBlock::new(ImageContentSource::url("https://example.com/chart.png"))),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolResultContent {
+    /// The result as a single text.
+    Text(String),
+    /// The result as a list of text, image or document blocks.
+    Blocks(Vec<ToolResultBlock>),
+}
+
+impl Default for ToolResultContent {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl_enum_with_string_or_array_serialization!(
+    ToolResultContent,
+    Text(String),
+    Blocks(ToolResultBlock)
+);
+
+impl_display_for_serialize!(ToolResultContent);
+
+impl From<&str> for ToolResultContent {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<TextContentBlock> for ToolResultContent {
+    fn from(block: TextContentBlock) -> Self {
+        Self::Blocks(vec![block.into()])
+    }
+}
+
+impl From<ImageContentBlock> for ToolResultContent {
+    fn from(block: ImageContentBlock) -> Self {
+        Self::Blocks(vec![block.into()])
+    }
+}
+
+impl From<DocumentContentBlock> for ToolResultContent {
+    fn from(block: DocumentContentBlock) -> Self {
+        Self::Blocks(vec![block.into()])
+    }
+}
+
+impl From<ToolResultBlock> for ToolResultContent {
+    fn from(block: ToolResultBlock) -> Self {
+        Self::Blocks(vec![block])
+    }
+}
+
+impl ToolResultContent {
+    /// Flattens the content into a single text.
+    /// - `ToolResultContent::Text` => Returns "`Ok(text)`"
+    /// - `ToolResultContent::Blocks` =>
+    ///     - Has `ToolResultBlock::Text` at the first block => Returns "`Ok(text)`"
+    ///     - Otherwise => Returns "`Err(NotFoundTargetBlock)`".
+    pub fn flatten_into_text(&self) -> Result<&str, ContentFlatteningError> {
+        match self {
+            | ToolResultContent::Text(text) => Ok(text),
+            | ToolResultContent::Blocks(blocks) => match blocks.first() {
+                | Some(ToolResultBlock::Text(text)) => Ok(&text.text),
+                | Some(_) => Err(ContentFlatteningError::NotFoundTargetBlock),
+                | None => Err(ContentFlatteningError::Empty),
+            },
+        }
+    }
+}
+
+/// A block of the tool result content.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolResultBlock {
+    /// The text content block.
+    Text(TextContentBlock),
+    /// The image content block.
+    Image(ImageContentBlock),
+    /// The document content block.
+    Document(DocumentContentBlock),
+}
+
+impl Default for ToolResultBlock {
+    fn default() -> Self {
+        Self::Text(TextContentBlock::default())
+    }
+}
+
+impl_enum_struct_serialization!(
+    ToolResultBlock,
+    type,
+    Text(TextContentBlock, "text"),
+    Image(ImageContentBlock, "image"),
+    Document(DocumentContentBlock, "document")
+);
+
+impl_display_for_serialize!(ToolResultBlock);
+
+/// A list of tools that can be called by the assistant, indexed by the tool names.
+///
+/// The definition of each tool is taken once when the tool is added.
 pub struct ToolList {
-    tools: Vec<Box<dyn Tool>>,
+    tools: ToolIndex<dyn Tool>,
 }
 
 impl ToolList {
     /// Create a new tool list.
-    pub fn new(tools: Vec<Box<dyn Tool>>) -> Self {
-        Self {
-            tools,
+    ///
+    /// ## Errors
+    /// It returns `ToolListError::InvalidName` if a tool name does not match `^[a-zA-Z0-9_-]{1,64}$`,
+    /// `ToolListError::DuplicateName` if multiple tools have the same name,
+    /// or `ToolListError::InvalidSchema` if an input schema is invalid with the `validation` feature.
+    pub fn new(tools: Vec<Box<dyn Tool>>) -> Result<Self, ToolListError> {
+        let mut list = Self {
+            tools: ToolIndex::new(),
+        };
+        for tool in tools {
+            list.add(tool)?;
         }
+
+        Ok(list)
+    }
+
+    /// Adds a tool to this list.
+    ///
+    /// ## Errors
+    /// It returns `ToolListError::InvalidName` if the tool name does not match `^[a-zA-Z0-9_-]{1,64}$`,
+    /// `ToolListError::DuplicateName` if a tool with the same name is already in this list,
+    /// or `ToolListError::InvalidSchema` if the input schema is invalid with the `validation` feature.
+    pub fn add(
+        &mut self,
+        tool: Box<dyn Tool>,
+    ) -> Result<(), ToolListError> {
+        self.tools
+            .insert(tool.definition(), tool)
+    }
+
+    /// Removes the tool with the name from this list.
+    pub fn remove(
+        &mut self,
+        name: &str,
+    ) -> Option<Box<dyn Tool>> {
+        self.tools.remove(name)
+    }
+
+    /// Whether the tool with the name is in this list.
+    pub fn contains(
+        &self,
+        name: &str,
+    ) -> bool {
+        self.tools.get(name).is_some()
+    }
+
+    /// The number of tools.
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether this list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The names of the tools in the order of addition.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tools.names()
+    }
+
+    /// The definition of the tool with the name.
+    pub fn definition(
+        &self,
+        name: &str,
+    ) -> Option<&ToolDefinition> {
+        self.tools
+            .get(name)
+            .map(|(definition, _)| definition)
     }
 
     /// List of tool definitions.
     pub fn definitions(&self) -> Vec<ToolDefinition> {
+        self.tools.definitions()
+    }
+
+    /// List of the definitions of the tools enabled for a request, in the order of the names.
+    ///
+    /// ## Errors
+    /// It returns `ToolListError::ToolNotFound` if a tool with the name is not in this list.
+    pub fn enabled_definitions<I, S>(
+        &self,
+        names: I,
+    ) -> Result<Vec<ToolDefinition>, ToolListError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
         self.tools
-            .iter()
-            .map(|tool| tool.definition())
-            .collect::<Vec<ToolDefinition>>()
-            .into()
+            .enabled_definitions(names)
     }
 
     /// Calls a tool in this list.
+    ///
+    /// With the `validation` feature enabled by default, if the input does not match the input schema of the tool,
+    /// the tool is not called and the error result listing the violations is returned.
     pub fn call(
         &self,
         tool_use: ToolUse,
     ) -> Result<ToolResult, ToolCallError> {
-        let target_name = tool_use.name.clone();
+        let (_, tool) = self
+            .tools
+            .get(&tool_use.name)
+            .ok_or_else(|| {
+                ToolCallError::ToolNotFound(tool_use.name.clone())
+            })?;
 
-        let target_tool = self
+        if let Some(result) = self
             .tools
-            .iter()
-            .find(|tool| tool.definition().name == target_name)
-            .ok_or_else(|| ToolCallError::ToolNotFound(target_name))?;
+            .validate_tool_use(&tool_use)
+        {
+            return Ok(result);
+        }
 
-        target_tool.call(tool_use)
+        tool.call(tool_use)
     }
 }
 
@@ -220,7 +668,7 @@ mod tests {
 
     #[test]
     fn default_tool_definition() {
-        let tool = ToolDefinition::default();
+        let tool = CustomToolDefinition::default();
         assert_eq!(tool.name, String::default());
         assert_eq!(tool.description, None);
         assert_eq!(
@@ -231,7 +679,7 @@ mod tests {
 
     #[test]
     fn display_tool_definition() {
-        let tool = ToolDefinition {
+        let tool = ToolDefinition::Custom(CustomToolDefinition {
             name: "tool".to_string(),
             description: Some("tool description".to_string()),
             input_schema: serde_json::json!({
@@ -244,7 +692,8 @@ mod tests {
                 "required": ["arg1"],
                 "type": "object",
             }),
-        };
+            cache_control: None,
+        });
         assert_eq!(
             tool.to_string(),
             r#"{
@@ -268,7 +717,7 @@ mod tests {
 
     #[test]
     fn serialize_tool_definition() {
-        let tool = ToolDefinition {
+        let tool = ToolDefinition::Custom(CustomToolDefinition {
             name: "tool".to_string(),
             description: Some("tool description".to_string()),
             input_schema: serde_json::json!({
@@ -281,16 +730,31 @@ mod tests {
                 "required": ["arg1"],
                 "type": "object",
             }),
-        };
+            cache_control: None,
+        });
         assert_eq!(
             serde_json::to_string(&tool).unwrap(),
             r#"{"name":"tool","description":"tool description","input_schema":{"properties":{"arg1":{"description":"First argument.","type":"integer"}},"required":["arg1"],"type":"object"}}"#,
         );
     }
 
+    #[test]
+    fn tool_definition_with_cache_control() {
+        let tool = ToolDefinition::new(
+            "tool",
+            Some("tool description"),
+            serde_json::json!({"type": "object"}),
+        )
+        .with_cache_control(CacheControl::ephemeral());
+        assert_eq!(
+            serde_json::to_string(&tool).unwrap(),
+            r#"{"name":"tool","description":"tool description","input_schema":{"type":"object"},"cache_control":{"type":"ephemeral"}}"#,
+        );
+    }
+
     #[test]
     fn deserialize_tool_definition() {
-        let tool = ToolDefinition {
+        let tool = ToolDefinition::Custom(CustomToolDefinition {
             name: "tool".to_string(),
             description: Some("tool description".to_string()),
             input_schema: serde_json::json!({
@@ -303,7 +767,8 @@ mod tests {
                 "required": ["arg1"],
                 "type": "object",
             }),
-        };
+            cache_control: None,
+        });
         assert_eq!(
             serde_json::from_str::<ToolDefinition>(
                 r#"{"name":"tool","description":"tool description","input_schema":{"properties":{"arg1":{"description":"First argument.","type":"integer"}},"required":["arg1"],"type":"object"}}"#
@@ -313,6 +778,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_anthropic_tool_definitions() {
+        assert_eq!(
+            serde_json::from_str::<ToolDefinition>(
+                r#"{"type":"custom","name":"tool","description":null,"input_schema":{"type":"object"}}"#
+            )
+            .unwrap(),
+            ToolDefinition::new(
+                "tool",
+                None::<String>,
+                serde_json::json!({"type": "object"}),
+            )
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolDefinition>(
+                r#"{"type":"bash_20250124","name":"bash"}"#
+            )
+            .unwrap(),
+            ToolDefinition::Bash(BashTool::new())
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolDefinition>(
+                r#"{"type":"text_editor_20250728","name":"str_replace_based_edit_tool"}"#
+            )
+            .unwrap(),
+            ToolDefinition::TextEditor(TextEditorTool::new())
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolDefinition>(
+                r#"{"type":"computer_20250124","name":"computer","display_width_px":1024,"display_height_px":768}"#
+            )
+            .unwrap(),
+            ToolDefinition::ComputerUse(ComputerUseTool::new(1024, 768))
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolDefinition>(
+                r#"{"type":"web_search_20250305","name":"web_search","max_uses":3}"#
+            )
+            .unwrap(),
+            ToolDefinition::WebSearch(WebSearchTool::new().with_max_uses(3))
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolDefinition>(
+                r#"{"type":"code_execution_20250522","name":"code_execution"}"#
+            )
+            .unwrap(),
+            ToolDefinition::CodeExecution(CodeExecutionTool::new())
+        );
+        assert!(serde_json::from_str::<ToolDefinition>(
+            r#"{"type":"unknown_20250101","name":"unknown"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn tool_definition_accessors() {
+        let tool = ToolDefinition::from(WebSearchTool::new())
+            .with_cache_control(CacheControl::ephemeral());
+        assert_eq!(tool.name(), "web_search");
+        assert!(tool.is_server_tool());
+        assert_eq!(
+            tool.cache_control(),
+            Some(&CacheControl::ephemeral())
+        );
+        assert_eq!(
+            serde_json::to_string(&tool).unwrap(),
+            r#"{"type":"web_search_20250305","name":"web_search","cache_control":{"type":"ephemeral"}}"#
+        );
+
+        let tool = ToolDefinition::from(BashTool::new());
+        assert_eq!(tool.name(), "bash");
+        assert!(!tool.is_server_tool());
+        assert_eq!(tool.cache_control(), None);
+    }
+
     #[test]
     fn default_tool_use() {
         let tool_use = ToolUse::default();
@@ -400,22 +940,22 @@ mod tests {
     fn display_tool_result() {
         let tool_result = ToolResult {
             tool_use_id: "id".to_string(),
-            content: Some(TextContentBlock::new("text")),
+            content: Some("text".into()),
             is_error: None,
         };
         assert_eq!(
             tool_result.to_string(),
-            "{\n  \"tool_use_id\": \"id\",\n  \"content\": {\n    \"type\": \"text\",\n    \"text\": \"text\"\n  }\n}"
+            "{\n  \"tool_use_id\": \"id\",\n  \"content\": \"text\"\n}"
         );
 
         let tool_result = ToolResult {
             tool_use_id: "id".to_string(),
-            content: Some(TextContentBlock::new("text")),
+            content: Some("text".into()),
             is_error: Some(true),
         };
         assert_eq!(
             tool_result.to_string(),
-            "{\n  \"tool_use_id\": \"id\",\n  \"content\": {\n    \"type\": \"text\",\n    \"text\": \"text\"\n  },\n  \"is_error\": true\n}"
+            "{\n  \"tool_use_id\": \"id\",\n  \"content\": \"text\",\n  \"is_error\": true\n}"
         );
     }
 
@@ -423,22 +963,22 @@ mod tests {
     fn serialize_tool_result() {
         let tool_result = ToolResult {
             tool_use_id: "id".to_string(),
-            content: Some(TextContentBlock::new("text")),
+            content: Some("text".into()),
             is_error: None,
         };
         assert_eq!(
             serde_json::to_string(&tool_result).unwrap(),
-            r#"{"tool_use_id":"id","content":{"type":"text","text":"text"}}"#
+            r#"{"tool_use_id":"id","content":"text"}"#
         );
 
         let tool_result = ToolResult {
             tool_use_id: "id".to_string(),
-            content: Some(TextContentBlock::new("text")),
+            content: Some("text".into()),
             is_error: Some(true),
         };
         assert_eq!(
             serde_json::to_string(&tool_result).unwrap(),
-            r#"{"tool_use_id":"id","content":{"type":"text","text":"text"},"is_error":true}"#
+            r#"{"tool_use_id":"id","content":"text","is_error":true}"#
         );
     }
 
@@ -446,12 +986,12 @@ mod tests {
     fn deserialize_tool_result() {
         let tool_result = ToolResult {
             tool_use_id: "id".to_string(),
-            content: Some(TextContentBlock::new("text")),
+            content: Some("text".into()),
             is_error: None,
         };
         assert_eq!(
             serde_json::from_str::<ToolResult>(
-                r#"{"tool_use_id":"id","content":{"type":"text","text":"text"}}"#
+                r#"{"tool_use_id":"id","content":"text"}"#
             )
             .unwrap(),
             tool_result
@@ -459,12 +999,12 @@ mod tests {
 
         let tool_result = ToolResult {
             tool_use_id: "id".to_string(),
-            content: Some(TextContentBlock::new("text")),
+            content: Some("text".into()),
             is_error: Some(true),
         };
         assert_eq!(
             serde_json::from_str::<ToolResult>(
-                r#"{"tool_use_id":"id","content":{"type":"text","text":"text"},"is_error":true}"#
+                r#"{"tool_use_id":"id","content":"text","is_error":true}"#
             )
             .unwrap(),
             tool_result
@@ -480,7 +1020,7 @@ mod tests {
         assert_eq!(tool_result.tool_use_id, "id");
         assert_eq!(
             tool_result.content,
-            Some(TextContentBlock::new("text"))
+            Some(ToolResultContent::from(TextContentBlock::new("text")))
         );
         assert_eq!(tool_result.is_error, None);
 
@@ -496,7 +1036,7 @@ mod tests {
         assert_eq!(tool_result.tool_use_id, "id");
         assert_eq!(
             tool_result.content,
-            Some(TextContentBlock::new("text"))
+            Some(ToolResultContent::from(TextContentBlock::new("text")))
         );
         assert_eq!(tool_result.is_error, Some(true));
 
@@ -506,13 +1046,61 @@ mod tests {
         assert_eq!(tool_result.is_error, Some(true));
     }
 
+    #[test]
+    fn tool_result_with_blocks() {
+        let tool_result = ToolResult::success(
+            "id",
+            Some(vec![
+                ToolResultBlock::from(TextContentBlock::new("chart")),
+                ToolResultBlock::from(ImageContentBlock::new(
+                    crate::messages::ImageContentSource::url(
+                        "https://example.com/chart.png",
+                    ),
+                )),
+            ]),
+        );
+        let json = r#"{"tool_use_id":"id","content":[{"type":"text","text":"chart"},{"type":"image","source":{"type":"url","url":"https://example.com/chart.png"}}]}"#;
+        assert_eq!(
+            serde_json::to_string(&tool_result).unwrap(),
+            json
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolResult>(json).unwrap(),
+            tool_result
+        );
+        assert_eq!(
+            tool_result
+                .content
+                .unwrap()
+                .flatten_into_text()
+                .unwrap(),
+            "chart"
+        );
+    }
+
+    #[test]
+    fn flatten_tool_result_content() {
+        assert_eq!(
+            ToolResultContent::from("text")
+                .flatten_into_text()
+                .unwrap(),
+            "text"
+        );
+        assert!(ToolResultContent::Blocks(vec![])
+            .flatten_into_text()
+            .is_err());
+        assert!(ToolResultContent::from(ImageContentBlock::default())
+            .flatten_into_text()
+            .is_err());
+    }
+
     #[test]
     fn tool_list() {
         struct TestTool {}
 
         impl Tool for TestTool {
             fn definition(&self) -> ToolDefinition {
-                ToolDefinition {
+                ToolDefinition::Custom(CustomToolDefinition {
                     name: "test_tool".to_string(),
                     description: Some("test tool description".to_string()),
                     input_schema: serde_json::json!({
@@ -525,7 +1113,8 @@ mod tests {
                         "required": ["arg1"],
                         "type": "object",
                     }),
-                }
+                    cache_control: None,
+                })
             }
 
             fn call(
@@ -545,9 +1134,10 @@ mod tests {
             input: serde_json::json!({"arg1": 42}),
         };
 
-        let tool_list = ToolList::new(vec![Box::new(
+        let mut tool_list = ToolList::new(vec![Box::new(
             TestTool {},
-        )]);
+        )])
+        .unwrap();
 
         let tool_result = tool_list
             .call(tool_use)
@@ -561,7 +1151,8 @@ mod tests {
             tool_result
                 .content
                 .unwrap()
-                .text,
+                .flatten_into_text()
+                .unwrap(),
             "1"
         );
 
@@ -572,6 +1163,44 @@ mod tests {
         };
 
         let tool_result = tool_list.call(tool_use);
-        assert!(tool_result.is_err())
+        assert!(tool_result.is_err());
+
+        #[cfg(feature = "validation")]
+        {
+            let tool_use = ToolUse {
+                id: "test_tool_use_id_invalid".to_string(),
+                name: "test_tool".to_string(),
+                input: serde_json::json!({"arg1": "42"}),
+            };
+
+            let tool_result = tool_list
+                .call(tool_use)
+                .unwrap();
+            assert_eq!(tool_result.is_error, Some(true));
+            assert!(tool_result
+                .content
+                .unwrap()
+                .flatten_into_text()
+                .unwrap()
+                .contains("- /arg1: "));
+        }
+
+        assert_eq!(
+            tool_list.add(Box::new(TestTool {})),
+            Err(ToolListError::DuplicateName(
+                "test_tool".to_string()
+            ))
+        );
+        assert!(ToolList::new(vec![
+            Box::new(TestTool {}),
+            Box::new(TestTool {}),
+        ])
+        .is_err());
+
+        assert!(tool_list.contains("test_tool"));
+        assert!(tool_list
+            .remove("test_tool")
+            .is_some());
+        assert!(tool_list.is_empty());
     }
 }
@@ -0,0 +1,267 @@
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+use crate::messages::ToolDefinition;
+use crate::ValidationError;
+use std::fmt::Display;
+
+/// How the model should use the provided tools.
+///
+/// See [controlling Claude's output](https://docs.anthropic.com/en/docs/build-with-claude/tool-use/implement-tool-use#controlling-claudes-output).
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::ToolChoice;
+///
+/// let tool_choice = ToolChoice::tool("get_weather");
+/// assert_eq!(tool_choice.name(), Some("get_weather"));
+///
+/// let tool_choice = ToolChoice::auto().disable_parallel_tool_use();
+/// assert_eq!(
+///     tool_choice.to_string(),
+///     "{\n  \"type\": \"auto\",\n  \"disable_parallel_tool_use\": true\n}"
+/// );
+/// ```
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct ToolChoice {
+    /// The type of the tool choice.
+    #[serde(rename = "type")]
+    _type: ToolChoiceType,
+    /// The name of the tool to use, only for the `tool` type.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    name: Option<String>,
+    /// Whether to disable the parallel tool use.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    disable_parallel_tool_use: Option<bool>,
+}
+
+impl_display_for_serialize!(ToolChoice);
+
+impl ToolChoice {
+    /// Allows the model to decide whether to use any provided tools.
+    pub fn auto() -> Self {
+        Self {
+            _type: ToolChoiceType::Auto,
+            name: None,
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Forces the model to use one of the provided tools.
+    pub fn any() -> Self {
+        Self {
+            _type: ToolChoiceType::Any,
+            name: None,
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Forces the model to use the tool of the name.
+    pub fn tool<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            _type: ToolChoiceType::Tool,
+            name: Some(name.into()),
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Prevents the model from using any tools.
+    pub fn none() -> Self {
+        Self {
+            _type: ToolChoiceType::None,
+            name: None,
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Disables the parallel tool use.
+    ///
+    /// The model uses at most one tool for `auto`, and exactly one tool for `any` and `tool`.
+    pub fn disable_parallel_tool_use(mut self) -> Self {
+        self.disable_parallel_tool_use = Some(true);
+        self
+    }
+
+    /// The type of the tool choice.
+    pub fn choice_type(&self) -> ToolChoiceType {
+        self._type
+    }
+
+    /// The name of the tool to use, only for the `tool` type.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Whether the parallel tool use is disabled.
+    pub fn is_parallel_tool_use_disabled(&self) -> bool {
+        self.disable_parallel_tool_use
+            .unwrap_or(false)
+    }
+
+    /// Validates that the tool of the name is defined in the tools.
+    ///
+    /// ## Arguments
+    /// - `tools` - The tool definitions of the request.
+    ///
+    /// ## Errors
+    /// It returns a validation error if the tool of the name is not defined.
+    pub fn validate(
+        &self,
+        tools: &[ToolDefinition],
+    ) -> Result<(), ValidationError<String>> {
+        match &self.name {
+            | Some(name) if !tools.iter().any(|tool| tool.name() == name) => {
+                Err(ValidationError {
+                    _type: "ToolChoice".to_string(),
+                    expected: format!(
+                        "The tool name must be one of the tools: [{}].",
+                        tools
+                            .iter()
+                            .map(|tool| tool.name())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    actual: name.clone(),
+                })
+            },
+            | _ => Ok(()),
+        }
+    }
+}
+
+/// The type of the tool choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ToolChoiceType {
+    /// auto
+    #[default]
+    Auto,
+    /// any
+    Any,
+    /// tool
+    Tool,
+    /// none
+    None,
+}
+
+impl Display for ToolChoiceType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | ToolChoiceType::Auto => {
+                write!(f, "auto")
+            },
+            | ToolChoiceType::Any => {
+                write!(f, "any")
+            },
+            | ToolChoiceType::Tool => {
+                write!(f, "tool")
+            },
+            | ToolChoiceType::None => {
+                write!(f, "none")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    ToolChoiceType,
+    Auto => "auto",
+    Any => "any",
+    Tool => "tool",
+    None => "none"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default() {
+        assert_eq!(ToolChoice::default(), ToolChoice::auto());
+        assert!(!ToolChoice::default().is_parallel_tool_use_disabled());
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            serde_json::to_string(&ToolChoice::auto()).unwrap(),
+            "{\"type\":\"auto\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&ToolChoice::any()).unwrap(),
+            "{\"type\":\"any\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&ToolChoice::tool("tool")).unwrap(),
+            "{\"type\":\"tool\",\"name\":\"tool\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&ToolChoice::none()).unwrap(),
+            "{\"type\":\"none\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(
+                &ToolChoice::any().disable_parallel_tool_use()
+            )
+            .unwrap(),
+            "{\"type\":\"any\",\"disable_parallel_tool_use\":true}"
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            serde_json::from_str::<ToolChoice>(
+                "{\"type\":\"tool\",\"name\":\"tool\",\"disable_parallel_tool_use\":true}"
+            )
+            .unwrap(),
+            ToolChoice::tool("tool").disable_parallel_tool_use()
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolChoice>("{\"type\":\"none\"}")
+                .unwrap(),
+            ToolChoice::none()
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let tools = vec![ToolDefinition::new(
+            "tool",
+            Some("description"),
+            serde_json::Value::Null,
+        )];
+
+        assert!(ToolChoice::tool("tool")
+            .validate(&tools)
+            .is_ok());
+        assert!(ToolChoice::auto()
+            .validate(&tools)
+            .is_ok());
+        assert!(ToolChoice::any()
+            .validate(&[])
+            .is_ok());
+
+        let error = ToolChoice::tool("unknown")
+            .validate(&tools)
+            .unwrap_err();
+        assert_eq!(error.actual, "unknown");
+        assert!(ToolChoice::tool("tool")
+            .validate(&[])
+            .is_err());
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+#[cfg(feature = "validation")]
+use std::sync::Arc;
+
+#[cfg(feature = "validation")]
+use crate::messages::tool_input_validation::{
+    validate_tool_use, CompiledInputSchema,
+};
+use crate::messages::{ToolDefinition, ToolListError, ToolResult, ToolUse};
+
+/// The tools indexed by the names of the definitions taken once when added.
+pub(crate) struct ToolIndex<T: ?Sized> {
+    entries: Vec<Entry<T>>,
+    index: HashMap<String, usize>,
+}
+
+struct Entry<T: ?Sized> {
+    definition: ToolDefinition,
+    /// The compiled input schema of a custom tool.
+    #[cfg(feature = "validation")]
+    input_schema: Option<Arc<CompiledInputSchema>>,
+    tool: Box<T>,
+}
+
+impl<T: ?Sized> ToolIndex<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        definition: ToolDefinition,
+        tool: Box<T>,
+    ) -> Result<(), ToolListError> {
+        let name = definition.name();
+        if !is_valid_tool_name(name) {
+            return Err(ToolListError::InvalidName(name.to_string()));
+        }
+        if self.index.contains_key(name) {
+            return Err(ToolListError::DuplicateName(name.to_string()));
+        }
+
+        #[cfg(feature = "validation")]
+        let input_schema = match &definition {
+            | ToolDefinition::Custom(custom) => Some(
+                CompiledInputSchema::get_or_compile(&custom.input_schema)
+                    .map_err(|message| ToolListError::InvalidSchema {
+                        name: name.to_string(),
+                        message,
+                    })?,
+            ),
+            | _ => None,
+        };
+
+        self.index
+            .insert(name.to_string(), self.entries.len());
+        self.entries.push(Entry {
+            definition,
+            #[cfg(feature = "validation")]
+            input_schema,
+            tool,
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn remove(
+        &mut self,
+        name: &str,
+    ) -> Option<Box<T>> {
+        let position = self.index.remove(name)?;
+        let entry = self.entries.remove(position);
+
+        for index in self.index.values_mut() {
+            if *index > position {
+                *index -= 1;
+            }
+        }
+
+        Some(entry.tool)
+    }
+
+    pub(crate) fn get(
+        &self,
+        name: &str,
+    ) -> Option<(&ToolDefinition, &T)> {
+        self.index
+            .get(name)
+            .map(|position| {
+                let entry = &self.entries[*position];
+                (&entry.definition, entry.tool.as_ref())
+            })
+    }
+
+    /// Validates the input of the tool use against the compiled input schema of the tool,
+    /// and creates the error result listing the violations if invalid.
+    ///
+    /// The input is not validated without the `validation` feature.
+    pub(crate) fn validate_tool_use(
+        &self,
+        tool_use: &ToolUse,
+    ) -> Option<ToolResult> {
+        #[cfg(feature = "validation")]
+        {
+            let position = self.index.get(&tool_use.name)?;
+            let input_schema = self.entries[*position]
+                .input_schema
+                .as_ref()?;
+            validate_tool_use(input_schema, tool_use)
+        }
+        #[cfg(not(feature = "validation"))]
+        {
+            let _ = tool_use;
+            None
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.definition.name())
+    }
+
+    pub(crate) fn definitions(&self) -> Vec<ToolDefinition> {
+        self.entries
+            .iter()
+            .map(|entry| entry.definition.clone())
+            .collect()
+    }
+
+    pub(crate) fn enabled_definitions<I, S>(
+        &self,
+        names: I,
+    ) -> Result<Vec<ToolDefinition>, ToolListError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        names
+            .into_iter()
+            .map(|name| {
+                self.get(name.as_ref())
+                    .map(|(definition, _)| definition.clone())
+                    .ok_or_else(|| {
+                        ToolListError::ToolNotFound(name.as_ref().to_string())
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Whether the name matches `^[a-zA-Z0-9_-]{1,64}$`.
+fn is_valid_tool_name(name: &str) -> bool {
+    (1..=64).contains(&name.len())
+        && name
+            .bytes()
+            .all(|byte| {
+                byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-'
+            })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(name: &str) -> ToolDefinition {
+        ToolDefinition::new(
+            name,
+            None::<String>,
+            serde_json::json!({"type": "object"}),
+        )
+    }
+
+    #[test]
+    fn valid_tool_name() {
+        assert!(is_valid_tool_name("get_weather"));
+        assert!(is_valid_tool_name("tool-1"));
+        assert!(is_valid_tool_name(&"a".repeat(64)));
+        assert!(!is_valid_tool_name(""));
+        assert!(!is_valid_tool_name(&"a".repeat(65)));
+        assert!(!is_valid_tool_name("get weather"));
+        assert!(!is_valid_tool_name("天気"));
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut index = ToolIndex::<str>::new();
+        for name in ["first", "second", "third"] {
+            index
+                .insert(definition(name), name.into())
+                .unwrap();
+        }
+
+        assert!(matches!(
+            index.insert(definition("second"), "duplicate".into()),
+            Err(ToolListError::DuplicateName(name)) if name == "second"
+        ));
+        assert!(matches!(
+            index.insert(definition("invalid name"), "invalid".into()),
+            Err(ToolListError::InvalidName(_))
+        ));
+        #[cfg(feature = "validation")]
+        assert!(matches!(
+            index.insert(
+                ToolDefinition::new(
+                    "invalid_schema",
+                    None::<String>,
+                    serde_json::json!({"type": 1}),
+                ),
+                "invalid".into()
+            ),
+            Err(ToolListError::InvalidSchema { name, .. }) if name == "invalid_schema"
+        ));
+
+        assert_eq!(
+            index.remove("first").as_deref(),
+            Some("first")
+        );
+        assert!(index.remove("first").is_none());
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            index.names().collect::<Vec<_>>(),
+            vec!["second", "third"]
+        );
+        assert_eq!(
+            index
+                .get("third")
+                .map(|(_, tool)| tool),
+            Some("third")
+        );
+    }
+
+    #[test]
+    fn enabled_definitions() {
+        let mut index = ToolIndex::<str>::new();
+        for name in ["first", "second", "third"] {
+            index
+                .insert(definition(name), name.into())
+                .unwrap();
+        }
+
+        assert_eq!(
+            index
+                .enabled_definitions(["third", "first"])
+                .unwrap(),
+            vec![definition("third"), definition("first")]
+        );
+        assert!(matches!(
+            index.enabled_definitions(["fourth"]),
+            Err(ToolListError::ToolNotFound(name)) if name == "fourth"
+        ));
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use valico::json_schema::{Scope, ValidationState};
+
+use crate::messages::{
+    CustomToolDefinition, ToolDefinition, ToolResult, ToolResultContent,
+    ToolUse,
+};
+
+/// A violation of the input schema of a tool.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchemaViolation {
+    /// The JSON pointer to the violating value, e.g. `/location`. It is empty for the root.
+    pub path: String,
+    /// The description of the violation.
+    pub message: String,
+}
+
+impl Display for SchemaViolation {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+impl CustomToolDefinition {
+    /// Validates the input of a tool use against the input schema.
+    ///
+    /// The input schema is compiled once and shared with the tool lists.
+    ///
+    /// ## Errors
+    /// It returns all violations of the input schema, or a violation at the root if the input schema itself is invalid.
+    pub fn validate_input(
+        &self,
+        input: &serde_json::Value,
+    ) -> Result<(), Vec<SchemaViolation>> {
+        CompiledInputSchema::get_or_compile(&self.input_schema)
+            .map_err(|message| {
+                vec![SchemaViolation {
+                    path: String::new(),
+                    message: format!(
+                        "The input schema is invalid: {}",
+                        message
+                    ),
+                }]
+            })?
+            .validate(input)
+    }
+}
+
+impl ToolDefinition {
+    /// Validates the input of a tool use against the input schema of a custom tool.
+    ///
+    /// The inputs of the Anthropic-defined tools are always valid.
+    ///
+    /// ## Errors
+    /// It returns all violations of the input schema.
+    pub fn validate_input(
+        &self,
+        input: &serde_json::Value,
+    ) -> Result<(), Vec<SchemaViolation>> {
+        match self {
+            | ToolDefinition::Custom(tool) => tool.validate_input(input),
+            | _ => Ok(()),
+        }
+    }
+}
+
+/// The maximum number of the compiled input schemas kept in the cache.
+const MAX_CACHED_INPUT_SCHEMAS: usize = 256;
+
+/// The cache of the compiled input schemas keyed by the serialized input schemas.
+static COMPILED_INPUT_SCHEMAS: OnceLock<
+    Mutex<HashMap<String, Arc<CompiledInputSchema>>>,
+> = OnceLock::new();
+
+/// The input schema of a custom tool compiled once and shared by the definitions and the tool lists.
+pub(crate) struct CompiledInputSchema {
+    // The compiled schema has interior mutability and is not `Sync`.
+    scope: Mutex<Scope>,
+    id: reqwest::Url,
+}
+
+impl CompiledInputSchema {
+    /// Gets the compiled input schema from the cache, or compiles and caches it.
+    ///
+    /// The cache is cleared when it is full. The invalid input schemas are not cached.
+    pub(crate) fn get_or_compile(
+        input_schema: &serde_json::Value
+    ) -> Result<Arc<Self>, String> {
+        let key = input_schema.to_string();
+        let mut cache = COMPILED_INPUT_SCHEMAS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(compiled) = cache.get(&key) {
+            return Ok(Arc::clone(compiled));
+        }
+
+        let compiled = Arc::new(Self::compile(input_schema)?);
+        if cache.len() >= MAX_CACHED_INPUT_SCHEMAS {
+            cache.clear();
+        }
+        cache.insert(key, Arc::clone(&compiled));
+
+        Ok(compiled)
+    }
+
+    /// Compiles the input schema, or returns the description of the error if the input schema is invalid.
+    pub(crate) fn compile(
+        input_schema: &serde_json::Value
+    ) -> Result<Self, String> {
+        let mut scope = Scope::new();
+        let id = scope
+            .compile(input_schema.clone(), false)
+            .map_err(|error| error.to_string())?;
+
+        Ok(Self {
+            scope: Mutex::new(scope),
+            id,
+        })
+    }
+
+    /// Validates the input of a tool use.
+    ///
+    /// ## Errors
+    /// It returns all violations of the input schema.
+    pub(crate) fn validate(
+        &self,
+        input: &serde_json::Value,
+    ) -> Result<(), Vec<SchemaViolation>> {
+        let scope = self
+            .scope
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match scope.resolve(&self.id) {
+            | Some(schema) => into_result(schema.validate(input)),
+            | None => Ok(()),
+        }
+    }
+}
+
+fn into_result(state: ValidationState) -> Result<(), Vec<SchemaViolation>> {
+    if state.is_valid() {
+        return Ok(());
+    }
+
+    Err(state
+        .errors
+        .iter()
+        .map(|error| SchemaViolation {
+            path: error.get_path().to_string(),
+            message: match error.get_detail() {
+                | Some(detail) => {
+                    format!("{}: {}", error.get_title(), detail)
+                },
+                | None => error.get_title().to_string(),
+            },
+        })
+        .collect())
+}
+
+/// Validates the input of the tool use against the compiled input schema, and creates the error result listing the violations if invalid.
+pub(crate) fn validate_tool_use(
+    input_schema: &CompiledInputSchema,
+    tool_use: &ToolUse,
+) -> Option<ToolResult> {
+    let violations = input_schema
+        .validate(&tool_use.input)
+        .err()?;
+
+    let mut message = format!(
+        "The input of the tool `{}` does not match the input schema:",
+        tool_use.name
+    );
+    for violation in violations {
+        message.push_str("\n- ");
+        message.push_str(&violation.to_string());
+    }
+
+    Some(ToolResult::error(
+        tool_use.id.clone(),
+        Some(ToolResultContent::Text(message)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "location": {"type": "string"},
+                "days": {"type": "integer", "minimum": 1}
+            },
+            "required": ["location"]
+        })
+    }
+
+    fn definition() -> ToolDefinition {
+        ToolDefinition::new("weather", None::<String>, input_schema())
+    }
+
+    #[test]
+    fn validate_input() {
+        assert_eq!(
+            definition().validate_input(&serde_json::json!({
+                "location": "Tokyo",
+                "days": 3
+            })),
+            Ok(())
+        );
+
+        let violations = definition()
+            .validate_input(&serde_json::json!({"days": 0}))
+            .unwrap_err();
+        assert_eq!(
+            violations
+                .iter()
+                .map(|violation| violation.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["/days", "/location"]
+        );
+
+        let violations = ToolDefinition::new(
+            "invalid",
+            None::<String>,
+            serde_json::json!({"type": 1}),
+        )
+        .validate_input(&serde_json::json!({}))
+        .unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "");
+    }
+
+    #[test]
+    fn get_or_compile_input_schema() {
+        let compiled =
+            CompiledInputSchema::get_or_compile(&input_schema()).unwrap();
+        assert!(Arc::ptr_eq(
+            &compiled,
+            &CompiledInputSchema::get_or_compile(&input_schema()).unwrap()
+        ));
+        assert!(CompiledInputSchema::get_or_compile(
+            &serde_json::json!({"type": 1})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn compile_input_schema() {
+        assert!(
+            CompiledInputSchema::compile(&serde_json::json!({"type": 1}))
+                .is_err()
+        );
+
+        let input_schema =
+            CompiledInputSchema::compile(&input_schema()).unwrap();
+        assert_eq!(
+            input_schema
+                .validate(&serde_json::json!({"location": "Tokyo"})),
+            Ok(())
+        );
+        assert_eq!(
+            input_schema
+                .validate(&serde_json::json!({"days": 0}))
+                .unwrap_err()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn validate_tool_use_result() {
+        assert_eq!(
+            validate_tool_use(
+                &CompiledInputSchema::compile(&input_schema()).unwrap(),
+                &ToolUse::new(
+                    "toolu_01",
+                    "weather",
+                    serde_json::json!({"location": "Tokyo"}),
+                ),
+            ),
+            None
+        );
+
+        let result = validate_tool_use(
+            &CompiledInputSchema::compile(&input_schema()).unwrap(),
+            &ToolUse::new(
+                "toolu_01",
+                "weather",
+                serde_json::json!({"location": 1}),
+            ),
+        )
+        .unwrap();
+        assert_eq!(result.tool_use_id, "toolu_01");
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            result
+                .content
+                .unwrap()
+                .flatten_into_text()
+                .unwrap(),
+            "The input of the tool `weather` does not match the input schema:\n- /location: Type of the value is wrong: The value must be string"
+        );
+    }
+}
@@ -0,0 +1,662 @@
+use crate::messages::{
+    AsyncToolList, Content, ContentBlock, Message, MessageStreamExt,
+    MessageStreamHandler, MessagesRequestBody, MessagesResponseBody,
+    StopReason, StreamOption, ToolResult, ToolRunnerError, Usage,
+};
+use crate::Client;
+
+/// The hooks called at each step of `ToolRunner`.
+///
+/// The step is the 1-based number of the request in the run.
+///
+/// ## Example
+/// ```rust
+/// use clust::messages::{MessagesResponseBody, ToolResult, ToolRunnerHook};
+///
+/// struct Logger;
+///
+/// impl ToolRunnerHook for Logger {
+///     fn after_response(&mut self, step: usize, response: &MessagesResponseBody) {
+///         println!("Step {}: {}", step, response.usage);
+///     }
+///
+///     fn after_tool_results(&mut self, step: usize, results: &[ToolResult]) {
+///         println!("Step {}: {} tool results", step, results.len());
+///     }
+/// }
+/// ```
+pub trait ToolRunnerHook {
+    /// Called before sending each request.
+    #[allow(unused_variables)]
+    fn before_request(
+        &mut self,
+        step: usize,
+        request: &MessagesRequestBody,
+    ) {
+    }
+
+    /// Called at each text delta in the streaming mode.
+    #[allow(unused_variables)]
+    fn on_text(
+        &mut self,
+        step: usize,
+        text: &str,
+    ) {
+    }
+
+    /// Called after receiving each response.
+    #[allow(unused_variables)]
+    fn after_response(
+        &mut self,
+        step: usize,
+        response: &MessagesResponseBody,
+    ) {
+    }
+
+    /// Called after calling the tools of each response.
+    #[allow(unused_variables)]
+    fn after_tool_results(
+        &mut self,
+        step: usize,
+        results: &[ToolResult],
+    ) {
+    }
+}
+
+/// The status of the end of a tool run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToolRunStatus {
+    /// The assistant stopped with a reason other than the tool use.
+    Completed,
+    /// The number of requests reached the max iterations.
+    MaxIterationsReached,
+    /// The summed usage reached the token budget.
+    TokenBudgetExceeded,
+}
+
+/// The result of a tool run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolRun {
+    /// The full transcript including the input messages, the responses and the tool results.
+    pub messages: Vec<Message>,
+    /// The last response.
+    pub response: MessagesResponseBody,
+    /// The summed usage of all responses.
+    pub usage: Usage,
+    /// The number of requests sent.
+    pub iterations: usize,
+    /// The status of the end of the run.
+    pub status: ToolRunStatus,
+}
+
+/// The runner of the tool use loop that sends the request, calls the tools of the response,
+/// appends the tool results and resends until the assistant stops with a reason other than the tool use.
+///
+/// A response with `StopReason::PauseTurn` is also resent to continue the turn.
+///
+/// The limits are checked after the tool results are appended,
+/// so the transcript of a stopped run can be resent as it is.
+///
+/// ## Example
+/// ```rust,no_run
+/// use clust::messages::{
+///     AsyncToolList, ClaudeModel, MaxTokens, Message, MessagesRequestBody,
+///     ToolRunner,
+/// };
+/// use clust::Client;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = Client::from_env()?;
+///     let tools = AsyncToolList::new(vec![])?; // Your tools
+///     let request_body = MessagesRequestBody {
+///         model: ClaudeModel::Claude35Sonnet20240620,
+///         messages: vec![Message::user("What is the weather like in San Francisco?")],
+///         max_tokens: MaxTokens::new(1024, ClaudeModel::Claude35Sonnet20240620)?,
+///         ..Default::default()
+///     };
+///
+///     let run = ToolRunner::new(&client, &tools)
+///         .max_iterations(5)
+///         .token_budget(Some(50_000))
+///         .run(request_body)
+///         .await?;
+///
+///     println!("Result:\n{}", run.response);
+///     println!("Usage: {}", run.usage);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct ToolRunner<'a> {
+    client: &'a Client,
+    tools: &'a AsyncToolList,
+    max_iterations: usize,
+    token_budget: Option<u32>,
+    hook: Box<dyn ToolRunnerHook + Send + 'a>,
+}
+
+impl<'a> ToolRunner<'a> {
+    /// Creates a new runner with the default max iterations: 10 and no token budget.
+    ///
+    /// ## Arguments
+    /// - `client` - The API client.
+    /// - `tools` - The tools to call.
+    pub fn new(
+        client: &'a Client,
+        tools: &'a AsyncToolList,
+    ) -> Self {
+        Self {
+            client,
+            tools,
+            max_iterations: 10,
+            token_budget: None,
+            hook: Box::new(NoHook),
+        }
+    }
+
+    /// Sets the max number of requests of a run. At least one request is sent.
+    pub fn max_iterations(
+        mut self,
+        max_iterations: usize,
+    ) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the budget of the total tokens of the summed usage, see `Usage::total_tokens`.
+    pub fn token_budget(
+        mut self,
+        token_budget: Option<u32>,
+    ) -> Self {
+        self.token_budget = token_budget;
+        self
+    }
+
+    /// Sets the hooks called at each step.
+    pub fn hook<H>(
+        mut self,
+        hook: H,
+    ) -> Self
+    where
+        H: ToolRunnerHook + Send + 'a,
+    {
+        self.hook = Box::new(hook);
+        self
+    }
+
+    /// Runs the tool use loop with `Client::create_a_message`.
+    ///
+    /// The tool definitions are set to the request if the request has no tools.
+    ///
+    /// ## Arguments
+    /// - `request_body` - The first request body.
+    pub async fn run(
+        &mut self,
+        request_body: MessagesRequestBody,
+    ) -> Result<ToolRun, ToolRunnerError> {
+        let client = self.client;
+        self.drive(&UnarySender(client), request_body)
+            .await
+    }
+
+    /// Runs the tool use loop with `Client::create_a_message_stream`,
+    /// calling `ToolRunnerHook::on_text` at each text delta.
+    ///
+    /// The tool definitions are set to the request if the request has no tools.
+    ///
+    /// ## Arguments
+    /// - `request_body` - The first request body.
+    pub async fn run_stream(
+        &mut self,
+        request_body: MessagesRequestBody,
+    ) -> Result<ToolRun, ToolRunnerError> {
+        let client = self.client;
+        self.drive(&StreamingSender(client), request_body)
+            .await
+    }
+
+    async fn drive<T>(
+        &mut self,
+        sender: &T,
+        mut request_body: MessagesRequestBody,
+    ) -> Result<ToolRun, ToolRunnerError>
+    where
+        T: MessageSender,
+    {
+        if request_body.tools.is_none() {
+            request_body.tools = Some(self.tools.definitions());
+        }
+        request_body.stream = sender.stream_option();
+
+        let mut usage = Usage::default();
+        let mut iterations = 0;
+
+        loop {
+            iterations += 1;
+
+            self.hook
+                .before_request(iterations, &request_body);
+            let response = sender
+                .send(
+                    request_body.clone(),
+                    StepHandler {
+                        hook: &mut *self.hook,
+                        step: iterations,
+                    },
+                )
+                .await?;
+            usage += response.usage;
+            self.hook
+                .after_response(iterations, &response);
+
+            request_body
+                .messages
+                .push(response.clone().crate_message());
+
+            match response.stop_reason {
+                | Some(StopReason::ToolUse) => {
+                    let results = self
+                        .tools
+                        .call_all_in(&response)
+                        .await;
+                    self.hook
+                        .after_tool_results(iterations, &results);
+
+                    request_body
+                        .messages
+                        .push(Message::user(Content::MultipleBlocks(
+                            results
+                                .into_iter()
+                                .map(ContentBlock::from)
+                                .collect(),
+                        )));
+                },
+                | Some(StopReason::PauseTurn) => {},
+                | _ => {
+                    return Ok(ToolRun {
+                        messages: request_body.messages,
+                        response,
+                        usage,
+                        iterations,
+                        status: ToolRunStatus::Completed,
+                    });
+                },
+            }
+
+            let status = if iterations >= self.max_iterations {
+                ToolRunStatus::MaxIterationsReached
+            } else if self
+                .token_budget
+                .is_some_and(|budget| usage.total_tokens() >= budget)
+            {
+                ToolRunStatus::TokenBudgetExceeded
+            } else {
+                continue;
+            };
+
+            return Ok(ToolRun {
+                messages: request_body.messages,
+                response,
+                usage,
+                iterations,
+                status,
+            });
+        }
+    }
+}
+
+/// The hook that does nothing.
+struct NoHook;
+
+impl ToolRunnerHook for NoHook {}
+
+/// The stream handler that forwards the text deltas to the hook with the step.
+struct StepHandler<'h> {
+    hook: &'h mut (dyn ToolRunnerHook + Send),
+    step: usize,
+}
+
+impl MessageStreamHandler for StepHandler<'_> {
+    fn on_text(
+        &mut self,
+        text: &str,
+    ) {
+        self.hook.on_text(self.step, text);
+    }
+}
+
+/// The sender of a request of the tool use loop.
+trait MessageSender {
+    fn stream_option(&self) -> Option<StreamOption>;
+
+    async fn send(
+        &self,
+        request_body: MessagesRequestBody,
+        handler: StepHandler<'_>,
+    ) -> Result<MessagesResponseBody, ToolRunnerError>;
+}
+
+struct UnarySender<'a>(&'a Client);
+
+impl MessageSender for UnarySender<'_> {
+    fn stream_option(&self) -> Option<StreamOption> {
+        None
+    }
+
+    async fn send(
+        &self,
+        request_body: MessagesRequestBody,
+        _handler: StepHandler<'_>,
+    ) -> Result<MessagesResponseBody, ToolRunnerError> {
+        Ok(self
+            .0
+            .create_a_message(request_body)
+            .await?)
+    }
+}
+
+struct StreamingSender<'a>(&'a Client);
+
+impl MessageSender for StreamingSender<'_> {
+    fn stream_option(&self) -> Option<StreamOption> {
+        Some(StreamOption::ReturnStream)
+    }
+
+    async fn send(
+        &self,
+        request_body: MessagesRequestBody,
+        handler: StepHandler<'_>,
+    ) -> Result<MessagesResponseBody, ToolRunnerError> {
+        Ok(self
+            .0
+            .create_a_message_stream(request_body)
+            .await?
+            .handle(handler)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        AsyncTool, ToolCallError, ToolDefinition, ToolUse, ToolUseContentBlock,
+    };
+    use crate::ApiKey;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    struct Weather;
+
+    impl AsyncTool for Weather {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition::new(
+                "weather",
+                None::<String>,
+                serde_json::json!({"type": "object"}),
+            )
+        }
+
+        async fn call(
+            &self,
+            tool_use: ToolUse,
+        ) -> Result<ToolResult, ToolCallError> {
+            Ok(ToolResult::success(tool_use.id, Some("Sunny")))
+        }
+    }
+
+    /// The sender that returns the scripted responses and records the requests.
+    struct ScriptedSender {
+        responses: Mutex<VecDeque<MessagesResponseBody>>,
+        requests: Mutex<Vec<MessagesRequestBody>>,
+    }
+
+    impl ScriptedSender {
+        fn new(responses: Vec<MessagesResponseBody>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into()),
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl MessageSender for ScriptedSender {
+        fn stream_option(&self) -> Option<StreamOption> {
+            None
+        }
+
+        async fn send(
+            &self,
+            request_body: MessagesRequestBody,
+            mut handler: StepHandler<'_>,
+        ) -> Result<MessagesResponseBody, ToolRunnerError> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(request_body);
+            let response = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            handler.on_text(&response.content.concatenated_text());
+            Ok(response)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingHook {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ToolRunnerHook for RecordingHook {
+        fn before_request(
+            &mut self,
+            step: usize,
+            request: &MessagesRequestBody,
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!(
+                    "request {}: {} messages",
+                    step,
+                    request.messages.len()
+                ));
+        }
+
+        fn on_text(
+            &mut self,
+            step: usize,
+            text: &str,
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("text {}: {}", step, text));
+        }
+
+        fn after_response(
+            &mut self,
+            step: usize,
+            response: &MessagesResponseBody,
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!(
+                    "response {}: {:?}",
+                    step, response.stop_reason
+                ));
+        }
+
+        fn after_tool_results(
+            &mut self,
+            step: usize,
+            results: &[ToolResult],
+        ) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("tool results {}: {}", step, results.len()));
+        }
+    }
+
+    fn tool_use_response(id: &str) -> MessagesResponseBody {
+        MessagesResponseBody {
+            content: Content::MultipleBlocks(vec![ContentBlock::ToolUse(
+                ToolUseContentBlock::new(ToolUse::new(
+                    id,
+                    "weather",
+                    serde_json::json!({}),
+                )),
+            )]),
+            stop_reason: Some(StopReason::ToolUse),
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 5,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn end_turn_response() -> MessagesResponseBody {
+        MessagesResponseBody {
+            content: Content::MultipleBlocks(vec!["Sunny".into()]),
+            stop_reason: Some(StopReason::EndTurn),
+            usage: Usage {
+                input_tokens: 20,
+                output_tokens: 2,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn request_body() -> MessagesRequestBody {
+        MessagesRequestBody {
+            messages: vec![Message::user("What is the weather?")],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn run_until_end_turn() {
+        let client = Client::from_api_key(ApiKey::new("api-key"));
+        let tools = AsyncToolList::new(vec![Box::new(Weather)])
+            .unwrap();
+        let hook = RecordingHook::default();
+        let events = hook.events.clone();
+        let sender = ScriptedSender::new(vec![
+            tool_use_response("toolu_01"),
+            tool_use_response("toolu_02"),
+            end_turn_response(),
+        ]);
+
+        let run = ToolRunner::new(&client, &tools)
+            .hook(hook)
+            .drive(&sender, request_body())
+            .await
+            .unwrap();
+
+        assert_eq!(run.status, ToolRunStatus::Completed);
+        assert_eq!(run.iterations, 3);
+        assert_eq!(run.response, end_turn_response());
+        assert_eq!(run.usage.input_tokens, 40);
+        assert_eq!(run.usage.output_tokens, 12);
+        assert_eq!(run.messages.len(), 6);
+        assert_eq!(
+            run.messages[2],
+            Message::user(ToolResult::success("toolu_01", Some("Sunny")))
+        );
+        assert_eq!(
+            run.messages[5],
+            end_turn_response().crate_message()
+        );
+
+        let requests = sender.requests.into_inner().unwrap();
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| request.messages.len())
+                .collect::<Vec<_>>(),
+            vec![1, 3, 5]
+        );
+        assert_eq!(
+            requests[0].tools,
+            Some(tools.definitions())
+        );
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "request 1: 1 messages",
+                "text 1: ",
+                "response 1: Some(ToolUse)",
+                "tool results 1: 1",
+                "request 2: 3 messages",
+                "text 2: ",
+                "response 2: Some(ToolUse)",
+                "tool results 2: 1",
+                "request 3: 5 messages",
+                "text 3: Sunny",
+                "response 3: Some(EndTurn)",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stop_at_limits() {
+        let client = Client::from_api_key(ApiKey::new("api-key"));
+        let tools = AsyncToolList::new(vec![Box::new(Weather)])
+            .unwrap();
+
+        let run = ToolRunner::new(&client, &tools)
+            .max_iterations(2)
+            .drive(
+                &ScriptedSender::new(vec![
+                    tool_use_response("toolu_01"),
+                    tool_use_response("toolu_02"),
+                    tool_use_response("toolu_03"),
+                ]),
+                request_body(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(run.status, ToolRunStatus::MaxIterationsReached);
+        assert_eq!(run.iterations, 2);
+        assert_eq!(run.messages.len(), 5);
+
+        let run = ToolRunner::new(&client, &tools)
+            .token_budget(Some(30))
+            .drive(
+                &ScriptedSender::new(vec![
+                    tool_use_response("toolu_01"),
+                    tool_use_response("toolu_02"),
+                    tool_use_response("toolu_03"),
+                ]),
+                request_body(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(run.status, ToolRunStatus::TokenBudgetExceeded);
+        assert_eq!(run.iterations, 2);
+        assert_eq!(run.usage.total_tokens(), 30);
+    }
+
+    #[test]
+    fn run_is_send() {
+        fn assert_send<T: Send>(_: T) {}
+
+        let client = Client::from_api_key(ApiKey::new("api-key"));
+        let tools = AsyncToolList::new(vec![]).unwrap();
+        let mut runner = ToolRunner::new(&client, &tools);
+        assert_send(runner.run(request_body()));
+        assert_send(runner.run_stream(request_body()));
+    }
+}
@@ -23,10 +23,85 @@ pub struct Usage {
     pub input_tokens: u32,
     /// The number of output tokens which were used.
     pub output_tokens: u32,
+    /// The number of input tokens used to create the cache entry.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// The number of input tokens read from the cache.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cache_read_input_tokens: Option<u32>,
 }
 
 impl_display_for_serialize!(Usage);
 
+impl Usage {
+    /// The total number of tokens including the cache creation and read input tokens.
+    ///
+    /// It saturates at `u32::MAX` instead of overflowing.
+    pub fn total_tokens(&self) -> u32 {
+        self.input_tokens
+            .saturating_add(self.output_tokens)
+            .saturating_add(
+                self.cache_creation_input_tokens
+                    .unwrap_or_default(),
+            )
+            .saturating_add(
+                self.cache_read_input_tokens
+                    .unwrap_or_default(),
+            )
+    }
+}
+
+/// Sums the numbers of tokens, saturating at `u32::MAX` instead of overflowing.
+impl std::ops::Add for Usage {
+    type Output = Self;
+
+    fn add(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Self {
+            input_tokens: self
+                .input_tokens
+                .saturating_add(rhs.input_tokens),
+            output_tokens: self
+                .output_tokens
+                .saturating_add(rhs.output_tokens),
+            cache_creation_input_tokens: add_optional(
+                self.cache_creation_input_tokens,
+                rhs.cache_creation_input_tokens,
+            ),
+            cache_read_input_tokens: add_optional(
+                self.cache_read_input_tokens,
+                rhs.cache_read_input_tokens,
+            ),
+        }
+    }
+}
+
+impl std::ops::AddAssign for Usage {
+    fn add_assign(
+        &mut self,
+        rhs: Self,
+    ) {
+        *self = *self + rhs;
+    }
+}
+
+fn add_optional(
+    lhs: Option<u32>,
+    rhs: Option<u32>,
+) -> Option<u32> {
+    match (lhs, rhs) {
+        | (None, None) => None,
+        | (lhs, rhs) => {
+            Some(
+                lhs.unwrap_or_default()
+                    .saturating_add(rhs.unwrap_or_default()),
+            )
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,6 +111,8 @@ mod tests {
         let usage = Usage {
             input_tokens: 1,
             output_tokens: 2,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
         };
         assert_eq!(
             serde_json::to_string(&usage).unwrap(),
@@ -48,6 +125,8 @@ mod tests {
         let usage = Usage {
             input_tokens: 1,
             output_tokens: 2,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
         };
         assert_eq!(
             serde_json::from_str::<Usage>(
@@ -57,4 +136,75 @@ mod tests {
             usage
         );
     }
+
+    #[test]
+    fn deserialize_with_cache() {
+        let usage = Usage {
+            input_tokens: 1,
+            output_tokens: 2,
+            cache_creation_input_tokens: Some(3),
+            cache_read_input_tokens: Some(4),
+        };
+        assert_eq!(
+            serde_json::from_str::<Usage>(
+                r#"{"input_tokens":1,"output_tokens":2,"cache_creation_input_tokens":3,"cache_read_input_tokens":4}"#
+            )
+            .unwrap(),
+            usage
+        );
+        assert_eq!(
+            serde_json::to_string(&usage).unwrap(),
+            r#"{"input_tokens":1,"output_tokens":2,"cache_creation_input_tokens":3,"cache_read_input_tokens":4}"#
+        );
+    }
+
+    #[test]
+    fn add() {
+        let mut usage = Usage {
+            input_tokens: 1,
+            output_tokens: 2,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: Some(3),
+        };
+        usage += Usage {
+            input_tokens: 4,
+            output_tokens: 5,
+            cache_creation_input_tokens: Some(6),
+            cache_read_input_tokens: None,
+        };
+        assert_eq!(
+            usage,
+            Usage {
+                input_tokens: 5,
+                output_tokens: 7,
+                cache_creation_input_tokens: Some(6),
+                cache_read_input_tokens: Some(3),
+            }
+        );
+        assert_eq!(usage.total_tokens(), 21);
+        assert_eq!(
+            (Usage::default() + Usage::default()).cache_read_input_tokens,
+            None
+        );
+    }
+
+    #[test]
+    fn add_saturating() {
+        let usage = Usage {
+            input_tokens: u32::MAX,
+            output_tokens: 1,
+            cache_creation_input_tokens: Some(u32::MAX),
+            cache_read_input_tokens: None,
+        };
+        assert_eq!(usage.total_tokens(), u32::MAX);
+        assert_eq!(
+            usage + usage,
+            Usage {
+                input_tokens: u32::MAX,
+                output_tokens: 2,
+                cache_creation_input_tokens: Some(u32::MAX),
+                cache_read_input_tokens: None,
+            }
+        );
+    }
 }
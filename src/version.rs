@@ -3,20 +3,17 @@ use std::fmt::Display;
 /// The API version.
 ///
 /// See also [the API reference](https://docs.anthropic.com/claude/reference/versions).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default,
+)]
 pub enum Version {
     /// 2023-01-01
     V2023_01_01,
     /// 2023-06-01
+    #[default]
     V2023_06_01,
 }
 
-impl Default for Version {
-    fn default() -> Self {
-        Self::V2023_06_01
-    }
-}
-
 impl Display for Version {
     fn fmt(
         &self,